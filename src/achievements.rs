@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+
+use crate::{engine::GameEvent, persist, Game};
+
+/// Size a plant has to reach for "Giant".
+const GIANT_SIZE: u32 = 20;
+
+/// A goal a profile meets once, checked against what the engine reports.
+pub struct Achievement {
+    /// Key in the profile, never changes once released
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Whether `event`, with the run as it is afterwards, earns it
+    earned_by: fn(&GameEvent, &Game) -> bool,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 4] = [
+    Achievement {
+        id: "first_harvest",
+        name: "First Harvest",
+        description: "Harvest a plant",
+        earned_by: first_harvest,
+    },
+    Achievement {
+        id: "giant",
+        name: "Giant",
+        description: "Grow a plant to size 20",
+        earned_by: giant,
+    },
+    Achievement {
+        id: "no_compost",
+        name: "Waste Not",
+        description: "Finish a run without composting a card",
+        earned_by: no_compost,
+    },
+    Achievement {
+        id: "full_board",
+        name: "Wall to Wall",
+        description: "Fill every tile that takes a plant",
+        earned_by: full_board,
+    },
+];
+
+fn first_harvest(event: &GameEvent, _: &Game) -> bool {
+    matches!(event, GameEvent::Harvested { .. })
+}
+
+fn giant(event: &GameEvent, game: &Game) -> bool {
+    matches!(event, GameEvent::RoundEnded { .. }) && game.records.biggest_size >= GIANT_SIZE
+}
+
+fn no_compost(event: &GameEvent, game: &Game) -> bool {
+    matches!(event, GameEvent::GameOver { .. }) && game.composted == 0
+}
+
+fn full_board(_: &GameEvent, game: &Game) -> bool {
+    game.board_full()
+}
+
+/// Unix time every earned achievement was unlocked at, by id.
+pub type Unlocked = BTreeMap<String, u64>;
+
+/// Unlocks what `events` earned and returns those achievements, once each.
+pub fn check(
+    unlocked: &mut Unlocked,
+    events: &[GameEvent],
+    game: &Game,
+) -> Vec<&'static Achievement> {
+    let mut earned = Vec::new();
+    for achievement in &ACHIEVEMENTS {
+        if unlocked.contains_key(achievement.id)
+            || !events
+                .iter()
+                .any(|event| (achievement.earned_by)(event, game))
+        {
+            continue;
+        }
+        unlocked.insert(achievement.id.to_string(), persist::unix_now());
+        earned.push(achievement);
+    }
+    earned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{terrain::Terrain, tests::game, Tile};
+
+    fn harvested() -> GameEvent {
+        GameEvent::Harvested {
+            x: 0,
+            y: 0,
+            points: 1.0,
+        }
+    }
+
+    fn round_ended(game: &Game) -> GameEvent {
+        GameEvent::RoundEnded {
+            round: game.round,
+            score: 0.0,
+            report: Default::default(),
+        }
+    }
+
+    fn ids(earned: Vec<&Achievement>) -> Vec<&str> {
+        earned.into_iter().map(|a| a.id).collect()
+    }
+
+    #[test]
+    fn a_harvest_earns_the_first_harvest_once() {
+        let game = game(&[]);
+        let mut unlocked = Unlocked::new();
+        let events = [GameEvent::Logged("Placed".into()), harvested()];
+        assert_eq!(
+            ids(check(&mut unlocked, &events[..1], &game)),
+            Vec::<&str>::new()
+        );
+        assert_eq!(ids(check(&mut unlocked, &events, &game)), ["first_harvest"]);
+        assert!(unlocked["first_harvest"] > 0);
+        assert!(check(&mut unlocked, &events, &game).is_empty());
+    }
+
+    #[test]
+    fn giants_are_earned_at_the_end_of_a_round() {
+        let mut game = game(&[]);
+        let mut unlocked = Unlocked::new();
+        game.records.biggest_size = GIANT_SIZE - 1;
+        assert!(check(&mut unlocked, &[round_ended(&game)], &game).is_empty());
+        game.records.biggest_size = GIANT_SIZE;
+        let logged = GameEvent::Logged("Grew".into());
+        assert!(check(&mut unlocked, &[logged], &game).is_empty());
+        let earned = check(&mut unlocked, &[round_ended(&game)], &game);
+        assert_eq!(ids(earned), ["giant"]);
+    }
+
+    #[test]
+    fn only_runs_without_compost_waste_not() {
+        let mut game = game(&[]);
+        let over = [GameEvent::GameOver { score: 0.0 }];
+        game.composted = 1;
+        assert!(check(&mut Unlocked::new(), &over, &game).is_empty());
+        game.composted = 0;
+        assert!(check(&mut Unlocked::new(), &[round_ended(&game)], &game).is_empty());
+        assert_eq!(
+            ids(check(&mut Unlocked::new(), &over, &game)),
+            ["no_compost"]
+        );
+    }
+
+    #[test]
+    fn a_full_board_counts_rocks_as_filled() {
+        let mut game = game(&["--dim", "2"]);
+        let event = [GameEvent::Logged("Placed".into())];
+        let grass = game.id_to_plant[0].clone();
+        for (x, y) in [(0, 0), (1, 0), (0, 1)] {
+            game.tile
+                .set(x, y, Tile::New(crate::PlantedPlant::new(grass.clone())));
+        }
+        assert!(check(&mut Unlocked::new(), &event, &game).is_empty());
+        game.terrain.set(1, 1, Terrain::Rock);
+        assert_eq!(
+            ids(check(&mut Unlocked::new(), &event, &game)),
+            ["full_board"]
+        );
+    }
+
+    #[test]
+    fn one_batch_can_earn_several() {
+        let mut game = game(&[]);
+        game.records.biggest_size = GIANT_SIZE;
+        let events = [harvested(), round_ended(&game)];
+        assert_eq!(
+            ids(check(&mut Unlocked::new(), &events, &game)),
+            ["first_harvest", "giant"]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::board;
+
+/// Everything the player can do. Keys and script lines are both turned into
+/// actions, so they share one dispatch path in `App::apply`, which leaves
+/// the rules to `engine::play`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// PgUp, PgDn, Home and End: move the cursor to that edge of the board
+    TopEdge,
+    BottomEdge,
+    LeftEdge,
+    RightEdge,
+    /// A click on the board: move the cursor to that tile
+    MoveTo(usize, usize),
+    /// Space: choose the selected card, place it or end the round
+    Place,
+    /// Enter while choosing: put the selected plant on the free tile
+    /// nearest the center
+    QuickPlace,
+    /// Next panel in on-screen order
+    Tab,
+    /// Previous panel, Shift+Tab
+    BackTab,
+    NextRound,
+    Delete,
+    ClearPest,
+    /// Pick the overripe plant under the cursor, with manual harvests
+    Harvest,
+    /// Spend water on the plant under the cursor
+    Water,
+    /// Compost the selected card, or spend compost on a plant while placing
+    Compost,
+    /// Jump to a slot of the Plants list, 0-based
+    Select(usize),
+    /// Cycle the order of the Plants list
+    SortHand,
+    /// `G`: list identical cards as one row with a count
+    GroupHand,
+    /// Start typing a filter for the Plants list
+    Filter,
+    ClearFilter,
+    /// Enter or leave Inspect mode
+    Inspect,
+    /// Open or close the plant codex
+    Codex,
+    /// Open or close the full text of the card in the info panel
+    Details,
+    /// Show or hide the cards waiting in the reserve
+    Reserve,
+    /// `b`: show or hide what the score is made of
+    ScoreBreakdown,
+    CycleTheme,
+    /// Show or hide the column letters and row numbers
+    ToggleGrid,
+    /// F3: switch between the canvas and the box-drawing board
+    ToggleRenderer,
+    /// `>` and `<`: give the board more or less of the width
+    WidenBoard,
+    NarrowBoard,
+    /// Re-read the plant file, for balancing without a restart
+    ReloadPlants,
+    /// Open or close the `--dev` plant editor
+    Editor,
+    /// Step the selected number of the plant editor up or down
+    Raise,
+    Lower,
+    /// Write the plants of the editor to their file
+    SavePlants,
+    /// Write the run as JSON for analysis
+    Export,
+    /// `S` and `L`: open the save dialog or the load menu. They only take
+    /// keys, so scripts can't open them.
+    SaveSlot,
+    LoadSlot,
+    /// Esc during `--tutorial`: leave it for a normal run
+    SkipTutorial,
+    /// Esc during a draft: take none of the rewards
+    SkipDraft,
+    /// Open or close the pause menu
+    Pause,
+    /// Pick the highlighted menu entry
+    Confirm,
+    Quit,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let action = match name {
+            "Up" => Action::Up,
+            "Down" => Action::Down,
+            "Left" => Action::Left,
+            "Right" => Action::Right,
+            "TopEdge" => Action::TopEdge,
+            "BottomEdge" => Action::BottomEdge,
+            "LeftEdge" => Action::LeftEdge,
+            "RightEdge" => Action::RightEdge,
+            "MoveTo" => {
+                let tile = words
+                    .next()
+                    .ok_or("MoveTo needs a tile, e.g. `MoveTo B3`")?;
+                let (x, y) =
+                    board::parse_tile_name(tile).ok_or(format!("invalid tile `{}`", tile))?;
+                Action::MoveTo(x, y)
+            }
+            "Place" => Action::Place,
+            "QuickPlace" => Action::QuickPlace,
+            "Tab" => Action::Tab,
+            "BackTab" => Action::BackTab,
+            "NextRound" => Action::NextRound,
+            "Delete" => Action::Delete,
+            "ClearPest" => Action::ClearPest,
+            "Harvest" => Action::Harvest,
+            "Water" => Action::Water,
+            "Compost" => Action::Compost,
+            "Select" => {
+                let slot = words
+                    .next()
+                    .ok_or("Select needs a hand slot, e.g. `Select 1`")?;
+                match slot.parse::<usize>() {
+                    Ok(slot) if slot > 0 => Action::Select(slot - 1),
+                    _ => return Err(format!("invalid hand slot `{}`, slots start at 1", slot)),
+                }
+            }
+            "SortHand" => Action::SortHand,
+            "GroupHand" => Action::GroupHand,
+            "Filter" => Action::Filter,
+            "ClearFilter" => Action::ClearFilter,
+            "Inspect" => Action::Inspect,
+            "Codex" => Action::Codex,
+            "Details" => Action::Details,
+            "Reserve" => Action::Reserve,
+            "ScoreBreakdown" => Action::ScoreBreakdown,
+            "CycleTheme" => Action::CycleTheme,
+            "ToggleGrid" => Action::ToggleGrid,
+            "ToggleRenderer" => Action::ToggleRenderer,
+            "WidenBoard" => Action::WidenBoard,
+            "NarrowBoard" => Action::NarrowBoard,
+            "ReloadPlants" => Action::ReloadPlants,
+            "Editor" => Action::Editor,
+            "Raise" => Action::Raise,
+            "Lower" => Action::Lower,
+            "SavePlants" => Action::SavePlants,
+            "Export" => Action::Export,
+            "SkipTutorial" => Action::SkipTutorial,
+            "SkipDraft" => Action::SkipDraft,
+            "Pause" => Action::Pause,
+            "Confirm" => Action::Confirm,
+            "Quit" => Action::Quit,
+            _ => return Err(format!("unknown action `{}`", name)),
+        };
+        if let Some(extra) = words.next() {
+            return Err(format!("unexpected `{}` after {}", extra, name));
+        }
+        Ok(action)
+    }
+}
+
+#[derive(Debug)]
+pub struct ScriptError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.msg)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// Parses one action per line, skipping blank lines and `#` comments. The
+/// whole script is checked before anything is played.
+pub fn parse_script(contents: &str) -> Result<Vec<Action>, ScriptError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, text)| text.parse().map_err(|msg| ScriptError { line, msg }))
+        .collect()
+}
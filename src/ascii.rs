@@ -0,0 +1,362 @@
+use clap::ValueEnum;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board, format_points, theme::Theme, BoardViewModel, TileDetail, TileEffect, TileView,
+    EFFECT_TICKS, FLASH_TICKS,
+};
+
+/// How the board panel is drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum Renderer {
+    /// Tile outlines in braille dots on a canvas
+    #[default]
+    Canvas,
+    /// Box-drawing characters only, for terminals and fonts without braille
+    Ascii,
+}
+
+impl Renderer {
+    pub fn next(self) -> Renderer {
+        match self {
+            Renderer::Canvas => Renderer::Ascii,
+            Renderer::Ascii => Renderer::Canvas,
+        }
+    }
+}
+
+/// Tiles need this many cells to get a frame of their own, smaller ones
+/// are a single line with the cursor in brackets.
+const BOXED_WIDTH: u16 = 5;
+const BOXED_HEIGHT: u16 = 3;
+
+const THIN: [&str; 6] = ["┌", "┐", "└", "┘", "─", "│"];
+const DOUBLE: [&str; 6] = ["╔", "╗", "╚", "╝", "═", "║"];
+
+/// The board of a `BoardViewModel` written straight into the buffer, the
+/// `--renderer ascii` stand-in for the canvas.
+pub struct AsciiBoard<'a> {
+    pub model: &'a BoardViewModel,
+    pub theme: &'a Theme,
+    /// Column letters and row numbers, where they fit
+    pub labels: bool,
+    pub effects: &'a [TileEffect],
+}
+
+/// Where the tiles go inside the panel.
+struct Grid {
+    /// Rows of tiles
+    rows: usize,
+    left: u16,
+    top: u16,
+    cell_width: u16,
+    cell_height: u16,
+}
+
+impl Grid {
+    fn cell(&self, x: usize, y: usize) -> Rect {
+        // Row 0 at the bottom, like on the canvas
+        let row = (self.rows - 1 - y) as u16;
+        Rect::new(
+            self.left + x as u16 * self.cell_width,
+            self.top + row * self.cell_height,
+            self.cell_width,
+            self.cell_height,
+        )
+    }
+
+    fn boxed(&self) -> bool {
+        self.cell_width >= BOXED_WIDTH && self.cell_height >= BOXED_HEIGHT
+    }
+}
+
+impl Widget for AsciiBoard<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = self
+            .theme
+            .panel(self.model.title.clone(), self.model.on_board);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        buf.set_style(inner, Style::default().bg(self.theme.bg));
+
+        // Labels take a margin on the left and at the bottom, but only on
+        // boards that still get framed tiles without it
+        let digits = self.model.height.to_string().len() as u16;
+        let margin = (digits + 1, 1);
+        let labels = self.labels && grid(self.model, inner, margin).is_some_and(|g| g.boxed());
+        let Some(grid) = grid(self.model, inner, if labels { margin } else { (0, 0) }) else {
+            return;
+        };
+        for tile in &self.model.tiles {
+            let cell = grid.cell(tile.x, tile.y);
+            if grid.boxed() {
+                draw_boxed(buf, tile, cell, self.theme);
+            } else {
+                draw_compact(buf, tile, cell);
+            }
+        }
+        if labels {
+            let style = Style::default().fg(self.theme.label);
+            for x in 0..self.model.width {
+                let cell = grid.cell(x, 0);
+                let name = board::column_name(x);
+                let column = cell.x + (cell.width.saturating_sub(name.len() as u16)) / 2;
+                buf.set_string(column, cell.bottom(), name, style);
+            }
+            for y in 0..self.model.height {
+                let cell = grid.cell(0, y);
+                let row = format!("{:>1$}", y + 1, digits as usize);
+                buf.set_string(inner.x, cell.y + cell.height / 2, row, style);
+            }
+        }
+        draw_effects(buf, &grid, self.effects, self.theme);
+    }
+}
+
+/// The tiles of `model` centered in `inner`, after `margin` columns on the
+/// left and rows at the bottom. `None` when the board doesn't fit at all.
+fn grid(model: &BoardViewModel, inner: Rect, margin: (u16, u16)) -> Option<Grid> {
+    let (width, height) = (model.width as u16, model.height as u16);
+    let room_width = inner.width.checked_sub(margin.0)?;
+    let room_height = inner.height.checked_sub(margin.1)?;
+    let cell_width = room_width / width;
+    let cell_height = room_height / height;
+    if cell_width == 0 || cell_height == 0 {
+        return None;
+    }
+    Some(Grid {
+        rows: model.height,
+        left: inner.x + margin.0 + (room_width - cell_width * width) / 2,
+        top: inner.y + (room_height - cell_height * height) / 2,
+        cell_width,
+        cell_height,
+    })
+}
+
+/// A frame in the tile's color, doubled under the cursor, with the plant in
+/// the middle and the annotations on the frame.
+fn draw_boxed(buf: &mut Buffer, tile: &TileView, cell: Rect, theme: &Theme) {
+    let lines = if tile.cursor { DOUBLE } else { THIN };
+    draw_frame(buf, cell, lines, tile.frame);
+    let room = cell.width - 2;
+    let top = cell.y;
+    let bottom = cell.bottom() - 1;
+    if let Some(cloud) = tile.cloud {
+        let style = Style::default().fg(tile.frame);
+        buf.set_string(cell.x + 1, top, cloud.to_string(), style);
+    }
+    if let Some((glyph, color)) = tile.terrain {
+        let style = Style::default().fg(color);
+        buf.set_string(cell.right() - 2, top, glyph.to_string(), style);
+    }
+    if tile.watered > 0 {
+        let marker = format!("+{}", tile.watered);
+        if marker.len() as u16 <= room {
+            let style = Style::default().fg(theme.rain);
+            buf.set_string(cell.x + 1, bottom, marker, style);
+        }
+    }
+
+    let full = tile.text(TileDetail::Full);
+    let text = if full.chars().count() as u16 <= room {
+        full
+    } else {
+        tile.text(TileDetail::Compact)
+    };
+    let len = text.chars().count() as u16;
+    // What the ghost would harvest goes right next to it
+    let points = tile
+        .points
+        .as_ref()
+        .filter(|points| len + 1 + points.len() as u16 <= room);
+    let total = len + points.map_or(0, |points| 1 + points.len() as u16);
+    let start = cell.x + 1 + (room - total) / 2;
+    let middle = cell.y + cell.height / 2;
+    buf.set_string(start, middle, text, tile.style);
+    if let Some(points) = points {
+        let style = Style::default().fg(theme.active);
+        buf.set_string(start + len + 1, middle, points, style);
+    }
+}
+
+fn draw_frame(buf: &mut Buffer, cell: Rect, lines: [&str; 6], color: Color) {
+    let style = Style::default().fg(color);
+    let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = lines;
+    let (right, bottom) = (cell.right() - 1, cell.bottom() - 1);
+    for x in cell.x + 1..right {
+        buf.set_string(x, cell.y, horizontal, style);
+        buf.set_string(x, bottom, horizontal, style);
+    }
+    for y in cell.y + 1..bottom {
+        buf.set_string(cell.x, y, vertical, style);
+        buf.set_string(right, y, vertical, style);
+    }
+    buf.set_string(cell.x, cell.y, top_left, style);
+    buf.set_string(right, cell.y, top_right, style);
+    buf.set_string(cell.x, bottom, bottom_left, style);
+    buf.set_string(right, bottom, bottom_right, style);
+}
+
+/// One line per tile on cramped boards: the plant, a dot on free tiles,
+/// and brackets around the cursor where there's room for them.
+fn draw_compact(buf: &mut Buffer, tile: &TileView, cell: Rect) {
+    let brackets = tile.cursor && cell.width >= 3;
+    let room = if brackets { cell.width - 2 } else { cell.width };
+    let full = tile.text(TileDetail::Full);
+    let (text, mut style) = match tile.text(TileDetail::Compact) {
+        " " => ("·", Style::default().fg(tile.frame)),
+        // A free column keeps it apart from the next tile
+        _ if (full.chars().count() as u16) < room => (full, tile.style),
+        compact => (compact, tile.style),
+    };
+    if tile.cursor && !brackets {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    let middle = cell.y + cell.height / 2;
+    let len = text.chars().count() as u16;
+    buf.set_string(cell.x + (cell.width - len) / 2, middle, text, style);
+    if brackets {
+        let style = Style::default().fg(tile.frame);
+        buf.set_string(cell.x, middle, "[", style);
+        buf.set_string(cell.right() - 1, middle, "]", style);
+    }
+}
+
+/// Harvested tiles flash like on the canvas, their points show on the
+/// tile and dim before they disappear.
+fn draw_effects(buf: &mut Buffer, grid: &Grid, effects: &[TileEffect], theme: &Theme) {
+    for effect in effects {
+        let Some(age) = effect.age() else {
+            continue;
+        };
+        let cell = grid.cell(effect.x, effect.y);
+        if age < FLASH_TICKS {
+            let color = if age % 2 == 0 {
+                theme.active
+            } else {
+                theme.maturing
+            };
+            if grid.boxed() {
+                draw_frame(buf, cell, THIN, color);
+            } else {
+                buf.set_style(cell, Style::default().bg(color));
+            }
+        }
+        let mut style = Style::default().fg(theme.new_plant);
+        if age * 3 >= EFFECT_TICKS * 2 {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        let popup = format!("+{}", format_points(effect.points));
+        let len = popup.chars().count() as u16;
+        if len <= cell.width {
+            // On the top edge of a frame, over the plant on a single line
+            let row = if grid.boxed() {
+                cell.y
+            } else {
+                cell.y + cell.height / 2
+            };
+            buf.set_string(cell.x + (cell.width - len) / 2, row, popup, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::Action,
+        engine::play,
+        tests::{game, tall_grass},
+        theme::ThemeName,
+        Game, PlacingState, PlantedPlant, State, Tile,
+    };
+
+    /// The board of `game` drawn into a `width` x `height` buffer, row by row.
+    fn draw(game: &Game, width: u16, height: u16, labels: bool) -> (Vec<String>, Buffer) {
+        let theme = Theme::new(ThemeName::Default);
+        let model = BoardViewModel::new(game, &theme, true);
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        let board = AsciiBoard {
+            model: &model,
+            theme: &theme,
+            labels,
+            effects: &[],
+        };
+        board.render(area, &mut buf);
+        let rows = (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        (rows, buf)
+    }
+
+    /// A 3x3 board with an older grass at A1 and the chosen plant over C3.
+    fn ghost_over_c3() -> Game {
+        let mut forest = game(&["--dim", "3"]);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = 1;
+        forest.tile.set(0, 0, Tile::Permanent(grass));
+        play(&mut forest, Action::Place);
+        forest.placing = PlacingState { x: 2, y: 2 };
+        assert_eq!(forest.state, State::Placing);
+        forest
+    }
+
+    #[test]
+    fn roomy_boards_get_a_frame_per_tile() {
+        let (rows, _) = draw(&ghost_over_c3(), 24, 12, true);
+        assert_eq!(
+            rows,
+            [
+                "╔ Forest (Normal) // C3╗",
+                "║   ┌────┐┌────┐╔════╗ ║",
+                "║3  │    ││    │║w 2 ║ ║",
+                "║   └────┘└────┘╚════╝ ║",
+                "║   ┌────┐┌────┐┌────┐ ║",
+                "║2  │    ││    ││    │ ║",
+                "║   └────┘└────┘└────┘ ║",
+                "║   ┌────┐┌────┐┌────┐ ║",
+                "║1  │W 3 ││    ││    │ ║",
+                "║   └────┘└────┘└────┘ ║",
+                "║     A     B     C    ║",
+                "╚══════════════════════╝",
+            ]
+        );
+    }
+
+    #[test]
+    fn cramped_boards_get_a_line_per_tile() {
+        let (rows, _) = draw(&ghost_over_c3(), 14, 6, true);
+        assert_eq!(
+            rows,
+            [
+                "╔ Forest (Nor╗",
+                "║ ·   ·  [w ]║",
+                "║ ·   ·   ·  ║",
+                "║W 3  ·   ·  ║",
+                "║            ║",
+                "╚════════════╝",
+            ]
+        );
+    }
+
+    #[test]
+    fn tiles_take_the_colors_of_the_view_model() {
+        let forest = ghost_over_c3();
+        let theme = Theme::new(ThemeName::Default);
+        let model = BoardViewModel::new(&forest, &theme, true);
+        let tile = |x, y| model.tiles.iter().find(|t| (t.x, t.y) == (x, y)).unwrap();
+        let (_, buf) = draw(&forest, 24, 12, true);
+        // The cursor's frame and ghost, then the grass at A1
+        assert_eq!(buf[(16, 1)].fg, tile(2, 2).frame);
+        assert_eq!(buf[(17, 2)].modifier, tile(2, 2).style.add_modifier);
+        assert_eq!(buf[(4, 7)].fg, tile(0, 0).frame);
+        assert_eq!(Some(buf[(5, 8)].fg), tile(0, 0).style.fg);
+    }
+}
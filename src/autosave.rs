@@ -0,0 +1,204 @@
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::{
+    backend::Backend,
+    layout::Alignment,
+    widgets::{Clear, Paragraph, Wrap},
+    Terminal,
+};
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    centered_rect, packs, persist,
+    save::{self, SaveFile},
+    App, Game, GlobalSetting, State,
+};
+
+pub fn autosave_path() -> PathBuf {
+    persist::data_dir().join("autosave.json")
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Keeps the running game on disk after every round, so a crash or a lost
+/// connection doesn't end the run. A clean exit removes it again.
+pub struct Autosave {
+    path: PathBuf,
+    round: u32,
+}
+
+impl Autosave {
+    pub fn new(path: PathBuf, round: u32) -> Autosave {
+        Autosave { path, round }
+    }
+
+    /// Writes the game once the round moved on. The previous autosave is
+    /// kept as `.bak` in case the new one turns out unreadable.
+    pub fn update(&mut self, game: &Game) -> io::Result<()> {
+        if game.round == self.round {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = save::to_json(game)?;
+        match fs::rename(&self.path, backup_path(&self.path)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        persist::write_atomic(&self.path, json.as_bytes())?;
+        self.round = game.round;
+        Ok(())
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        remove(&self.path)
+    }
+}
+
+/// Removes the autosave and its backup.
+fn remove(path: &Path) -> io::Result<()> {
+    for path in [path.to_path_buf(), backup_path(path)] {
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The newest autosave that still parses, falling back to the backup.
+/// Finished runs, other board sizes and other modes are not offered.
+fn load(path: &Path, settings: &GlobalSetting) -> Option<SaveFile> {
+    [path.to_path_buf(), backup_path(path)]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|contents| save::from_json(&contents).ok())
+        .find(|save| {
+            save.width == settings.width
+                && save.height == settings.height
+                && save.game.mode == settings.mode
+                && save.game.state != State::GameOver
+        })
+}
+
+/// Offers to resume an unfinished run before the board is shown, then
+/// starts autosaving whichever game is played.
+pub fn start<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<Autosave> {
+    let path = autosave_path();
+    if let Some(save) = load(&path, &app.game.settings) {
+        if save.game.packs != app.pack.names {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "The run saved at {} was played with {}, this one with {}. Start with the same --pack or remove the autosave",
+                    path.display(),
+                    packs::describe(&save.game.packs),
+                    packs::describe(&app.pack.names)
+                ),
+            ));
+        }
+        if prompt_resume(terminal, app, &save)? {
+            let settings = app.game.settings.clone();
+            app.game = save.game;
+            app.game.settings = settings;
+            // Saved before the selection was kept in line with the hand
+            app.game.keep_selection();
+        } else {
+            remove(&path)?;
+        }
+    }
+    Ok(Autosave::new(path, app.game.round))
+}
+
+fn prompt_resume<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &App,
+    save: &SaveFile,
+) -> io::Result<bool> {
+    let msg = format!(
+        "An unfinished run was found at round {} with {} points.\n\nResume it? [y]es / [n]o",
+        save.game.round,
+        save.game.score_display()
+    );
+    loop {
+        terminal.draw(|f| {
+            let area = centered_rect(60, 30, f.area());
+            let paragraph = Paragraph::new(msg.as_str())
+                .block(app.theme.panel(" Autosave ", true))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            f.render_widget(Clear, area);
+            f.render_widget(paragraph, area);
+        })?;
+
+        match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => return Ok(true),
+                KeyCode::Char('n') | KeyCode::Esc => return Ok(false),
+                _ => {}
+            },
+            Event::Resize(..) => terminal.autoresize()?,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{action::Action, engine::play, tests::game};
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-autosave-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("autosave.json")
+    }
+
+    #[test]
+    fn a_partly_written_autosave_falls_back_to_the_backup() {
+        let path = scratch("partial");
+        let mut game = game(&[]);
+        let mut autosave = Autosave::new(path.clone(), game.round);
+        play(&mut game, Action::NextRound);
+        autosave.update(&game).unwrap();
+        assert!(!backup_path(&path).exists());
+        play(&mut game, Action::NextRound);
+        autosave.update(&game).unwrap();
+        let saved = |path: &Path| save::from_json(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(saved(&backup_path(&path)).game.round, 1);
+        assert_eq!(load(&path, &game.settings).unwrap().game.round, 2);
+
+        // Cut off halfway, the round before is offered and the backup kept
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(&path, &contents[..contents.len() / 2]).unwrap();
+        assert_eq!(load(&path, &game.settings).unwrap().game.round, 1);
+        assert_eq!(saved(&backup_path(&path)).game.round, 1);
+
+        // Nothing readable left, nothing is offered
+        fs::write(backup_path(&path), "{").unwrap();
+        assert!(load(&path, &game.settings).is_none());
+        autosave.finish().unwrap();
+        assert!(!path.exists() && !backup_path(&path).exists());
+    }
+
+    #[test]
+    fn the_same_round_is_not_written_twice() {
+        let path = scratch("unchanged");
+        let game = game(&[]);
+        let mut autosave = Autosave::new(path.clone(), game.round);
+        autosave.update(&game).unwrap();
+        assert!(!path.exists());
+    }
+}
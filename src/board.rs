@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+/// A grid of cells addressed by (x, y), with x growing to the right and y
+/// growing upwards, the way the board is drawn. Cells are stored row by
+/// row, but no index ever leaves this module: every access is checked
+/// against the board size and misses return `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Board<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Board<T> {
+    /// A board with every cell made by `cell(x, y)`.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+        Board {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    fn idx(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.cells.get(self.idx(x, y)?)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        let idx = self.idx(x, y)?;
+        self.cells.get_mut(idx)
+    }
+
+    /// Puts `cell` on (x, y) and returns what was there. Off the board
+    /// nothing changes.
+    pub fn set(&mut self, x: usize, y: usize, cell: T) -> Option<T> {
+        self.get_mut(x, y).map(|old| std::mem::replace(old, cell))
+    }
+
+    /// Every coordinate row by row. The iterator doesn't borrow the board,
+    /// so cells can be changed while walking it.
+    pub fn iter_coords(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The orthogonal neighbors of (x, y) that are on the board.
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        [
+            x.checked_sub(1).map(|x| (x, y)),
+            Some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            Some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(move |&(x, y)| x < width && y < height)
+    }
+
+    /// Every coordinate in a square spiral out of (x, y): one step right,
+    /// one up, two left, two down, three right and so on, skipping what is
+    /// off the board.
+    pub fn spiral(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        const TURNS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+        let (width, height) = (self.width as isize, self.height as isize);
+        let visit = |x: isize, y: isize, coords: &mut Vec<(usize, usize)>| {
+            if (0..width).contains(&x) && (0..height).contains(&y) {
+                coords.push((x as usize, y as usize));
+            }
+        };
+        let (mut x, mut y) = (x as isize, y as isize);
+        let mut coords = Vec::with_capacity(self.cells.len());
+        visit(x, y, &mut coords);
+        let mut leg = 0;
+        while coords.len() < self.cells.len() {
+            let (dx, dy) = TURNS[leg % 4];
+            for _ in 0..leg / 2 + 1 {
+                x += dx;
+                y += dy;
+                visit(x, y, &mut coords);
+            }
+            leg += 1;
+        }
+        coords
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+}
+
+impl<T: Clone> Board<T> {
+    pub fn filled(width: usize, height: usize, cell: T) -> Self {
+        Board::from_fn(width, height, |_, _| cell.clone())
+    }
+}
+
+/// Letters of column `x` as on a spreadsheet: A to Z, then AA, AB and on.
+pub fn column_name(x: usize) -> String {
+    let mut name = Vec::new();
+    let mut n = x + 1;
+    while n > 0 {
+        name.push(b'A' + ((n - 1) % 26) as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// How a tile is referred to on screen and in the log, like `B3`. Rows
+/// count from 1 at the bottom, the way the board is drawn.
+pub fn tile_name(x: usize, y: usize) -> String {
+    format!("{}{}", column_name(x), y + 1)
+}
+
+/// The tile `tile_name` calls `name`, on a board of any size.
+pub fn parse_tile_name(name: &str) -> Option<(usize, usize)> {
+    let digits = name.find(|c: char| !c.is_ascii_uppercase())?;
+    let (letters, row) = name.split_at(digits);
+    if letters.is_empty() || !row.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let column = letters.bytes().try_fold(0usize, |n, letter| {
+        n.checked_mul(26)?.checked_add((letter - b'A') as usize + 1)
+    })?;
+    Some((column - 1, row.parse::<usize>().ok()?.checked_sub(1)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_names_parse_back_to_their_tile() {
+        for (x, y) in [(0, 0), (1, 2), (25, 9), (26, 0), (701, 99)] {
+            assert_eq!(parse_tile_name(&tile_name(x, y)), Some((x, y)));
+        }
+        assert_eq!(tile_name(26, 0), "AA1");
+        for name in ["", "B", "3", "B0", "b3", "B3x", "3B"] {
+            assert_eq!(parse_tile_name(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn the_spiral_winds_out_of_its_start() {
+        let board = Board::from_fn(3, 3, |_, _| ());
+        assert_eq!(
+            board.spiral(1, 1),
+            [
+                (1, 1),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1),
+                (0, 0),
+                (1, 0),
+                (2, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn the_spiral_skips_what_is_off_the_board() {
+        let board = Board::from_fn(2, 2, |_, _| ());
+        assert_eq!(board.spiral(0, 0), [(0, 0), (1, 0), (1, 1), (0, 1)]);
+
+        let board = Board::from_fn(5, 2, |_, _| ());
+        let mut coords = board.spiral(4, 1);
+        assert_eq!(coords[..4], [(4, 1), (3, 1), (3, 0), (4, 0)]);
+        let mut all = board.iter_coords().collect::<Vec<(usize, usize)>>();
+        coords.sort();
+        all.sort();
+        assert_eq!(coords, all);
+    }
+}
@@ -0,0 +1,744 @@
+//! `config.toml`: values for the flags that stick between runs. Flags go
+//! over the file, the file goes over the built-in defaults.
+//!
+//! The file is flat, one `key = value` per line like the flags without
+//! their dashes, read with the `toml` crate. Strings, numbers, booleans
+//! and arrays are taken, tables are not. The one exception is
+//! `last_setup`, the inline table the setup screen keeps its last run in.
+//!
+//! A switch the file turns on is turned off again for one run with
+//! `--switch=false`.
+
+use clap::{parser::ValueSource, ArgMatches};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use toml::Spanned;
+
+use std::{collections::BTreeMap, fs, io, ops::Range, path::Path, path::PathBuf};
+
+use crate::{
+    ascii::Renderer, difficulty::Level, mode::GameMode, mutators::Mutator, persist, setup::Setup,
+    simulate::PolicyName, theme::ThemeName, Args, HarvestMode, StaleCards, MAX_BOARD_SPLIT,
+    MIN_BOARD_SPLIT,
+};
+
+/// The flags a config file can set, `None` for the ones it leaves alone.
+/// Flags for one run only, like `--script` or `--export`, aren't in here.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dim: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub difficulty: Option<Level>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub mode: Option<GameMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pest_chance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_chance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_chance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carryover_water: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overplant: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub theme: Option<ThemeName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub renderer: Option<Renderer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spread_multiplier: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_points: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_passives: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_fate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forecast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maturing_at: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap_cursor: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board_split: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_threshold: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub players: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_autosave: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plain: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_timer: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_advance: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_hand: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vs_ai: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub ai_policy: Option<PolicyName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_profile: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub harvest_mode: Option<HarvestMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overripe_rounds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enum")]
+    pub stale_cards: Option<StaleCards>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_every: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "value_enums")]
+    pub mutator: Option<Vec<Mutator>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack: Option<String>,
+    /// Not a flag: what the setup screen offers to start with again
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_setup: Option<Setup>,
+}
+
+pub fn config_path() -> PathBuf {
+    persist::config_dir().join("config.toml")
+}
+
+impl Config {
+    /// Reads the file at `path`, an empty config if there is none. Errors
+    /// point at the line and column in the file.
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(err) => return Err(format!("{}: {}", path.display(), err)),
+        };
+        Config::parse(&text)
+            .map_err(|(line, col, msg)| format!("{}:{}:{}: {}", path.display(), line, col, msg))
+    }
+
+    /// Every key is read on its own, so a wrong one is reported where it
+    /// is in the file.
+    fn parse(text: &str) -> Result<Config, ParseError> {
+        let entries = toml::from_str::<BTreeMap<Spanned<String>, Spanned<toml::Value>>>(text)
+            .map_err(|err| {
+                let (line, col) = line_col(text, err.span().unwrap_or(0..0));
+                (line, col, err.message().to_string())
+            })?;
+        let mut config = Config::default();
+        for (key, value) in entries {
+            let (line, col) = line_col(text, key.span());
+            let value = value.into_inner();
+            let key = key.into_inner().replace('-', "_");
+            if value.is_table() && key != "last_setup" {
+                return Err((
+                    line,
+                    col,
+                    String::from("tables aren't supported, keep every key at the top"),
+                ));
+            }
+            let one = toml::Table::from_iter([(key, value)]);
+            let one = one
+                .try_into::<Config>()
+                .map_err(|err| (line, col, err.message().to_string()))?;
+            one.check().map_err(|msg| (line, col, msg))?;
+            config = config.merge(one);
+        }
+        Ok(config)
+    }
+
+    /// What clap checks for the flags.
+    fn check(&self) -> Result<(), String> {
+        let splits = MIN_BOARD_SPLIT..=MAX_BOARD_SPLIT;
+        match (self.players, self.board_split) {
+            (Some(players), _) if !(1..=4).contains(&players) => {
+                Err(format!("players has to be from 1 to 4, not {}", players))
+            }
+            (_, Some(split)) if !splits.contains(&split) => Err(format!(
+                "board_split has to be from {} to {}, not {}",
+                MIN_BOARD_SPLIT, MAX_BOARD_SPLIT, split
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Both configs in one, `over` wins where both set a value.
+    pub fn merge(self, over: Config) -> Config {
+        Config {
+            dim: over.dim.or(self.dim),
+            seed: over.seed.or(self.seed),
+            difficulty: over.difficulty.or(self.difficulty),
+            mode: over.mode.or(self.mode),
+            pest_chance: over.pest_chance.or(self.pest_chance),
+            drop_chance: over.drop_chance.or(self.drop_chance),
+            cloud_chance: over.cloud_chance.or(self.cloud_chance),
+            rounds: over.rounds.or(self.rounds),
+            actions: over.actions.or(self.actions),
+            carryover_water: over.carryover_water.or(self.carryover_water),
+            overplant: over.overplant.or(self.overplant),
+            preset: over.preset.or(self.preset),
+            theme: over.theme.or(self.theme),
+            lang: over.lang.or(self.lang),
+            renderer: over.renderer.or(self.renderer),
+            spread_multiplier: over.spread_multiplier.or(self.spread_multiplier),
+            tile_points: over.tile_points.or(self.tile_points),
+            no_passives: over.no_passives.or(self.no_passives),
+            show_fate: over.show_fate.or(self.show_fate),
+            forecast: over.forecast.or(self.forecast),
+            maturing_at: over.maturing_at.or(self.maturing_at),
+            wrap_cursor: over.wrap_cursor.or(self.wrap_cursor),
+            board_split: over.board_split.or(self.board_split),
+            family_threshold: over.family_threshold.or(self.family_threshold),
+            players: over.players.or(self.players),
+            no_autosave: over.no_autosave.or(self.no_autosave),
+            tick_ms: over.tick_ms.or(self.tick_ms),
+            plain: over.plain.or(self.plain),
+            no_timer: over.no_timer.or(self.no_timer),
+            auto_advance: over.auto_advance.or(self.auto_advance),
+            group_hand: over.group_hand.or(self.group_hand),
+            vs_ai: over.vs_ai.or(self.vs_ai),
+            ai_policy: over.ai_policy.or(self.ai_policy),
+            profile: over.profile.or(self.profile),
+            no_profile: over.no_profile.or(self.no_profile),
+            harvest_mode: over.harvest_mode.or(self.harvest_mode),
+            overripe_rounds: over.overripe_rounds.or(self.overripe_rounds),
+            freshness: over.freshness.or(self.freshness),
+            stale_cards: over.stale_cards.or(self.stale_cards),
+            draft_every: over.draft_every.or(self.draft_every),
+            mutator: over.mutator.or(self.mutator),
+            pack: over.pack.or(self.pack),
+            last_setup: over.last_setup.or(self.last_setup),
+        }
+    }
+
+    /// The flags that were typed out. A flag left out and one given its
+    /// default look the same in `args`, only `matches` tells them apart.
+    pub fn given(args: &Args, matches: &ArgMatches) -> Config {
+        let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        Config {
+            dim: given("dim").then_some(args.dim),
+            // Flags without a default are only set when given
+            seed: args.seed,
+            difficulty: given("difficulty").then_some(args.difficulty),
+            mode: given("mode").then_some(args.mode),
+            pest_chance: args.pest_chance,
+            drop_chance: args.drop_chance,
+            cloud_chance: given("cloud_chance").then_some(args.cloud_chance),
+            rounds: args.rounds,
+            actions: given("actions").then_some(args.actions),
+            carryover_water: given("carryover_water").then_some(args.carryover_water),
+            overplant: given("overplant").then_some(args.overplant),
+            preset: args.preset.clone(),
+            theme: given("theme").then_some(args.theme),
+            lang: args.lang.clone(),
+            renderer: given("renderer").then_some(args.renderer),
+            spread_multiplier: given("spread_multiplier").then_some(args.spread_multiplier),
+            tile_points: given("tile_points").then_some(args.tile_points),
+            no_passives: given("no_passives").then_some(args.no_passives),
+            show_fate: given("show_fate").then_some(args.show_fate),
+            forecast: given("forecast").then_some(args.forecast),
+            maturing_at: given("maturing_at").then_some(args.maturing_at),
+            wrap_cursor: given("wrap_cursor").then_some(args.wrap_cursor),
+            board_split: given("board_split").then_some(args.board_split),
+            family_threshold: given("family_threshold").then_some(args.family_threshold),
+            players: given("players").then_some(args.players),
+            no_autosave: given("no_autosave").then_some(args.no_autosave),
+            tick_ms: given("tick_ms").then_some(args.tick_ms),
+            plain: given("plain").then_some(args.plain),
+            no_timer: given("no_timer").then_some(args.no_timer),
+            auto_advance: given("auto_advance").then_some(args.auto_advance),
+            group_hand: given("group_hand").then_some(args.group_hand),
+            vs_ai: given("vs_ai").then_some(args.vs_ai),
+            ai_policy: given("ai_policy").then_some(args.ai_policy),
+            profile: given("profile").then(|| args.profile.clone()),
+            no_profile: given("no_profile").then_some(args.no_profile),
+            harvest_mode: given("harvest_mode").then_some(args.harvest_mode),
+            overripe_rounds: given("overripe_rounds").then_some(args.overripe_rounds),
+            freshness: args.freshness,
+            stale_cards: given("stale_cards").then_some(args.stale_cards),
+            draft_every: args.draft_every,
+            mutator: given("mutator").then(|| args.mutator.clone()),
+            pack: args.pack.clone(),
+            last_setup: None,
+        }
+    }
+
+    /// Everything `args` is set to, defaults included.
+    pub fn of(args: &Args) -> Config {
+        Config {
+            dim: Some(args.dim),
+            seed: args.seed,
+            difficulty: Some(args.difficulty),
+            mode: Some(args.mode),
+            pest_chance: args.pest_chance,
+            drop_chance: args.drop_chance,
+            cloud_chance: Some(args.cloud_chance),
+            rounds: args.rounds,
+            actions: Some(args.actions),
+            carryover_water: Some(args.carryover_water),
+            overplant: Some(args.overplant),
+            preset: args.preset.clone(),
+            theme: Some(args.theme),
+            lang: args.lang.clone(),
+            renderer: Some(args.renderer),
+            spread_multiplier: Some(args.spread_multiplier),
+            tile_points: Some(args.tile_points),
+            no_passives: Some(args.no_passives),
+            show_fate: Some(args.show_fate),
+            forecast: Some(args.forecast),
+            maturing_at: Some(args.maturing_at),
+            wrap_cursor: Some(args.wrap_cursor),
+            board_split: Some(args.board_split),
+            family_threshold: Some(args.family_threshold),
+            players: Some(args.players),
+            no_autosave: Some(args.no_autosave),
+            tick_ms: Some(args.tick_ms),
+            plain: Some(args.plain),
+            no_timer: Some(args.no_timer),
+            auto_advance: Some(args.auto_advance),
+            group_hand: Some(args.group_hand),
+            vs_ai: Some(args.vs_ai),
+            ai_policy: Some(args.ai_policy),
+            profile: Some(args.profile.clone()),
+            no_profile: Some(args.no_profile),
+            harvest_mode: Some(args.harvest_mode),
+            overripe_rounds: Some(args.overripe_rounds),
+            freshness: args.freshness,
+            stale_cards: Some(args.stale_cards),
+            draft_every: args.draft_every,
+            mutator: Some(args.mutator.clone()),
+            pack: args.pack.clone(),
+            last_setup: None,
+        }
+    }
+
+    /// Sets the flags of `args` the config has a value for.
+    pub fn apply(self, args: &mut Args) {
+        args.dim = self.dim.unwrap_or(args.dim);
+        args.seed = self.seed.or(args.seed);
+        args.difficulty = self.difficulty.unwrap_or(args.difficulty);
+        args.mode = self.mode.unwrap_or(args.mode);
+        args.pest_chance = self.pest_chance.or(args.pest_chance);
+        args.drop_chance = self.drop_chance.or(args.drop_chance);
+        args.cloud_chance = self.cloud_chance.unwrap_or(args.cloud_chance);
+        args.rounds = self.rounds.or(args.rounds);
+        args.actions = self.actions.unwrap_or(args.actions);
+        args.carryover_water = self.carryover_water.unwrap_or(args.carryover_water);
+        args.overplant = self.overplant.unwrap_or(args.overplant);
+        args.preset = self.preset.or(args.preset.take());
+        args.theme = self.theme.unwrap_or(args.theme);
+        args.lang = self.lang.or(args.lang.take());
+        args.renderer = self.renderer.unwrap_or(args.renderer);
+        args.spread_multiplier = self.spread_multiplier.unwrap_or(args.spread_multiplier);
+        args.tile_points = self.tile_points.unwrap_or(args.tile_points);
+        args.no_passives = self.no_passives.unwrap_or(args.no_passives);
+        args.show_fate = self.show_fate.unwrap_or(args.show_fate);
+        args.forecast = self.forecast.unwrap_or(args.forecast);
+        args.maturing_at = self.maturing_at.unwrap_or(args.maturing_at);
+        args.wrap_cursor = self.wrap_cursor.unwrap_or(args.wrap_cursor);
+        args.board_split = self.board_split.unwrap_or(args.board_split);
+        args.family_threshold = self.family_threshold.unwrap_or(args.family_threshold);
+        args.players = self.players.unwrap_or(args.players);
+        args.no_autosave = self.no_autosave.unwrap_or(args.no_autosave);
+        args.tick_ms = self.tick_ms.unwrap_or(args.tick_ms);
+        args.plain = self.plain.unwrap_or(args.plain);
+        args.no_timer = self.no_timer.unwrap_or(args.no_timer);
+        args.auto_advance = self.auto_advance.unwrap_or(args.auto_advance);
+        args.group_hand = self.group_hand.unwrap_or(args.group_hand);
+        args.vs_ai = self.vs_ai.unwrap_or(args.vs_ai);
+        args.ai_policy = self.ai_policy.unwrap_or(args.ai_policy);
+        if let Some(profile) = self.profile {
+            args.profile = profile;
+        }
+        args.no_profile = self.no_profile.unwrap_or(args.no_profile);
+        args.harvest_mode = self.harvest_mode.unwrap_or(args.harvest_mode);
+        args.overripe_rounds = self.overripe_rounds.unwrap_or(args.overripe_rounds);
+        args.freshness = self.freshness.or(args.freshness);
+        args.stale_cards = self.stale_cards.unwrap_or(args.stale_cards);
+        args.draft_every = self.draft_every.or(args.draft_every);
+        if let Some(mutator) = self.mutator {
+            args.mutator = mutator;
+        }
+        args.pack = self.pack.or(args.pack.take());
+    }
+
+    /// The config as a file to start `config.toml` from, sorted by key.
+    pub fn to_toml(&self) -> String {
+        let Ok(Value::Object(map)) = serde_json::to_value(self) else {
+            unreachable!("configs serialize to a map")
+        };
+        map.iter()
+            .map(|(key, value)| format!("{} = {}\n", key, toml_value(value)))
+            .collect()
+    }
+}
+
+/// Sets `key` in the file at `path` and keeps the rest of the file, its
+/// comments included, as it is.
+pub fn set_value(path: &Path, key: &str, value: &str) -> io::Result<()> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let entry = format!("{} = {}", key, value);
+    let mut found = false;
+    let mut lines = text
+        .lines()
+        .map(|line| match !found && sets_key(line, key) {
+            true => {
+                found = true;
+                entry.clone()
+            }
+            false => line.to_string(),
+        })
+        .collect::<Vec<String>>();
+    if !found {
+        lines.push(entry);
+    }
+    let mut text = lines.join("\n");
+    text.push('\n');
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    persist::write_atomic(path, text.as_bytes())
+}
+
+/// Keeps `setup` as the `last_setup` of the file at `path`.
+pub fn save_last_setup(path: &Path, setup: &Setup) -> io::Result<()> {
+    set_value(
+        path,
+        "last_setup",
+        &toml_value(&serde_json::to_value(setup)?),
+    )
+}
+
+/// Whether `line` sets `key`, spelled with underscores or dashes.
+fn sets_key(line: &str, key: &str) -> bool {
+    let line = line.trim_start();
+    [key.to_string(), key.replace('_', "-")].iter().any(|key| {
+        line.strip_prefix(key.as_str())
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    })
+}
+
+/// JSON strings, numbers and arrays are TOML as they are, objects become
+/// inline tables without their nulls. The floats were `f32` before
+/// serde_json made them `f64`, and print like `f32` again.
+fn toml_value(value: &Value) -> String {
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            let float = n.as_f64().unwrap_or_default() as f32;
+            match float.fract() == 0.0 {
+                true => format!("{:.1}", float),
+                false => float.to_string(),
+            }
+        }
+        Value::Array(values) => {
+            let values = values.iter().map(toml_value).collect::<Vec<String>>();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Object(map) => {
+            let entries = map
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| format!("{} = {}", key, toml_value(value)))
+                .collect::<Vec<String>>();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
+
+/// Enums spelled like their flag values, `difficulty = "hard"`.
+mod value_enum {
+    use clap::ValueEnum;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn name<T: ValueEnum>(value: &T) -> String {
+        value
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn parse<T: ValueEnum>(input: &str) -> Result<T, String> {
+        T::from_str(input, true).map_err(|_| {
+            let names = T::value_variants().iter().map(name).collect::<Vec<_>>();
+            format!(
+                "unknown value `{}`, expected one of {}",
+                input,
+                names.join(", ")
+            )
+        })
+    }
+
+    pub fn serialize<S: Serializer, T: ValueEnum>(
+        value: &Option<T>,
+        ser: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => ser.serialize_str(&name(value)),
+            None => ser.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: ValueEnum>(
+        de: D,
+    ) -> Result<Option<T>, D::Error> {
+        let name = String::deserialize(de)?;
+        parse(&name).map(Some).map_err(D::Error::custom)
+    }
+}
+
+/// Lists of enums, `mutator = ["glass-cannon"]`.
+mod value_enums {
+    use clap::ValueEnum;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::value_enum;
+
+    pub fn serialize<S: Serializer, T: ValueEnum>(
+        values: &Option<Vec<T>>,
+        ser: S,
+    ) -> Result<S::Ok, S::Error> {
+        match values {
+            Some(values) => ser.collect_seq(values.iter().map(value_enum::name)),
+            None => ser.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: ValueEnum>(
+        de: D,
+    ) -> Result<Option<Vec<T>>, D::Error> {
+        Vec::<String>::deserialize(de)?
+            .iter()
+            .map(|name| value_enum::parse(name))
+            .collect::<Result<Vec<T>, String>>()
+            .map(Some)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Line, column and what is wrong there.
+type ParseError = (usize, usize, String);
+
+/// Line and column of where `span` starts in `text`, both from 1.
+fn line_col(text: &str, span: Range<usize>) -> (usize, usize) {
+    let before = &text[..span.start.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let col = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, col)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+
+    /// The flags of a run with `file` as its config, laid over each other
+    /// the way `run` does.
+    fn resolve(file: &str, flags: &[&str]) -> Args {
+        let matches = Args::command()
+            .try_get_matches_from(std::iter::once("rogue_forest").chain(flags.iter().copied()))
+            .unwrap();
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        let file = Config::parse(file).unwrap();
+        file.merge(Config::given(&args, &matches)).apply(&mut args);
+        args
+    }
+
+    #[test]
+    fn flags_go_over_the_file_over_the_defaults() {
+        assert_eq!(resolve("", &[]).dim, 6);
+        assert_eq!(resolve("dim = 8", &[]).dim, 8);
+        assert_eq!(resolve("dim = 8", &["--dim", "10"]).dim, 10);
+        assert_eq!(resolve("", &["--dim", "10"]).dim, 10);
+
+        let args = resolve("seed = 3\nrounds = 9", &["--seed", "4"]);
+        assert_eq!((args.seed, args.rounds), (Some(4), Some(9)));
+        let args = resolve("mutator = [\"barren\"]", &["--mutator", "generous"]);
+        assert_eq!(args.mutator, [Mutator::Generous]);
+    }
+
+    #[test]
+    fn a_flag_given_its_default_still_goes_over_the_file() {
+        // Absent and given as 6 are both 6 in `Args`, only the first leaves
+        // the file alone
+        assert_eq!(resolve("dim = 8", &["--dim", "6"]).dim, 6);
+        assert_eq!(
+            resolve("difficulty = \"hard\"", &["--difficulty", "normal"]).difficulty,
+            Level::Normal
+        );
+        assert_eq!(
+            resolve("difficulty = \"hard\"", &[]).difficulty,
+            Level::Hard
+        );
+        assert!(resolve("wrap_cursor = true", &[]).wrap_cursor);
+    }
+
+    #[test]
+    fn a_switch_the_file_turns_on_can_be_turned_off() {
+        let file = "overplant = true\nno_autosave = true\nvs_ai = true";
+        let args = resolve(file, &[]);
+        assert!(args.overplant && args.no_autosave && args.vs_ai);
+        let args = resolve(file, &["--overplant=false", "--no-autosave=false"]);
+        assert!(!args.overplant && !args.no_autosave && args.vs_ai);
+        assert!(!resolve(file, &["--vs-ai=false"]).vs_ai);
+        // Without a value a switch is still just on
+        assert!(resolve("overplant = false", &["--overplant"]).overplant);
+        assert!(resolve("", &["--overplant=true"]).overplant);
+        assert!(!resolve("", &[]).overplant);
+    }
+
+    #[test]
+    fn only_typed_flags_count_as_given() {
+        let matches = Args::command()
+            .try_get_matches_from(["rogue_forest", "--dim", "6", "--overplant"])
+            .unwrap();
+        assert_eq!(matches.value_source("dim"), Some(ValueSource::CommandLine));
+        assert_eq!(
+            matches.value_source("actions"),
+            Some(ValueSource::DefaultValue)
+        );
+        let args = Args::from_arg_matches(&matches).unwrap();
+        let given = Config::given(&args, &matches);
+        assert_eq!((given.dim, given.overplant), (Some(6), Some(true)));
+        assert_eq!(
+            (given.actions, given.wrap_cursor, given.seed),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn merge_keeps_what_only_one_side_sets() {
+        let file = Config::parse("dim = 8\nplayers = 2").unwrap();
+        let flags = Config {
+            dim: Some(10),
+            seed: Some(1),
+            ..Config::default()
+        };
+        let config = file.merge(flags);
+        assert_eq!(
+            (config.dim, config.players, config.seed),
+            (Some(10), Some(2), Some(1))
+        );
+    }
+
+    #[test]
+    fn the_file_is_read_as_toml() {
+        let config = Config::parse(
+            "# mine\npest-chance = 0\ntheme = 'autumn' # warm\nmutator = [\n  \"barren\",\n]\n",
+        )
+        .unwrap();
+        assert_eq!(config.pest_chance, Some(0.0));
+        assert_eq!(config.theme, Some(ThemeName::Autumn));
+        assert_eq!(config.mutator, Some(vec![Mutator::Barren]));
+
+        let err = |text: &str| Config::parse(text).unwrap_err();
+        assert_eq!(err("dim = 8\n  seed = \"x\"").0, 2);
+        assert_eq!(err("dim = 8\n  seed = \"x\"").1, 3);
+        assert!(err("[table]\ndim = 8").2.contains("tables"));
+        assert!(err("players = 7").2.contains("from 1 to 4"));
+        assert!(err("dim = 8\ndim = 9").2.contains("duplicate"));
+        assert!(err("nope = 1").2.contains("unknown field"));
+    }
+
+    #[test]
+    fn printed_configs_read_back_the_same() {
+        let config = Config::of(&resolve(
+            "",
+            &["--mutator", "barren", "--pest-chance", "0.1"],
+        ));
+        assert_eq!(Config::parse(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn set_value_replaces_the_key_and_keeps_the_rest() {
+        let path = std::env::temp_dir().join(format!(
+            "rogue_forest-config-{}/config.toml",
+            std::process::id()
+        ));
+        set_value(&path, "board_split", "60").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "board_split = 60\n");
+        fs::write(&path, "dim = 8\nboard_split = 60\nseed = 3\n").unwrap();
+        set_value(&path, "board_split", "75").unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(text, "dim = 8\nboard_split = 75\nseed = 3\n");
+        assert_eq!(Config::parse(&text).unwrap().board_split, Some(75));
+    }
+
+    #[test]
+    fn the_last_setup_is_kept_next_to_the_flags() {
+        let path = std::env::temp_dir().join(format!(
+            "rogue_forest-last-setup-{}/config.toml",
+            std::process::id()
+        ));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# mine\ndim = 8\n").unwrap();
+        let mut setup = Setup {
+            size: 10,
+            difficulty: Level::Hard,
+            seed: None,
+            mutators: vec![Mutator::Barren],
+        };
+        save_last_setup(&path, &setup).unwrap();
+        assert_eq!(
+            Config::load(&path).unwrap().last_setup.as_ref(),
+            Some(&setup)
+        );
+        setup.seed = Some(7);
+        save_last_setup(&path, &setup).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let config = Config::load(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert!(
+            text.starts_with("# mine\ndim = 8\nlast_setup = {"),
+            "{text}"
+        );
+        assert_eq!(text.lines().count(), 3);
+        assert_eq!((config.dim, config.last_setup), (Some(8), Some(setup)));
+
+        // Written by hand it reads too, other tables are still refused
+        let hand = Config::parse("last_setup = { size = 4, difficulty = \"Easy\" }").unwrap();
+        assert_eq!(hand.last_setup.map(|setup| setup.size), Some(4));
+        assert!(Config::parse("board = { dim = 8 }")
+            .unwrap_err()
+            .2
+            .contains("tables"));
+    }
+
+    #[test]
+    fn board_splits_outside_the_range_are_refused() {
+        assert!(Config::parse("board_split = 49").is_err());
+        assert!(Config::parse("board_split = 86").is_err());
+        assert_eq!(
+            Config::parse("board_split = 85").unwrap().board_split,
+            Some(85)
+        );
+    }
+}
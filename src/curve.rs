@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+/// How a plant's growth is spread over its life. In the plant JSON
+/// `"linear"`, or a curve with its parameters like
+/// `{"accelerating": {"step": 2}}`, where `{}` keeps the defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrowthCurve {
+    /// `size_per_turn` every round
+    #[default]
+    Linear,
+    /// `size_per_turn` in the first round, `step` more every round after
+    Accelerating {
+        #[serde(default = "one")]
+        step: u8,
+    },
+    /// Nothing until the last `rounds` rounds, which grow all of what
+    /// linear growth would in the whole life
+    Burst {
+        #[serde(default = "two")]
+        rounds: u8,
+    },
+}
+
+fn one() -> u8 {
+    1
+}
+
+fn two() -> u8 {
+    2
+}
+
+impl GrowthCurve {
+    pub fn is_linear(&self) -> bool {
+        *self == GrowthCurve::Linear
+    }
+}
+
+impl Display for GrowthCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrowthCurve::Linear => f.pad("linear"),
+            GrowthCurve::Accelerating { step } => write!(f, "accelerating, +{} a round", step),
+            GrowthCurve::Burst { rounds } => write!(f, "burst in the last {} rounds", rounds),
+        }
+    }
+}
+
+/// Size gained in fair weather by a plant `age` rounds old, out of
+/// `max_age`, that grows `size_per_turn` a round on a linear curve.
+pub fn growth(curve: GrowthCurve, size_per_turn: u32, age: u32, max_age: u32) -> u32 {
+    match curve {
+        GrowthCurve::Linear => size_per_turn,
+        GrowthCurve::Accelerating { step } => size_per_turn + u32::from(step) * age,
+        GrowthCurve::Burst { rounds } => {
+            let rounds = u32::from(rounds).clamp(1, max_age.max(1));
+            match age + rounds >= max_age {
+                true => (size_per_turn * max_age).div_ceil(rounds),
+                false => 0,
+            }
+        }
+    }
+}
+
+/// Size after growing from age 0 to `age` in fair weather.
+pub fn size_at(curve: GrowthCurve, size_per_turn: u32, age: u32, max_age: u32) -> u32 {
+    (0..age)
+        .map(|grown| growth(curve, size_per_turn, grown, max_age))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes(curve: GrowthCurve, max_age: u32) -> Vec<u32> {
+        (0..=max_age)
+            .map(|age| size_at(curve, 2, age, max_age))
+            .collect()
+    }
+
+    #[test]
+    fn linear_growth_adds_the_same_every_round() {
+        assert_eq!(sizes(GrowthCurve::Linear, 4), [0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn accelerating_growth_adds_a_step_more_every_round() {
+        let curve = GrowthCurve::Accelerating { step: 1 };
+        assert_eq!(sizes(curve, 4), [0, 2, 5, 9, 14]);
+        let curve = GrowthCurve::Accelerating { step: 3 };
+        assert_eq!(size_at(curve, 2, 4, 4), 2 + 5 + 8 + 11);
+    }
+
+    #[test]
+    fn bursts_grow_it_all_in_the_last_rounds() {
+        let curve = GrowthCurve::Burst { rounds: 2 };
+        assert_eq!(sizes(curve, 5), [0, 0, 0, 0, 5, 10]);
+        // At least what linear growth would have reached
+        let curve = GrowthCurve::Burst { rounds: 3 };
+        assert_eq!(sizes(curve, 4), [0, 0, 3, 6, 9]);
+        assert!(size_at(curve, 2, 4, 4) >= size_at(GrowthCurve::Linear, 2, 4, 4));
+    }
+
+    #[test]
+    fn bursts_longer_than_the_life_grow_every_round() {
+        let curve = GrowthCurve::Burst { rounds: 9 };
+        assert_eq!(sizes(curve, 2), [0, 2, 4]);
+        assert_eq!(growth(GrowthCurve::Burst { rounds: 0 }, 2, 0, 1), 2);
+    }
+
+    #[test]
+    fn curves_read_as_a_name_or_with_parameters() {
+        let read = |json: &str| serde_json::from_str::<GrowthCurve>(json).unwrap();
+        assert_eq!(read(r#""linear""#), GrowthCurve::Linear);
+        assert_eq!(
+            read(r#"{"accelerating": {}}"#),
+            GrowthCurve::Accelerating { step: 1 }
+        );
+        assert_eq!(
+            read(r#"{"accelerating": {"step": 2}}"#),
+            GrowthCurve::Accelerating { step: 2 }
+        );
+        assert_eq!(read(r#"{"burst": {}}"#), GrowthCurve::Burst { rounds: 2 });
+        assert!(serde_json::from_str::<GrowthCurve>(r#""sigmoid""#).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize,
+)]
+pub enum Level {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Easy => f.pad("Easy"),
+            Level::Normal => f.pad("Normal"),
+            Level::Hard => f.pad("Hard"),
+        }
+    }
+}
+
+/// The knobs a `--difficulty` bundles. Explicit flags like `--pest-chance`
+/// or `--rounds` are laid over it afterwards.
+#[derive(Debug, Clone)]
+pub struct Difficulty {
+    pub level: Level,
+    /// Names of the starting cards
+    pub hand: Vec<&'static str>,
+    pub round_limit: Option<u32>,
+    pub pest_chance: f32,
+    /// Chance that a harvest drops anything at all
+    pub drop_chance: f32,
+}
+
+impl Difficulty {
+    pub fn new(level: Level) -> Difficulty {
+        match level {
+            Level::Easy => Difficulty::easy(),
+            Level::Normal => Difficulty::normal(),
+            Level::Hard => Difficulty::hard(),
+        }
+    }
+
+    pub fn easy() -> Difficulty {
+        Difficulty {
+            level: Level::Easy,
+            hand: vec!["Grass", "Grass", "Shrub"],
+            round_limit: None,
+            pest_chance: 0.05,
+            drop_chance: 1.0,
+        }
+    }
+
+    pub fn normal() -> Difficulty {
+        Difficulty {
+            level: Level::Normal,
+            hand: vec!["Grass", "Grass"],
+            round_limit: None,
+            pest_chance: 0.1,
+            drop_chance: 1.0,
+        }
+    }
+
+    /// A single starting card, a round limit and a quarter of the
+    /// harvests drop nothing.
+    pub fn hard() -> Difficulty {
+        Difficulty {
+            level: Level::Hard,
+            hand: vec!["Grass"],
+            round_limit: Some(15),
+            pest_chance: 0.15,
+            drop_chance: 0.75,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! `--draft-every <ROUNDS>`: every so many rounds the run stops for a
+//! draft. Three rewards are rolled from the run's RNG, the player takes one
+//! and the others are gone. Esc takes none.
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+use crate::{
+    lang::{tr, tr_with},
+    Card,
+};
+
+/// Rewards a draft offers.
+pub const DRAFT_CHOICES: usize = 3;
+
+/// What the water reward adds to the round's water.
+pub const WATER_REWARD: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reward {
+    /// A card for the hand
+    Card(Card),
+    /// One more action this round, only offered with an action budget
+    ExtraAction,
+    /// Water to spend this round
+    Water(u32),
+}
+
+impl Reward {
+    /// The reward in the chosen language, for the draft panel.
+    pub fn label(&self) -> String {
+        match self {
+            Reward::Card(card) => card.display_name().to_string(),
+            Reward::ExtraAction => tr("draft.extra_action").to_string(),
+            Reward::Water(water) => tr_with("draft.water", &[water]),
+        }
+    }
+}
+
+/// In English, for the log.
+impl Display for Reward {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reward::Card(card) => f.pad(card.name()),
+            Reward::ExtraAction => f.pad("an extra action"),
+            Reward::Water(water) => write!(f, "{} water", water),
+        }
+    }
+}
+
+/// A draft waiting for a pick.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Draft {
+    pub rewards: Vec<Reward>,
+    /// Index into `rewards`
+    pub selected: usize,
+}
+
+impl Draft {
+    pub fn on_up(&mut self) {
+        let len = self.rewards.len().max(1);
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    pub fn on_down(&mut self) {
+        self.selected = (self.selected + 1) % self.rewards.len().max(1);
+    }
+
+    /// Selects a reward by its place in the list, false past the end.
+    pub fn select(&mut self, slot: usize) -> bool {
+        let valid = slot < self.rewards.len();
+        if valid {
+            self.selected = slot;
+        }
+        valid
+    }
+
+    /// The selected reward, the draft is over with it.
+    pub fn take(mut self) -> Option<Reward> {
+        (self.selected < self.rewards.len()).then(|| self.rewards.swap_remove(self.selected))
+    }
+}
+
+/// Whether the run drafts once `round` rounds are over.
+pub fn due(round: u32, every: Option<u32>) -> bool {
+    every.is_some_and(|every| round > 0 && round.is_multiple_of(every))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{action::Action, engine, Game, State};
+
+    /// The rewards of the first four drafts of a run on `seed`, each time
+    /// taking the first.
+    fn offers(seed: u64) -> Vec<Vec<String>> {
+        let config = engine::config_from_flags(&["--draft-every", "1", "--actions", "3"]).unwrap();
+        let plants = engine::builtin_plants(&config).unwrap();
+        let mut game = Game::with_plants(plants, Arc::new(config), seed);
+        let mut offers = Vec::new();
+        while offers.len() < 4 {
+            engine::play(&mut game, Action::NextRound);
+            assert_eq!(game.state, State::Drafting);
+            let draft = game.draft.as_ref().unwrap();
+            assert_eq!(draft.rewards.len(), DRAFT_CHOICES);
+            offers.push(draft.rewards.iter().map(Reward::to_string).collect());
+            engine::play(&mut game, Action::Place);
+        }
+        offers
+    }
+
+    #[test]
+    fn the_same_seed_drafts_the_same_rewards() {
+        for seed in 1..=5 {
+            assert_eq!(offers(seed), offers(seed));
+        }
+        assert!((2..=5).any(|seed| offers(seed) != offers(1)));
+    }
+}
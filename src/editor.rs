@@ -0,0 +1,387 @@
+//! The `--dev` plant editor: numbers of the loaded plants tweaked in play
+//! and written back as a plant file.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use serde::Serialize;
+
+use std::{collections::BTreeMap, fmt::Display, fs, path::PathBuf};
+
+use crate::{
+    curve::GrowthCurve, format_points, lang::tr, persist, theme::Theme, DropTarget, HybridRule,
+    Plant, Rarity,
+};
+
+/// A number of a plant the editor changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    MaxAge,
+    SizePerTurn,
+    PointsPerSize,
+    /// Weight of the drop at this index
+    DropChance(usize),
+}
+
+impl Field {
+    /// The fields `plant` has, in the order they are listed.
+    fn of(plant: &Plant) -> Vec<Field> {
+        let mut fields = vec![Field::MaxAge, Field::SizePerTurn, Field::PointsPerSize];
+        fields.extend((0..plant.drops.len()).map(Field::DropChance));
+        fields
+    }
+
+    /// Moves the field of `plant` one step up or down. Plants of a set that
+    /// mutators changed move by the same step.
+    pub fn adjust(self, plant: &mut Plant, up: bool) {
+        let sign = if up { 1.0 } else { -1.0 };
+        match self {
+            Field::MaxAge => plant.max_age = step_count(plant.max_age, up, 1),
+            Field::SizePerTurn => plant.size_per_turn = step_count(plant.size_per_turn, up, 0),
+            Field::PointsPerSize => {
+                plant.points_per_size = round_step(plant.points_per_size + 0.1 * sign);
+            }
+            Field::DropChance(at) => {
+                if let Some(drop) = plant.drops.get(at) {
+                    let chance = round_step(drop.chance + 0.05 * sign);
+                    plant.drops.set_chance(at, chance);
+                }
+            }
+        }
+    }
+
+    fn value(self, plant: &Plant) -> String {
+        match self {
+            Field::MaxAge => plant.max_age.to_string(),
+            Field::SizePerTurn => plant.size_per_turn.to_string(),
+            Field::PointsPerSize => format_points(plant.points_per_size),
+            Field::DropChance(at) => plant
+                .drops
+                .get(at)
+                .map(|drop| {
+                    format!(
+                        "{} ({})",
+                        format_points(drop.chance),
+                        drop.plants.join(" + ")
+                    )
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Field::MaxAge => f.write_str("Max age"),
+            Field::SizePerTurn => f.write_str("Size per turn"),
+            Field::PointsPerSize => f.write_str("Points per size"),
+            Field::DropChance(at) => write!(f, "Drop {} weight", at + 1),
+        }
+    }
+}
+
+fn step_count(value: u32, up: bool, min: u32) -> u32 {
+    match up {
+        true => value + 1,
+        false => value.saturating_sub(1).max(min),
+    }
+}
+
+/// Never below zero, and without the float noise of many small steps.
+fn round_step(value: f32) -> f32 {
+    ((value * 100.0).round() / 100.0).max(0.0)
+}
+
+/// A plant as it is written in a plant file, only what the file holds and
+/// in the order the files list it.
+#[derive(Serialize)]
+struct PlantEntry<'a> {
+    max_age: u32,
+    age: u32,
+    size_per_turn: u32,
+    size: u32,
+    points_per_size: f32,
+    class: char,
+    name: &'a str,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    names: &'a BTreeMap<String, String>,
+    short_display: char,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: &'a Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    family: &'a Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: &'a Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flavor: &'a Option<String>,
+    drops: Vec<DropEntry<'a>>,
+    rarity: Rarity,
+    spread_chance: f32,
+    #[serde(skip_serializing_if = "GrowthCurve::is_linear")]
+    growth_curve: GrowthCurve,
+    #[serde(skip_serializing_if = "is_zero")]
+    unlock_at_points: f32,
+}
+
+#[derive(Serialize)]
+struct DropEntry<'a> {
+    chance: f32,
+    plants: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires_adjacent: &'a Option<String>,
+    #[serde(skip_serializing_if = "DropTarget::is_hand")]
+    target: DropTarget,
+}
+
+fn is_zero(value: &f32) -> bool {
+    *value == 0.0
+}
+
+impl<'a> From<&'a Plant> for PlantEntry<'a> {
+    fn from(p: &'a Plant) -> PlantEntry<'a> {
+        PlantEntry {
+            max_age: p.max_age,
+            age: p.age,
+            size_per_turn: p.size_per_turn,
+            size: p.size,
+            points_per_size: p.points_per_size,
+            class: p.class,
+            name: &p.name,
+            names: &p.names,
+            short_display: p.short_display,
+            color: &p.color,
+            family: &p.family,
+            description: &p.description,
+            flavor: &p.flavor,
+            drops: p
+                .drops
+                .iter()
+                .map(|drop| DropEntry {
+                    chance: drop.chance,
+                    plants: &drop.plants,
+                    requires_adjacent: &drop.requires_adjacent,
+                    target: drop.target,
+                })
+                .collect(),
+            rarity: p.rarity,
+            spread_chance: p.spread_chance,
+            growth_curve: p.growth_curve,
+            unlock_at_points: p.unlock_at_points,
+        }
+    }
+}
+
+/// A plant file with hybrids.
+#[derive(Serialize)]
+struct PlantFileEntry<'a> {
+    plants: Vec<PlantEntry<'a>>,
+    hybrids: &'a [HybridRule],
+}
+
+/// `plants` as a plant file: pretty-printed with the four spaces the
+/// files in `assets` use, so saving twice gives the same bytes. Without
+/// `hybrids` it is a plain list of the plants.
+pub fn to_json(plants: &[Plant], hybrids: &[HybridRule]) -> String {
+    let entries = plants.iter().map(PlantEntry::from).collect::<Vec<_>>();
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut ser = serde_json::Serializer::with_formatter(&mut out, formatter);
+    match hybrids {
+        [] => entries.serialize(&mut ser),
+        hybrids => PlantFileEntry {
+            plants: entries,
+            hybrids,
+        }
+        .serialize(&mut ser),
+    }
+    .expect("plants always serialize");
+    let mut json = String::from_utf8(out).expect("serde_json writes UTF-8");
+    json.push('\n');
+    json
+}
+
+/// The editor while a run is going, open or not.
+pub struct Editor {
+    /// The plants as read from their files, before any mutator
+    pub plants: Vec<Plant>,
+    /// Written back with the plants, the editor doesn't change them
+    hybrids: Vec<HybridRule>,
+    plant: usize,
+    pub list: ListState,
+    field: usize,
+    /// Where saving writes to, `None` if that isn't clear
+    path: Option<PathBuf>,
+    /// What the file held when the run loaded its plants, `None` for a
+    /// file that didn't exist yet
+    on_disk: Option<String>,
+    pub msg: String,
+}
+
+impl Editor {
+    pub fn new(plants: Vec<Plant>, hybrids: Vec<HybridRule>, path: Option<PathBuf>) -> Editor {
+        let on_disk = path.as_ref().and_then(|p| fs::read_to_string(p).ok());
+        let mut list = ListState::default();
+        list.select(Some(0));
+        Editor {
+            plants,
+            hybrids,
+            plant: 0,
+            list,
+            field: 0,
+            path,
+            on_disk,
+            msg: String::from("Tab next plant, Up/Down pick, +/- change, s saves, Esc closes"),
+        }
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        self.plants
+            .get(self.plant)
+            .map(Field::of)
+            .unwrap_or_default()
+    }
+
+    /// Next or previous plant, by `by` places.
+    pub fn step_plant(&mut self, by: isize) {
+        let len = self.plants.len() as isize;
+        if len > 0 {
+            self.plant = (self.plant as isize + by).rem_euclid(len) as usize;
+            self.list.select(Some(self.plant));
+            self.field = self.field.min(self.fields().len().saturating_sub(1));
+        }
+    }
+
+    pub fn step_field(&mut self, by: isize) {
+        let len = self.fields().len() as isize;
+        if len > 0 {
+            self.field = (self.field as isize + by).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Moves the selected field and returns what changed for the game's
+    /// own copy of the plant: its name and the field.
+    pub fn adjust(&mut self, up: bool) -> Option<(String, Field)> {
+        let field = *self.fields().get(self.field)?;
+        let plant = self.plants.get_mut(self.plant)?;
+        field.adjust(plant, up);
+        Some((plant.name.to_string(), field))
+    }
+
+    /// Writes every plant to the file, unless the file changed since the
+    /// run loaded it.
+    pub fn save(&mut self) -> Result<PathBuf, String> {
+        let path = self.path.clone().ok_or_else(|| {
+            String::from("The plants come from several packs, start with --dev <file> to save them")
+        })?;
+        if fs::read_to_string(&path).ok() != self.on_disk {
+            return Err(format!(
+                "{} changed on disk since it was loaded, not overwriting it",
+                path.display()
+            ));
+        }
+        let json = to_json(&self.plants, &self.hybrids);
+        persist::write_atomic(&path, json.as_bytes())
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        self.on_disk = Some(json);
+        Ok(path)
+    }
+}
+
+pub fn draw(f: &mut Frame, editor: &mut Editor, theme: &Theme, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+        .margin(1)
+        .split(area);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+        .split(rows[0]);
+
+    let items = editor
+        .plants
+        .iter()
+        .map(|p| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", p.short_display)),
+                Span::styled(p.name.to_string(), theme.rarity(p.rarity)),
+            ]))
+        })
+        .collect::<Vec<ListItem>>();
+    let list = List::new(items)
+        .block(theme.panel(format!(" {} ", tr("editor.title")), true))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol);
+    f.render_stateful_widget(list, chunks[0], &mut editor.list);
+
+    let fields = match editor.plants.get(editor.plant) {
+        Some(plant) => Field::of(plant)
+            .into_iter()
+            .map(|field| ListItem::new(format!("{:<16} {}", field.to_string(), field.value(plant))))
+            .collect::<Vec<ListItem>>(),
+        None => Vec::new(),
+    };
+    let mut selected = ListState::default();
+    selected.select(Some(editor.field));
+    let fields = List::new(fields)
+        .block(theme.panel("", false))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol);
+    f.render_stateful_widget(fields, chunks[1], &mut selected);
+
+    let help = Paragraph::new(Span::styled(
+        editor.msg.clone(),
+        Style::default().fg(theme.label),
+    ))
+    .block(theme.panel("", false))
+    .wrap(Wrap { trim: true });
+    f.render_widget(help, rows[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{builtin_hybrids, builtin_plants, read_plants};
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-editor-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("plants.json")
+    }
+
+    #[test]
+    fn a_plant_file_changed_on_disk_is_not_overwritten() {
+        let path = scratch("changed");
+        let json = to_json(&builtin_plants(), &builtin_hybrids());
+        fs::write(&path, &json).unwrap();
+        let mut editor = Editor::new(builtin_plants(), builtin_hybrids(), Some(path.clone()));
+        editor.adjust(true);
+        assert_eq!(editor.save(), Ok(path.clone()));
+        let saved = read_plants(&path).unwrap();
+        assert_eq!(saved.plants[0].max_age, builtin_plants()[0].max_age + 1);
+        assert_eq!(saved.hybrids, builtin_hybrids());
+        // Its own last save doesn't count as a change
+        editor.adjust(true);
+        assert!(editor.save().is_ok());
+
+        fs::write(&path, &json).unwrap();
+        editor.adjust(true);
+        assert_eq!(
+            editor.save(),
+            Err(format!(
+                "{} changed on disk since it was loaded, not overwriting it",
+                path.display()
+            ))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), json);
+    }
+}
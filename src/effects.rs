@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+use crate::{curve, Plant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectKind {
+    /// Grows `magnitude` more size per round
+    GrowthBoost,
+    /// Doesn't grow in the `Grow` phase, whatever else boosts it
+    GrowthHalt,
+    /// Every size is worth `magnitude` times its points
+    PointMultiplier,
+    /// Loses `magnitude` size per round
+    DamagePerTurn,
+}
+
+/// A temporary change to a plant on the board. It counts down once the
+/// round is over and is gone once no rounds remain.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: EffectKind,
+    pub magnitude: f32,
+    pub remaining_rounds: u32,
+}
+
+impl Display for StatusEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            EffectKind::GrowthBoost => write!(f, "+{} size per round", self.magnitude),
+            EffectKind::GrowthHalt => f.write_str("No growth"),
+            EffectKind::PointMultiplier => write!(f, "x{} points", self.magnitude),
+            EffectKind::DamagePerTurn => write!(f, "-{} size per round", self.magnitude),
+        }
+    }
+}
+
+/// Adds an effect. Effects of the same kind don't stack, the plant keeps
+/// the stronger magnitude and the longer duration of the two.
+pub fn add(effects: &mut Vec<StatusEffect>, effect: StatusEffect) {
+    match effects.iter_mut().find(|e| e.kind == effect.kind) {
+        Some(old) => {
+            old.magnitude = old.magnitude.max(effect.magnitude);
+            old.remaining_rounds = old.remaining_rounds.max(effect.remaining_rounds);
+        }
+        None => effects.push(effect),
+    }
+}
+
+/// One round passed, drops the effects that ran out.
+pub fn tick(effects: &mut Vec<StatusEffect>) {
+    for effect in effects.iter_mut() {
+        effect.remaining_rounds = effect.remaining_rounds.saturating_sub(1);
+    }
+    effects.retain(|e| e.remaining_rounds > 0);
+}
+
+/// What a plant grows and scores with its effects, before weather and
+/// terrain. `size_per_turn` is this round's gain on its growth curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub size_per_turn: u32,
+    pub points_per_size: f32,
+    /// Size lost every round
+    pub damage: u32,
+    pub halted: bool,
+}
+
+/// The stats of `plant` under `effects`. The round update and the info
+/// panel both go through this.
+pub fn effective(plant: &Plant, effects: &[StatusEffect]) -> Stats {
+    effective_at(plant, effects, plant.age)
+}
+
+/// The stats of `plant` once it is `age` rounds old, for looking ahead
+/// along its growth curve.
+pub fn effective_at(plant: &Plant, effects: &[StatusEffect], age: u32) -> Stats {
+    let mut stats = Stats {
+        size_per_turn: curve::growth(plant.growth_curve, plant.size_per_turn, age, plant.max_age),
+        points_per_size: plant.points_per_size,
+        damage: 0,
+        halted: false,
+    };
+    for effect in effects {
+        let amount = effect.magnitude.max(0.0).round() as u32;
+        match effect.kind {
+            EffectKind::GrowthBoost => stats.size_per_turn += amount,
+            EffectKind::PointMultiplier => stats.points_per_size *= effect.magnitude.max(0.0),
+            EffectKind::DamagePerTurn => stats.damage += amount,
+            EffectKind::GrowthHalt => {}
+        }
+    }
+    if effects.iter().any(|e| e.kind == EffectKind::GrowthHalt) {
+        stats.size_per_turn = 0;
+        stats.halted = true;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tall_grass;
+
+    fn effect(kind: EffectKind, magnitude: f32, remaining_rounds: u32) -> StatusEffect {
+        StatusEffect {
+            kind,
+            magnitude,
+            remaining_rounds,
+        }
+    }
+
+    #[test]
+    fn the_same_kind_keeps_the_stronger_and_the_longer() {
+        let mut effects = Vec::new();
+        add(&mut effects, effect(EffectKind::GrowthBoost, 2.0, 1));
+        add(&mut effects, effect(EffectKind::GrowthBoost, 1.0, 3));
+        assert_eq!(effects, vec![effect(EffectKind::GrowthBoost, 2.0, 3)]);
+        add(&mut effects, effect(EffectKind::GrowthBoost, 3.0, 1));
+        assert_eq!(effects, vec![effect(EffectKind::GrowthBoost, 3.0, 3)]);
+    }
+
+    #[test]
+    fn different_kinds_stack() {
+        let mut effects = Vec::new();
+        add(&mut effects, effect(EffectKind::GrowthBoost, 2.0, 2));
+        add(&mut effects, effect(EffectKind::PointMultiplier, 1.5, 2));
+        add(&mut effects, effect(EffectKind::DamagePerTurn, 1.0, 2));
+        assert_eq!(effects.len(), 3);
+        let plant = tall_grass();
+        let stats = effective(&plant, &effects);
+        assert_eq!(stats.size_per_turn, plant.size_per_turn + 2);
+        assert_eq!(stats.points_per_size, plant.points_per_size * 1.5);
+        assert_eq!(stats.damage, 1);
+        assert!(!stats.halted);
+    }
+
+    #[test]
+    fn effects_run_out_after_their_rounds() {
+        let mut effects = vec![
+            effect(EffectKind::GrowthBoost, 1.0, 1),
+            effect(EffectKind::DamagePerTurn, 1.0, 2),
+        ];
+        tick(&mut effects);
+        assert_eq!(effects, vec![effect(EffectKind::DamagePerTurn, 1.0, 1)]);
+        tick(&mut effects);
+        assert!(effects.is_empty());
+        tick(&mut effects);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn a_halt_beats_any_boost() {
+        let effects = [
+            effect(EffectKind::GrowthBoost, 5.0, 2),
+            effect(EffectKind::GrowthHalt, 0.0, 2),
+        ];
+        let stats = effective(&tall_grass(), &effects);
+        assert_eq!(stats.size_per_turn, 0);
+        assert!(stats.halted);
+    }
+
+    #[test]
+    fn no_effects_leave_the_plant_as_it_is() {
+        let plant = tall_grass();
+        let stats = effective(&plant, &[]);
+        assert_eq!(stats.size_per_turn, plant.size_per_turn);
+        assert_eq!(stats.points_per_size, plant.points_per_size);
+        assert_eq!((stats.damage, stats.halted), (0, false));
+    }
+}
@@ -0,0 +1,221 @@
+//! The rules of a run without any terminal in the way. The TUI and the
+//! simulator both play through `play`, other frontends go through `Engine`
+//! and get told what happened as `GameEvent`s.
+//!
+//! ```
+//! use rogue_forest::{
+//!     action::Action,
+//!     engine::{self, Engine, GameEvent},
+//! };
+//!
+//! let config = engine::config_from_flags(&["--rounds", "3"])?;
+//! let plants = engine::builtin_plants(&config)?;
+//! let mut engine = Engine::new(config, plants, 42);
+//! let events = engine.apply(Action::NextRound);
+//! assert!(events
+//!     .iter()
+//!     .any(|event| matches!(event, GameEvent::RoundEnded { round: 0, .. })));
+//! assert_eq!(engine.view().round(), 1);
+//! # Ok::<(), String>(())
+//! ```
+
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use std::{iter, sync::Arc};
+
+use crate::{
+    action::Action, packs::Pack, report::RoundReport, Args, Game, GlobalSetting, PlantSet, State,
+};
+
+/// Everything the rules read about a run, fixed before it starts.
+pub type Config = GlobalSetting;
+
+/// A run as the rules see it.
+pub type GameState = Game;
+
+/// The settings of a run started without any flags.
+pub fn default_config() -> Config {
+    config_from_flags(&[]).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// The settings of a run started with `flags`, like `["--rounds", "5"]`.
+/// The config file is left out, flags are all there is.
+pub fn config_from_flags(flags: &[&str]) -> Result<Config, String> {
+    let args = Args::try_parse_from(iter::once("rogue_forest").chain(flags.iter().copied()))
+        .map_err(|err| err.to_string())?;
+    GlobalSetting::load(&args).map_err(|err| err.to_string())
+}
+
+/// The plants the game comes with, dealt the starting hand of `config`.
+pub fn builtin_plants(config: &Config) -> Result<PlantSet, String> {
+    let hand = config.difficulty.hand.iter().map(|name| name.to_string());
+    Pack::builtin(hand.collect()).plant_set()
+}
+
+/// What an action led to, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// A line of the event log
+    Logged(String),
+    /// The outcome of the action itself, for a status bar
+    Notice(String),
+    Harvested {
+        x: usize,
+        y: usize,
+        points: f32,
+    },
+    /// Total score once the round is over, and what the round did
+    RoundEnded {
+        round: u32,
+        score: f32,
+        report: RoundReport,
+    },
+    GameOver {
+        score: f32,
+    },
+}
+
+/// One run driven by actions.
+pub struct Engine {
+    game: Game,
+}
+
+impl Engine {
+    /// Starts a run on `config` with `plants`. Every engine keeps its own
+    /// settings, any number of them can play side by side.
+    pub fn new(config: Config, plants: PlantSet, seed: u64) -> Engine {
+        Engine {
+            game: Game::with_plants(plants, Arc::new(config), seed),
+        }
+    }
+
+    /// Plays `action` like a key press would and reports what came of it.
+    /// Actions that only concern a frontend, like `Codex`, do nothing.
+    pub fn apply(&mut self, action: Action) -> Vec<GameEvent> {
+        self.try_apply(action).1
+    }
+
+    /// Like `apply`, and whether the action meant anything in the state it
+    /// was played in.
+    pub fn try_apply(&mut self, action: Action) -> (bool, Vec<GameEvent>) {
+        let mark = Mark::of(&self.game);
+        let taken = play(&mut self.game, action);
+        let events = events_since(&self.game, &mark);
+        self.game.notice = None;
+        self.game.harvests.clear();
+        (taken, events)
+    }
+
+    /// Plays `action` without collecting what came of it, for callers
+    /// that only look at the game afterwards.
+    pub fn step(&mut self, action: Action) {
+        play(&mut self.game, action);
+        self.game.notice = None;
+        self.game.harvests.clear();
+    }
+
+    pub fn view(&self) -> &GameState {
+        &self.game
+    }
+}
+
+/// How far a run was, to tell what an action added to it.
+pub struct Mark {
+    logged: usize,
+    reports: usize,
+    over: bool,
+}
+
+impl Mark {
+    pub fn of(game: &Game) -> Mark {
+        Mark {
+            logged: game.log.len(),
+            reports: game.round_reports.len(),
+            over: game.state == State::GameOver,
+        }
+    }
+}
+
+/// What happened to `game` since `mark`. The notice and the harvests stay
+/// in the game for whoever shows them.
+pub fn events_since(game: &Game, mark: &Mark) -> Vec<GameEvent> {
+    let mut events = game.log[mark.logged.min(game.log.len())..]
+        .iter()
+        .cloned()
+        .map(GameEvent::Logged)
+        .collect::<Vec<GameEvent>>();
+    events.extend(game.notice.clone().map(GameEvent::Notice));
+    events.extend(
+        game.harvests
+            .iter()
+            .map(|&(x, y, points)| GameEvent::Harvested { x, y, points }),
+    );
+    for report in game.round_reports.iter().skip(mark.reports) {
+        events.push(GameEvent::RoundEnded {
+            round: report.round,
+            score: game.total_points(),
+            report: report.clone(),
+        });
+    }
+    if game.state == State::GameOver && !mark.over {
+        events.push(GameEvent::GameOver {
+            score: game.total_points(),
+        });
+    }
+    events
+}
+
+/// Drives a run that is already set up, like a loaded one.
+impl From<Game> for Engine {
+    fn from(game: Game) -> Engine {
+        Engine { game }
+    }
+}
+
+/// Applies the rules of `action` to `game`. False if the action means
+/// nothing in the current state.
+pub fn play(game: &mut Game, action: Action) -> bool {
+    if game.state == State::Drafting {
+        return game.on_draft(action);
+    }
+    match action {
+        Action::Tab => game.on_tab(),
+        Action::BackTab => game.on_back_tab(),
+        Action::Inspect => game.on_inspect(),
+        Action::NextRound => game.next_round(),
+        Action::Select(slot) => return game.quick_select(slot),
+        _ => match (game.state, action) {
+            (State::Choosing, Action::Down) => game.choosing.on_down(&game.visible_hand()),
+            (State::Choosing, Action::Up) => game.choosing.on_up(&game.visible_hand()),
+            (State::Choosing, Action::SortHand) => game.cycle_sort(),
+            (State::Choosing, Action::GroupHand) => game.toggle_groups(),
+            (State::Choosing, Action::ClearFilter) => game.set_filter(String::new()),
+            (State::Choosing, Action::QuickPlace) => game.on_quick_place(),
+            (State::Choosing, Action::Compost) => game.on_compost(),
+            (State::Placing, Action::Delete) => game.on_delete(),
+            (State::Placing, Action::ClearPest) => game.on_clear_pest(),
+            (State::Placing, Action::Water) => game.on_water(),
+            (State::Placing, Action::Compost) => game.on_fertilize(),
+            (State::Placing | State::Inspect, Action::Harvest) => game.on_pick(),
+            (State::Placing | State::Inspect, Action::Up) => game.placing.on_up(&game.settings),
+            (State::Placing | State::Inspect, Action::Down) => game.placing.on_down(&game.settings),
+            (State::Placing | State::Inspect, Action::Right) => {
+                game.placing.on_right(&game.settings)
+            }
+            (State::Placing | State::Inspect, Action::Left) => game.placing.on_left(&game.settings),
+            (
+                State::Placing | State::Inspect,
+                Action::TopEdge | Action::BottomEdge | Action::LeftEdge | Action::RightEdge,
+            ) => game.placing.on_edge(action, &game.settings),
+            (State::Placing | State::Inspect, Action::MoveTo(x, y))
+                if x < game.width() && y < game.height() =>
+            {
+                game.placing.on_move_to(x, y)
+            }
+            (State::Choosing | State::Placing | State::NextRound, Action::Place) => game.on_space(),
+            _ => return false,
+        },
+    }
+    true
+}
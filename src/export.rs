@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    difficulty::Level, ledger::ScoreLedger, mode::GameMode, persist, report::RoundReport,
+    terrain::Terrain, Game, Tile,
+};
+
+/// Bumped whenever a field of `Export` changes meaning or goes away. New
+/// fields can be added without a bump.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A finished (or abandoned) run for analysis outside the game. Unlike the
+/// save file it only holds plain data and never needs the game to be read.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Export {
+    pub schema_version: u32,
+    pub seed: u64,
+    pub config: Config,
+    pub score: f32,
+    /// What `score` is made of, for all players together
+    #[serde(default)]
+    pub ledger: ScoreLedger,
+    pub rounds: u32,
+    /// Time played, without the pauses
+    #[serde(default)]
+    pub played_ms: u64,
+    /// Points gained in every round, in order
+    pub round_deltas: Vec<f32>,
+    /// Every occupied tile at the time of the export
+    pub board: Vec<ExportTile>,
+    pub log: Vec<String>,
+    pub plants: BTreeMap<String, PlantStats>,
+    /// What every round update did, in order
+    #[serde(default)]
+    pub reports: Vec<RoundReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Config {
+    pub width: usize,
+    pub height: usize,
+    pub difficulty: Level,
+    #[serde(default)]
+    pub mode: GameMode,
+    pub round_limit: Option<u32>,
+    pub pest_chance: f32,
+    pub cloud_chance: f32,
+    pub drop_chance: f32,
+    pub actions_per_round: u32,
+    pub carryover_water: bool,
+    pub preset: Option<String>,
+    pub players: usize,
+    pub spread_multiplier: f32,
+    /// Per plant on the board at the end of a round
+    #[serde(default)]
+    pub tile_points: f32,
+    #[serde(default)]
+    pub full_board_bonus: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportTile {
+    pub x: usize,
+    pub y: usize,
+    pub terrain: Terrain,
+    pub plant: String,
+    pub age: u32,
+    #[serde(default)]
+    pub max_age: u32,
+    pub size: u32,
+    pub infested: bool,
+}
+
+/// What one species did over a run, kept up to date by the game.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlantStats {
+    pub placed: u32,
+    pub harvested: u32,
+    pub points: f32,
+    /// Dug up with `--overplant` to make room
+    #[serde(default)]
+    pub overplanted: u32,
+}
+
+impl Export {
+    pub fn from_game(game: &Game) -> Export {
+        let settings = &game.settings;
+        let round_deltas = game
+            .round_scores
+            .iter()
+            .scan(0.0, |last, &score| {
+                let delta = score - *last;
+                *last = score;
+                Some(delta)
+            })
+            .collect();
+        Export {
+            schema_version: SCHEMA_VERSION,
+            seed: game.seed,
+            config: Config {
+                width: game.width(),
+                height: game.height(),
+                difficulty: game.difficulty,
+                mode: game.mode,
+                round_limit: settings.round_limit,
+                pest_chance: settings.pest_chance,
+                cloud_chance: settings.cloud_chance,
+                drop_chance: settings.difficulty.drop_chance,
+                actions_per_round: game.actions_per_round,
+                carryover_water: settings.carryover_water,
+                preset: settings.preset.as_ref().map(|preset| preset.name.clone()),
+                players: game.players(),
+                spread_multiplier: settings.spread_multiplier,
+                tile_points: settings.tile_points,
+                full_board_bonus: settings.full_board_bonus,
+            },
+            score: game.total_points(),
+            ledger: game.total_ledger(),
+            rounds: game.round,
+            played_ms: game.played_ms,
+            round_deltas,
+            board: board(game),
+            log: game.log.clone(),
+            plants: game.plant_stats.clone(),
+            reports: game.round_reports.clone(),
+        }
+    }
+}
+
+/// Every occupied tile of `game`, row by row from the bottom.
+pub fn board(game: &Game) -> Vec<ExportTile> {
+    game.tile
+        .iter_coords()
+        .filter_map(|(x, y)| {
+            let tile = game.tile.get(x, y)?;
+            let plant = tile.plant()?;
+            Some(ExportTile {
+                x,
+                y,
+                terrain: game.terrain_at(x, y),
+                plant: plant.name.to_string(),
+                age: plant.age,
+                max_age: plant.max_age,
+                size: plant.size,
+                infested: matches!(tile, Tile::Infested(..)),
+            })
+        })
+        .collect()
+}
+
+/// Where `x` on the end screen exports to without `--export`.
+pub fn default_path() -> PathBuf {
+    persist::data_dir().join(format!("export-{}.json", persist::unix_now()))
+}
+
+pub fn write(path: &Path, game: &Game) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(&Export::from_game(game))?;
+    persist::write_atomic(path, json.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{action::Action, engine::play, tests::game, ScoreCategory};
+
+    #[test]
+    fn exports_read_back_into_the_typed_schema() {
+        let mut game = game(&["--pest-chance", "0"]);
+        for action in [Action::Place, Action::Place, Action::NextRound] {
+            play(&mut game, action);
+        }
+        for _ in 0..3 {
+            play(&mut game, Action::NextRound);
+        }
+        let path = std::env::temp_dir().join(format!(
+            "rogue_forest-export-{}/run.json",
+            std::process::id()
+        ));
+        write(&path, &game).unwrap();
+        let export: Export = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(export.schema_version, SCHEMA_VERSION);
+        assert_eq!((export.seed, export.rounds), (game.seed, 4));
+        assert_eq!(export.config.width, game.width());
+        assert_eq!(export.round_deltas.len(), 4);
+        let summed = export.round_deltas.iter().sum::<f32>();
+        assert!((summed - export.score).abs() < 1e-4, "{summed}");
+        assert!(export.score > 0.0);
+        assert_eq!(export.plants["Grass"].placed, 1);
+        assert_eq!(export.log, game.log);
+        assert_eq!(export.reports.len(), 4);
+        assert_eq!(
+            export.board.len(),
+            game.tile.iter().filter_map(Tile::plant).count()
+        );
+    }
+
+    #[test]
+    fn round_deltas_include_the_passives() {
+        let mut game = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+        for action in [
+            Action::Place,
+            Action::Place,
+            Action::NextRound,
+            Action::NextRound,
+        ] {
+            play(&mut game, action);
+        }
+        let export = Export::from_game(&game);
+        let passives = game.ledger.get(ScoreCategory::Passives);
+        assert!(passives > 0.0);
+        let summed = export.round_deltas.iter().sum::<f32>();
+        assert!(
+            (summed - game.points()).abs() < 1e-4,
+            "{summed} {}",
+            game.points()
+        );
+        assert!((export.round_deltas[0] - 0.1).abs() < 1e-6);
+        assert_eq!(export.ledger, game.total_ledger());
+    }
+}
@@ -0,0 +1,221 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{difficulty::Level, format_points, mode::GameMode, objectives::Status, persist, Game};
+
+/// Finished games kept in the history, the oldest are dropped first.
+pub const MAX_GAMES: usize = 1000;
+
+/// Species listed as the most planted.
+const TOP_PLANTS: usize = 5;
+
+/// What a finished game leaves behind for `stats`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSummary {
+    /// Unix timestamp of the end of the run
+    pub timestamp: u64,
+    pub score: f32,
+    pub rounds: u32,
+    pub width: usize,
+    pub height: usize,
+    pub difficulty: Level,
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Cards placed, by plant name
+    #[serde(default)]
+    pub planted: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub objectives: u32,
+    /// Objectives reached before the end
+    #[serde(default)]
+    pub objectives_done: u32,
+}
+
+impl GameSummary {
+    pub fn from_game(game: &Game) -> GameSummary {
+        GameSummary {
+            timestamp: persist::unix_now(),
+            score: game.points(),
+            rounds: game.round,
+            width: game.width(),
+            height: game.height(),
+            difficulty: game.difficulty,
+            mode: game.mode,
+            planted: game
+                .plant_stats
+                .iter()
+                .filter(|(_, stats)| stats.placed > 0)
+                .map(|(name, stats)| (name.clone(), stats.placed))
+                .collect(),
+            objectives: game.objectives.len() as u32,
+            objectives_done: game
+                .objectives
+                .iter()
+                .filter(|o| o.status == Status::Done)
+                .count() as u32,
+        }
+    }
+}
+
+/// Every finished solo game, up to `MAX_GAMES`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    games: Vec<GameSummary>,
+}
+
+pub fn history_path() -> PathBuf {
+    persist::data_dir().join("history.json")
+}
+
+impl History {
+    /// Reads the history, starting a fresh one if the file is missing. A
+    /// corrupt file is backed up and replaced, like the score table.
+    pub fn load(path: &Path) -> io::Result<History> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(err) => return Err(err),
+        };
+        match serde_json::from_str(&contents) {
+            Ok(history) => Ok(history),
+            Err(_) => {
+                persist::back_up_corrupt(path)?;
+                Ok(History::default())
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string(self)?;
+        persist::write_atomic(path, json.as_bytes())
+    }
+
+    /// Adds a game, dropping the oldest ones past `MAX_GAMES`.
+    pub fn push(&mut self, game: GameSummary) {
+        self.games.push(game);
+        let over = self.games.len().saturating_sub(MAX_GAMES);
+        self.games.drain(..over);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Plain text columns
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BoardStats {
+    /// Like `6x6`
+    pub board: String,
+    pub difficulty: Level,
+    pub games: usize,
+    pub average_score: f32,
+}
+
+/// Totals over the whole history.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub games: usize,
+    pub rounds: u32,
+    pub boards: Vec<BoardStats>,
+    /// Placements by plant, most first
+    pub most_planted: Vec<(String, u32)>,
+    pub objectives: u32,
+    pub objectives_done: u32,
+    /// Share of the objectives reached, 0 without any
+    pub objective_rate: f32,
+}
+
+pub fn stats(history: &History) -> Stats {
+    let mut boards = BTreeMap::<(usize, usize, Level), (usize, f32)>::new();
+    let mut planted = BTreeMap::<&str, u32>::new();
+    for game in &history.games {
+        let (games, score) = boards
+            .entry((game.width, game.height, game.difficulty))
+            .or_default();
+        *games += 1;
+        *score += game.score;
+        for (name, count) in &game.planted {
+            *planted.entry(name).or_default() += count;
+        }
+    }
+    let mut most_planted = planted
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect::<Vec<(String, u32)>>();
+    // Stable, so ties stay in name order
+    most_planted.sort_by_key(|(_, count)| Reverse(*count));
+    most_planted.truncate(TOP_PLANTS);
+    let objectives = history.games.iter().map(|g| g.objectives).sum::<u32>();
+    let objectives_done = history.games.iter().map(|g| g.objectives_done).sum::<u32>();
+    Stats {
+        games: history.games.len(),
+        rounds: history.games.iter().map(|g| g.rounds).sum(),
+        boards: boards
+            .into_iter()
+            .map(|((width, height, difficulty), (games, score))| BoardStats {
+                board: format!("{}x{}", width, height),
+                difficulty,
+                games,
+                average_score: score / games as f32,
+            })
+            .collect(),
+        most_planted,
+        objectives,
+        objectives_done,
+        objective_rate: match objectives {
+            0 => 0.0,
+            _ => objectives_done as f32 / objectives as f32,
+        },
+    }
+}
+
+/// Prints the stats for `stats --format table`.
+pub fn print_stats(stats: &Stats) {
+    if stats.games == 0 {
+        println!("No finished games yet");
+        return;
+    }
+    println!("Games: {}", stats.games);
+    println!("Rounds played: {}", stats.rounds);
+    println!(
+        "Objectives reached: {} of {} ({:.0}%)",
+        stats.objectives_done,
+        stats.objectives,
+        stats.objective_rate * 100.0
+    );
+    println!();
+    println!(
+        "{:<8} {:<10} {:>6} {:>10}",
+        "Board", "Difficulty", "Games", "Avg score"
+    );
+    for board in &stats.boards {
+        println!(
+            "{:<8} {:<10} {:>6} {:>10}",
+            board.board,
+            board.difficulty,
+            board.games,
+            format_points(board.average_score)
+        );
+    }
+    if !stats.most_planted.is_empty() {
+        println!();
+        println!("{:<16} {:>8}", "Most planted", "Placed");
+        for (name, count) in &stats.most_planted {
+            println!("{:<16} {:>8}", name, count);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+use crate::{effects::StatusEffect, Rarity};
+
+/// Every item a drop can name, see `assets/items.json`.
+const ITEMS: &str = include_str!("../assets/items.json");
+
+/// A card that is used on a plant already on the board instead of being
+/// planted itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Item {
+    pub name: String,
+    pub short_display: char,
+    pub effect: Effect,
+    #[serde(default)]
+    pub rarity: Rarity,
+    /// Rounds left in hand before it goes stale, like `Plant::freshness`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Effect {
+    /// The target grows `size` at once
+    Grow { size: u32 },
+    /// The target is harvested right away for `share` of its points,
+    /// without drops
+    Harvest { share: f32 },
+    /// The target is under the effect for a few rounds
+    Status(StatusEffect),
+}
+
+impl Display for Effect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Effect::Grow { size } => write!(f, "Grows a plant by {}", size),
+            Effect::Harvest { share } => {
+                write!(
+                    f,
+                    "Harvests a plant now for {:.0}% of its points",
+                    share * 100.0
+                )
+            }
+            Effect::Status(effect) => {
+                write!(f, "{} for {} rounds", effect, effect.remaining_rounds)
+            }
+        }
+    }
+}
+
+/// Index of an item in `all()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ItemId(pub u32);
+
+impl ItemId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+pub fn all() -> Vec<Item> {
+    serde_json::from_str(ITEMS).expect("assets/items.json is invalid")
+}
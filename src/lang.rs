@@ -0,0 +1,232 @@
+//! `--lang`: the labels of the screens in another language. The English
+//! table is built in, a translation in `assets/lang/<code>.json` only needs
+//! the keys it translates. What it leaves out, or a file that isn't there,
+//! falls back to English.
+//!
+//! Values with `{}` are filled in by `tr_with`, in the order the code gives
+//! the arguments. The log and the event log stay in English, they are
+//! what bug reports quote.
+
+use once_cell::sync::OnceCell;
+use tracing::warn;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+/// Folder the translations are read from.
+pub const LANG_DIR: &str = "assets/lang";
+
+const ENGLISH: &str = include_str!("../assets/lang/en.json");
+
+struct Translation {
+    code: String,
+    /// `None` for English and for a language without a file
+    strings: Option<BTreeMap<String, String>>,
+}
+
+static CHOSEN: OnceCell<Translation> = OnceCell::new();
+
+/// Keys already warned about, so a missing one is logged once a run.
+static WARNED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+fn english() -> &'static BTreeMap<String, String> {
+    static PARSED: OnceCell<BTreeMap<String, String>> = OnceCell::new();
+    PARSED.get_or_init(|| serde_json::from_str(ENGLISH).expect("assets/lang/en.json is invalid"))
+}
+
+/// `de` from `de_DE.UTF-8`, nothing for the `C` and `POSIX` locales.
+fn from_locale(locale: &str) -> Option<String> {
+    let code = locale
+        .split(['_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match code.as_str() {
+        "" | "c" | "posix" => None,
+        _ => Some(code),
+    }
+}
+
+/// Picks the language for the rest of the process: `lang`, or `LANG` when
+/// that isn't given. Call it once, before anything is drawn.
+pub fn init(lang: Option<&str>) {
+    let code = match lang {
+        Some(lang) => Some(lang.to_ascii_lowercase()),
+        None => std::env::var("LANG").ok().as_deref().and_then(from_locale),
+    };
+    let code = code.unwrap_or_else(|| String::from("en"));
+    let path = PathBuf::from(LANG_DIR).join(&code).with_extension("json");
+    let strings = match code.as_str() {
+        "en" => None,
+        _ => fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| match serde_json::from_str(&text) {
+                Ok(strings) => Some(strings),
+                Err(err) => {
+                    warn!("{}: {}", path.display(), err);
+                    None
+                }
+            }),
+    };
+    let _ = CHOSEN.set(Translation { code, strings });
+}
+
+/// The language code in use, `en` until `init` picks another.
+pub fn code() -> &'static str {
+    CHOSEN.get().map_or("en", |chosen| chosen.code.as_str())
+}
+
+fn warn_once(key: &'static str, msg: impl FnOnce() -> String) {
+    if let Ok(mut warned) = WARNED.lock() {
+        if warned.insert(key) {
+            warn!("{}", msg());
+        }
+    }
+}
+
+/// The text for `key` in the chosen language.
+pub fn tr(key: &'static str) -> &'static str {
+    if let Some((code, strings)) = CHOSEN
+        .get()
+        .and_then(|chosen| Some((&chosen.code, chosen.strings.as_ref()?)))
+    {
+        match strings.get(key) {
+            Some(text) => return text,
+            None => warn_once(key, || format!("No {} text for `{}`", code, key)),
+        }
+    }
+    match english().get(key) {
+        Some(text) => text,
+        None => {
+            warn_once(key, || format!("No text at all for `{}`", key));
+            key
+        }
+    }
+}
+
+/// `tr(key)` with every `{}` replaced by the next of `args`.
+pub fn tr_with(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = tr(key).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+/// The entry of `names` for the chosen language, `fallback` without one.
+pub fn name<'a>(names: &'a BTreeMap<String, String>, fallback: &'a str) -> &'a str {
+    names.get(code()).map_or(fallback, String::as_str)
+}
+
+/// `tr(key)` as the label in front of a value, like `Score: `.
+pub fn label(key: &'static str) -> String {
+    format!("{}: ", tr(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(path: &str) -> BTreeMap<String, String> {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Every string in the code that looks like `hand.title`, for any of
+    /// the groups the English table has.
+    fn keys_in_code() -> BTreeSet<String> {
+        let groups = english()
+            .keys()
+            .filter_map(|key| Some(key.split_once('.')?.0))
+            .collect::<BTreeSet<_>>();
+        let mut keys = BTreeSet::new();
+        for entry in fs::read_dir("src").unwrap() {
+            let code = fs::read_to_string(entry.unwrap().path()).unwrap();
+            for part in code.split('"') {
+                let Some((group, rest)) = part.split_once('.') else {
+                    continue;
+                };
+                let looks_like_a_key = !rest.is_empty()
+                    && !["json", "txt", "toml"].contains(&rest)
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c == '_' || c == '.');
+                if looks_like_a_key && groups.contains(group) {
+                    keys.insert(part.to_string());
+                }
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn every_key_in_the_code_has_english_text() {
+        let keys = keys_in_code();
+        assert!(keys.contains("hand.title"));
+        assert!(keys.contains("score.harvest"));
+        let missing = keys
+            .iter()
+            .filter(|key| !english().contains_key(*key))
+            .collect::<Vec<_>>();
+        assert!(missing.is_empty(), "not in en.json: {:?}", missing);
+    }
+
+    #[test]
+    fn german_translates_every_key_with_the_same_blanks() {
+        let german = table("assets/lang/de.json");
+        for (key, text) in english() {
+            let translated = german
+                .get(key)
+                .unwrap_or_else(|| panic!("de.json misses {}", key));
+            assert_eq!(
+                translated.matches("{}").count(),
+                text.matches("{}").count(),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn the_language_comes_from_the_locale() {
+        assert_eq!(from_locale("de_DE.UTF-8").as_deref(), Some("de"));
+        assert_eq!(from_locale("FR").as_deref(), Some("fr"));
+        assert_eq!(from_locale("en_US@euro").as_deref(), Some("en"));
+        assert_eq!(from_locale("C.UTF-8"), None);
+        assert_eq!(from_locale("POSIX"), None);
+        assert_eq!(from_locale(""), None);
+    }
+
+    #[test]
+    fn the_blanks_are_filled_in_order() {
+        assert_eq!(
+            tr_with("too_small", &[&80, &24]),
+            "Terminal too small, need at least 80x24"
+        );
+        assert_eq!(tr_with("draft.water", &[]), " water this round");
+        assert_eq!(label("label.score"), "Score: ");
+    }
+
+    #[test]
+    fn an_unknown_key_shows_itself() {
+        assert_eq!(tr("no.such_key"), "no.such_key");
+    }
+
+    #[test]
+    fn plant_names_fall_back_without_the_language() {
+        let names = BTreeMap::from([("de".to_string(), "Gras".to_string())]);
+        assert_eq!(code(), "en");
+        assert_eq!(name(&names, "Grass"), "Grass");
+        let names = BTreeMap::from([("en".to_string(), "Meadow grass".to_string())]);
+        assert_eq!(name(&names, "Grass"), "Meadow grass");
+    }
+}
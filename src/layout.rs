@@ -0,0 +1,391 @@
+//! `--board <file>`: a run that starts from a board laid out by hand, for
+//! puzzles and for sharing a situation.
+//!
+//! ```text
+//! # Two Grass about to mature next to the river
+//! round = 3
+//! points = 12.5
+//! hand = Grass, Tall Grass
+//!
+//! .  .   ~  .
+//! w1 "w2 ~  .
+//! .  "   ~  ^
+//! .  .   ~  Y0
+//! ```
+//!
+//! `key = value` lines set where the run is at, every other line is a row
+//! of the board, from the top one down. A tile is a terrain, `.` plain,
+//! `"` fertile, `~` water and `^` rocks, or a plant as its short display
+//! with its age, on plain soil or after a `"` on fertile. A plant without
+//! an age was just promoted. `#` starts a comment.
+
+use std::{fs, path::Path};
+
+use crate::{board::Board, terrain::Terrain, PlantSet};
+
+/// What the `key = value` lines can set.
+const KEYS: [&str; 3] = ["round", "points", "hand"];
+
+/// A plant laid on the board, by name.
+#[derive(Debug, Clone)]
+pub struct LaidPlant {
+    pub x: usize,
+    pub y: usize,
+    pub name: String,
+    pub age: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoardLayout {
+    pub terrain: Board<Terrain>,
+    pub plants: Vec<LaidPlant>,
+    pub round: Option<u32>,
+    pub points: Option<f32>,
+    /// Replaces the starting hand
+    pub hand: Option<Vec<String>>,
+}
+
+/// Reads the layout at `path` for a `width` x `height` board of the plants
+/// in `set`. Errors point at the line and column in the file.
+pub fn load(
+    path: &Path,
+    set: &PlantSet,
+    width: usize,
+    height: usize,
+) -> Result<BoardLayout, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    parse(&text, set, width, height)
+        .map_err(|err| format!("{}:{}:{}: {}", path.display(), err.line, err.col, err.msg))
+}
+
+/// Where in the file something went wrong, `line:col: message` once shown.
+struct ParseError {
+    line: usize,
+    col: usize,
+    msg: String,
+}
+
+impl ParseError {
+    fn at(line: usize, col: usize, msg: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            col,
+            msg: msg.into(),
+        }
+    }
+}
+
+fn parse(
+    text: &str,
+    set: &PlantSet,
+    width: usize,
+    height: usize,
+) -> Result<BoardLayout, ParseError> {
+    let mut layout = BoardLayout {
+        terrain: Board::filled(width, height, Terrain::Plain),
+        plants: Vec::new(),
+        round: None,
+        points: None,
+        hand: None,
+    };
+    let mut rows = 0;
+    let mut last = 0;
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        last = line;
+        let content = raw.split('#').next().unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+        if let Some((key, value)) = content.split_once('=') {
+            let key_col = column(raw, key.trim_start());
+            // One after the board would be easy to overlook
+            if rows > 0 {
+                return Err(ParseError::at(line, key_col, "keys go before the board"));
+            }
+            let col = match KEYS.contains(&key.trim()) {
+                true => column(raw, value.trim_start()),
+                false => key_col,
+            };
+            read_key(&mut layout, set, key.trim(), value.trim())
+                .map_err(|msg| ParseError::at(line, col, msg))?;
+            continue;
+        }
+        if rows == height {
+            return Err(ParseError::at(
+                line,
+                column(raw, content.trim_start()),
+                format!("the board is only {} rows high", height),
+            ));
+        }
+        read_row(&mut layout, set, width, height - 1 - rows, content)
+            .map_err(|(col, msg)| ParseError::at(line, col, msg))?;
+        rows += 1;
+    }
+    if rows < height {
+        return Err(ParseError::at(
+            last + 1,
+            1,
+            format!("{} rows for a board {} rows high", rows, height),
+        ));
+    }
+    Ok(layout)
+}
+
+/// A `width` x `height` board from its rows alone, the top one first, for
+/// files that keep the rest of the run elsewhere. Errors name the row and
+/// column.
+pub fn from_rows(
+    rows: &[String],
+    set: &PlantSet,
+    width: usize,
+    height: usize,
+) -> Result<BoardLayout, String> {
+    if rows.len() != height {
+        return Err(format!(
+            "{} rows for a board {} rows high",
+            rows.len(),
+            height
+        ));
+    }
+    let mut layout = BoardLayout {
+        terrain: Board::filled(width, height, Terrain::Plain),
+        plants: Vec::new(),
+        round: None,
+        points: None,
+        hand: None,
+    };
+    for (i, row) in rows.iter().enumerate() {
+        read_row(&mut layout, set, width, height - 1 - i, row)
+            .map_err(|(col, msg)| format!("row {}, column {}: {}", i + 1, col, msg))?;
+    }
+    Ok(layout)
+}
+
+/// Lays out the `width` tiles of `row` as row `y`. Errors come with the
+/// 1-based column they are in.
+fn read_row(
+    layout: &mut BoardLayout,
+    set: &PlantSet,
+    width: usize,
+    y: usize,
+    row: &str,
+) -> Result<(), (usize, String)> {
+    let tokens = tokens(row);
+    if tokens.len() != width {
+        let col = tokens
+            .get(width)
+            .map_or(row.chars().count() + 1, |&(col, _)| col);
+        let msg = format!(
+            "{} tiles in this row, the board is {} wide",
+            tokens.len(),
+            width
+        );
+        return Err((col, msg));
+    }
+    for (x, (col, token)) in tokens.into_iter().enumerate() {
+        read_tile(layout, set, x, y, token).map_err(|msg| (col, msg))?;
+    }
+    Ok(())
+}
+
+/// 1-based column of `part`, a slice of `line`.
+fn column(line: &str, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+/// The tiles of a row with their 1-based columns.
+fn tokens(row: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, (at, c)) in row.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((i + 1, at)),
+            (true, Some((col, from))) => {
+                tokens.push((col, &row[from..at]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((col, from)) = start {
+        tokens.push((col, &row[from..]));
+    }
+    tokens
+}
+
+fn read_key(
+    layout: &mut BoardLayout,
+    set: &PlantSet,
+    key: &str,
+    value: &str,
+) -> Result<(), String> {
+    match key {
+        "round" => {
+            let round = value
+                .parse()
+                .map_err(|_| format!("`{}` is not a round number", value))?;
+            layout.round = Some(round);
+        }
+        "points" => {
+            let points = value
+                .parse::<f32>()
+                .ok()
+                .filter(|points| points.is_finite())
+                .ok_or_else(|| format!("`{}` is not a number of points", value))?;
+            layout.points = Some(points);
+        }
+        "hand" => {
+            let names = value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect::<Vec<String>>();
+            if let Some(name) = names.iter().find(|name| set.card_id(name).is_none()) {
+                return Err(format!("no plant or item is called <{}>", name));
+            }
+            layout.hand = Some(names);
+        }
+        _ => {
+            return Err(format!(
+                "unknown key `{}`, use one of {}",
+                key,
+                KEYS.join(", ")
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn read_tile(
+    layout: &mut BoardLayout,
+    set: &PlantSet,
+    x: usize,
+    y: usize,
+    token: &str,
+) -> Result<(), String> {
+    let (terrain, rest) = match token.strip_prefix('"') {
+        Some(rest) => (Terrain::Fertile, rest),
+        None => match token {
+            "." => (Terrain::Plain, ""),
+            "~" | "≈" => (Terrain::Water, ""),
+            "^" => (Terrain::Rock, ""),
+            _ => (Terrain::Plain, token),
+        },
+    };
+    layout.terrain.set(x, y, terrain);
+    let mut chars = rest.chars();
+    let Some(short) = chars.next() else {
+        return Ok(());
+    };
+    let mut named = set.plants.iter().filter(|p| p.short_display == short);
+    let plant = match (named.next(), named.next()) {
+        (Some(plant), None) => plant,
+        (Some(a), Some(b)) => {
+            return Err(format!(
+                "`{}` stands for both {} and {}",
+                short, a.name, b.name
+            ))
+        }
+        (None, _) => return Err(format!("no plant is shown as `{}`", short)),
+    };
+    let age = match chars.as_str() {
+        "" => 0,
+        age => age
+            .parse::<u32>()
+            .map_err(|_| format!("`{}` is not the age of {}", age, plant.name))?,
+    };
+    if age > plant.max_age {
+        return Err(format!(
+            "{} is at most {} rounds old, not {}",
+            plant.name, plant.max_age, age
+        ));
+    }
+    layout.plants.push(LaidPlant {
+        x,
+        y,
+        name: plant.name.to_string(),
+        age,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine;
+
+    fn plants() -> PlantSet {
+        engine::builtin_plants(&engine::default_config()).unwrap()
+    }
+
+    /// `line:col: message` of a layout that doesn't parse on a 3x3 board.
+    fn error(text: &str) -> String {
+        let err = parse(text, &plants(), 3, 3).err().unwrap();
+        format!("{}:{}: {}", err.line, err.col, err.msg)
+    }
+
+    #[test]
+    fn a_layout_sets_the_terrain_plants_and_run() {
+        let layout = parse(
+            "round = 3\nhand = Grass\n\n. ~ w1\n\"w . .\n^ . . # rocks",
+            &plants(),
+            3,
+            3,
+        )
+        .unwrap_or_else(|err| panic!("{}:{}: {}", err.line, err.col, err.msg));
+        assert_eq!(
+            (layout.round, layout.hand),
+            (Some(3), Some(vec!["Grass".into()]))
+        );
+        assert_eq!(layout.terrain.get(1, 2), Some(&Terrain::Water));
+        assert_eq!(layout.terrain.get(0, 1), Some(&Terrain::Fertile));
+        assert_eq!(layout.terrain.get(0, 0), Some(&Terrain::Rock));
+        let laid = layout
+            .plants
+            .iter()
+            .map(|p| (p.x, p.y, p.name.as_str(), p.age))
+            .collect::<Vec<_>>();
+        assert_eq!(laid, [(2, 2, "Grass", 1), (0, 1, "Grass", 0)]);
+    }
+
+    #[test]
+    fn errors_point_at_their_line_and_column() {
+        assert_eq!(
+            error(". . .\n. q1 .\n. . ."),
+            "2:3: no plant is shown as `q`"
+        );
+        assert_eq!(
+            error(". . .\n. . .\n.  .  .  ."),
+            "3:10: 4 tiles in this row, the board is 3 wide"
+        );
+        assert_eq!(
+            error(". . .\n. .\n. . ."),
+            "2:4: 2 tiles in this row, the board is 3 wide"
+        );
+        assert_eq!(
+            error("round =  x\n. . .\n. . .\n. . ."),
+            "1:10: `x` is not a round number"
+        );
+        assert_eq!(error(". . .\nseed = 1"), "2:1: keys go before the board");
+        assert_eq!(
+            error("  seed = 1"),
+            "1:3: unknown key `seed`, use one of round, points, hand"
+        );
+        assert_eq!(
+            error(". . .\n. . .\n"),
+            "3:1: 2 rows for a board 3 rows high"
+        );
+    }
+
+    #[test]
+    fn rows_alone_name_the_row_and_column() {
+        let rows = [". . .", ". w99 .", ". . ."].map(String::from);
+        assert_eq!(
+            from_rows(&rows, &plants(), 3, 3).err().unwrap(),
+            "row 2, column 3: Grass is at most 2 rounds old, not 99"
+        );
+    }
+}
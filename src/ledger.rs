@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::lang::tr;
+
+/// Where a share of the score came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreCategory {
+    /// What a `--board` file starts the run with
+    Starting,
+    /// Size times points per size of everything harvested
+    Harvest,
+    /// What the streak multiplier added on top of harvests
+    Streak,
+    /// Points for the plants on the board and a full board
+    Passives,
+    /// The collection bonus for families on the board
+    Families,
+    Objectives,
+    /// Negative, like overplanting
+    Penalties,
+}
+
+impl Display for ScoreCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(tr(match self {
+            ScoreCategory::Starting => "score.starting",
+            ScoreCategory::Harvest => "score.harvest",
+            ScoreCategory::Streak => "score.streak",
+            ScoreCategory::Passives => "score.passives",
+            ScoreCategory::Families => "score.families",
+            ScoreCategory::Objectives => "score.objectives",
+            ScoreCategory::Penalties => "score.penalties",
+        }))
+    }
+}
+
+/// A player's score by category. It is only ever added to, so the total
+/// always adds up to what the categories show.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ScoreLedger {
+    entries: BTreeMap<ScoreCategory, f32>,
+}
+
+impl ScoreLedger {
+    pub fn add(&mut self, category: ScoreCategory, points: f32) {
+        if points != 0.0 {
+            *self.entries.entry(category).or_default() += points;
+        }
+    }
+
+    pub fn get(&self, category: ScoreCategory) -> f32 {
+        self.entries.get(&category).copied().unwrap_or(0.0)
+    }
+
+    pub fn total(&self) -> f32 {
+        self.entries.values().sum()
+    }
+
+    /// Every category that scored, in the order of `ScoreCategory`.
+    pub fn lines(&self) -> impl Iterator<Item = (ScoreCategory, f32)> + '_ {
+        self.entries
+            .iter()
+            .map(|(&category, &points)| (category, points))
+    }
+
+    /// Both ledgers together, for the scores of all players.
+    pub fn merged(&self, other: &ScoreLedger) -> ScoreLedger {
+        let mut merged = self.clone();
+        for (category, points) in other.lines() {
+            merged.add(category, points);
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_total_is_the_sum_of_the_categories() {
+        let mut ledger = ScoreLedger::default();
+        ledger.add(ScoreCategory::Harvest, 4.0);
+        ledger.add(ScoreCategory::Penalties, -1.5);
+        ledger.add(ScoreCategory::Harvest, 2.0);
+        assert_eq!(ledger.get(ScoreCategory::Harvest), 6.0);
+        assert_eq!(ledger.get(ScoreCategory::Streak), 0.0);
+        assert_eq!(ledger.total(), 4.5);
+    }
+
+    #[test]
+    fn only_categories_that_scored_are_listed_in_order() {
+        let mut ledger = ScoreLedger::default();
+        ledger.add(ScoreCategory::Objectives, 5.0);
+        ledger.add(ScoreCategory::Streak, 0.0);
+        ledger.add(ScoreCategory::Harvest, 1.0);
+        let lines = ledger.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                (ScoreCategory::Harvest, 1.0),
+                (ScoreCategory::Objectives, 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn merged_ledgers_add_up_per_category() {
+        let mut first = ScoreLedger::default();
+        first.add(ScoreCategory::Harvest, 1.0);
+        let mut second = ScoreLedger::default();
+        second.add(ScoreCategory::Harvest, 2.0);
+        second.add(ScoreCategory::Families, 3.0);
+        let merged = first.merged(&second);
+        assert_eq!(merged.get(ScoreCategory::Harvest), 3.0);
+        assert_eq!(merged.total(), first.total() + second.total());
+    }
+
+    #[test]
+    fn ledgers_save_as_a_map_of_categories() {
+        let mut ledger = ScoreLedger::default();
+        ledger.add(ScoreCategory::Passives, 0.5);
+        let json = serde_json::to_string(&ledger).unwrap();
+        assert_eq!(json, r#"{"passives":0.5}"#);
+        assert_eq!(serde_json::from_str::<ScoreLedger>(&json).unwrap(), ledger);
+    }
+}
@@ -0,0 +1,10297 @@
+//! The rules of Rogue Forest and the terminal game built on them. Other
+//! frontends drive the rules through `engine::Engine`, the binary is `run`.
+
+use action::Action;
+use ascii::{AsciiBoard, Renderer};
+use autosave::Autosave;
+use board::{tile_name, Board};
+use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use config::Config;
+use crossterm::{
+    event::{self, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    execute,
+};
+use curve::GrowthCurve;
+use difficulty::{Difficulty, Level};
+use draft::{Draft, Reward};
+use editor::Editor;
+use effects::{Stats, StatusEffect};
+use engine::GameEvent;
+use export::PlantStats;
+use history::{Format, GameSummary, History};
+use items::{Effect, Item, ItemId};
+use lang::{tr, tr_with};
+use layout::BoardLayout;
+use ledger::{ScoreCategory, ScoreLedger};
+use mode::{Ending, GameMode};
+use mutators::Mutator;
+use objectives::{Goal, Objective, Status};
+use packs::Pack;
+use profile::Profile;
+use puzzle::Puzzle;
+use rand::{prng::XorShiftRng, Rng, SeedableRng};
+use records::Records;
+use report::{Matured, Passive, RoundReport};
+use scores::{ScoreEntry, ScoreTable};
+use serde::{Deserialize, Serialize};
+use simulate::PolicyName;
+use slots::{MenuKind, SlotMenu};
+use spectate::Publisher;
+use terrain::{BoardPreset, Terrain};
+use theme::{Theme, ThemeName};
+use trace::LogLevel;
+use tutorial::Tutorial;
+use weather::{Cloud, CloudKind, Weather};
+
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        canvas::{self, Canvas, Context, Rectangle},
+        Clear, List, ListItem, ListState, Paragraph, Wrap,
+    },
+    Frame, Terminal,
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    fs,
+    io::{self},
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+mod achievements;
+pub mod action;
+mod ascii;
+mod autosave;
+mod board;
+mod config;
+mod curve;
+mod difficulty;
+mod draft;
+mod editor;
+mod effects;
+pub mod engine;
+mod export;
+mod history;
+mod items;
+mod lang;
+mod layout;
+mod ledger;
+mod mode;
+mod mutators;
+mod objectives;
+mod packs;
+mod persist;
+mod profile;
+mod puzzle;
+mod records;
+pub mod report;
+mod save;
+mod scores;
+mod serve;
+mod setup;
+mod simulate;
+mod slots;
+mod spectate;
+mod terrain;
+mod theme;
+mod trace;
+mod tutorial;
+mod validate;
+mod weather;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Tiles along each side of the board
+    #[arg(short, long, default_value_t = 6, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(setup::MIN_SIZE as u64..=setup::MAX_SIZE as u64))]
+    dim: usize,
+    /// Seed for the game RNG, random if not given
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Bundles the starting hand, round limit, pest and drop chances
+    #[arg(long, value_enum, default_value_t = Level::Normal)]
+    difficulty: Level,
+    /// How long the run goes: the difficulty's limit, a sprint of 10 rounds,
+    /// or endless with ever fewer drops. Overrides --rounds.
+    #[arg(long, value_enum, default_value_t = GameMode::Standard)]
+    mode: GameMode,
+    /// Chance per round that a pest infests a random plant [default: set by the difficulty]
+    #[arg(long)]
+    pest_chance: Option<f32>,
+    /// Chance that a harvest drops anything [default: set by the difficulty]
+    #[arg(long)]
+    drop_chance: Option<f32>,
+    /// Chance per round that a rain cloud or drought drifts onto the board
+    #[arg(long, default_value_t = 0.2)]
+    cloud_chance: f32,
+    /// End the run after this many rounds, harvesting what is left [default: set by the difficulty]
+    #[arg(long)]
+    rounds: Option<u32>,
+    /// Card placements per round, 0 for unlimited
+    #[arg(long, default_value_t = 2)]
+    actions: u32,
+    /// Keep unused water for the next round instead of losing it
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    carryover_water: bool,
+    /// Allow placing onto taken tiles: a plant placed this round goes back
+    /// to the hand, an older one is dug up for a point penalty
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    overplant: bool,
+    /// Starting terrain layout, one of the files in assets/presets
+    #[arg(long)]
+    preset: Option<String>,
+    /// Write the run as JSON for analysis to this file once it ends
+    #[arg(long)]
+    export: Option<PathBuf>,
+    /// Keep the latest game state in this file for `spectate`
+    #[arg(long)]
+    publish: Option<PathBuf>,
+    /// Color theme, cycle through them in game with F2
+    #[arg(long, value_enum, default_value_t = ThemeName::Default)]
+    theme: ThemeName,
+    /// Language of the screens, like `de`, from `assets/lang`. Without it
+    /// `LANG` picks one, and English fills in what a language lacks
+    #[arg(long)]
+    lang: Option<String>,
+    /// How the board is drawn, switch in game with F3
+    #[arg(long, value_enum, default_value_t = Renderer::Canvas)]
+    renderer: Renderer,
+    /// Play the actions in this file (one per line) before handing over the keyboard
+    #[arg(long)]
+    script: Option<PathBuf>,
+    /// Don't open the terminal UI, play the script and print the final state as JSON
+    #[arg(long, requires = "script")]
+    headless: bool,
+    /// Print the high score tables and exit
+    #[arg(long)]
+    scores: bool,
+    /// Scales the spread chance of every plant
+    #[arg(long, default_value_t = 1.0)]
+    spread_multiplier: f32,
+    /// Points every plant still on the board scores at the end of a round
+    #[arg(long, default_value_t = 0.1)]
+    tile_points: f32,
+    /// No points at the end of a round for the plants on the board or for
+    /// a full board, only harvests score
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    no_passives: bool,
+    /// Show the drops a plant about to mature has already rolled
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    show_fate: bool,
+    /// Show the weather of the coming round in the Next Round panel
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    forecast: bool,
+    /// Rounds left before the harvest from which a plant shows as maturing
+    #[arg(long, default_value_t = 2)]
+    maturing_at: u32,
+    /// Moving the board cursor past an edge comes back in on the other side
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    wrap_cursor: bool,
+    /// Percent of the width the board gets, change it in game with < and >
+    #[arg(long, default_value_t = 70, value_parser = clap::value_parser!(u16).range(MIN_BOARD_SPLIT as i64..=MAX_BOARD_SPLIT as i64))]
+    board_split: u16,
+    /// Plant families on the board at once that earn a collection bonus, 0
+    /// for none
+    #[arg(long, default_value_t = 3)]
+    family_threshold: usize,
+    /// Players taking turns on the same board
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=4))]
+    players: u8,
+    /// Play against the CPU, each on one half of the board
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set, conflicts_with_all = ["players", "tutorial", "puzzle"])]
+    vs_ai: bool,
+    /// How the CPU of `--vs-ai` plays
+    #[arg(long, value_enum, default_value_t = PolicyName::Greedy)]
+    ai_policy: PolicyName,
+    /// Don't keep the running game on disk between rounds
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    no_autosave: bool,
+    /// Milliseconds between two animation ticks
+    #[arg(long, default_value_t = 100)]
+    tick_ms: u64,
+    /// Draw the board as plain text and describe every change in the status
+    /// bar, for screen readers
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    plain: bool,
+    /// Don't show the time played, it is still kept for the scores
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    no_timer: bool,
+    /// End the round on its own once the last move is played, instead of
+    /// asking for space
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    auto_advance: bool,
+    /// List identical cards in the hand as one row with a count, G toggles
+    /// it during a run
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    group_hand: bool,
+    /// Show how many times a second the screen is drawn
+    #[arg(long)]
+    debug_fps: bool,
+    /// Write game events, state changes, keys and errors to this file
+    #[arg(long, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+    /// What `--log-file` records
+    #[arg(long, value_enum, default_value_t = LogLevel::Info)]
+    log_level: LogLevel,
+    /// Profile that banks seeds from finished runs, to spend on the next ones
+    #[arg(long, default_value = "default")]
+    profile: String,
+    /// Play without a profile: no seeds, no loadout screen
+    #[arg(long, default_value_t = false, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true", action = ArgAction::Set)]
+    no_profile: bool,
+    /// Learn the game step by step on a small board first, Esc skips to a
+    /// normal run
+    #[arg(long, conflicts_with_all = ["script", "headless"])]
+    tutorial: bool,
+    /// Start from the board laid out in this file: rows of tiles like `.`,
+    /// `~` or the plant `w2`, after `round`, `points` or `hand = ...` lines
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["preset", "tutorial"])]
+    board: Option<PathBuf>,
+    /// The puzzle of `--mode puzzle`, a file or the name of one in
+    /// assets/puzzles
+    #[arg(long, conflicts_with_all = ["board", "preset", "tutorial"])]
+    puzzle: Option<PathBuf>,
+    /// Play on the run saved in a slot, the load menu (L) lists them
+    #[arg(long, value_name = "SLOT", conflicts_with = "tutorial")]
+    load: Option<String>,
+    /// Print the records of the profile and exit
+    #[arg(long)]
+    records: bool,
+    /// Whether ripe plants are harvested on their own, or stay overripe
+    /// until they are picked with h
+    #[arg(long, value_enum, default_value_t = HarvestMode::Auto)]
+    harvest_mode: HarvestMode,
+    /// Rounds an overripe plant can still be picked before it withers
+    #[arg(long, default_value_t = 3)]
+    overripe_rounds: u32,
+    /// Rounds a card can stay unplayed in hand before it goes stale
+    /// [default: cards keep]
+    #[arg(long, value_name = "ROUNDS")]
+    freshness: Option<u32>,
+    /// What a stale card turns into
+    #[arg(long, value_enum, default_value_t = StaleCards::Compost)]
+    stale_cards: StaleCards,
+    /// Pick one of three rewards after every this many rounds [default: no
+    /// drafts]
+    #[arg(long, value_name = "ROUNDS", value_parser = clap::value_parser!(u32).range(1..))]
+    draft_every: Option<u32>,
+    /// Rule changes for the whole run, comma-separated
+    #[arg(long, value_enum, value_delimiter = ',')]
+    mutator: Vec<Mutator>,
+    /// Plant pack from assets/packs to play with instead of the built-in
+    /// plants, `all` for every pack at once
+    #[arg(long)]
+    pack: Option<String>,
+    /// Developer tools: F6 opens a plant editor for balancing
+    #[arg(long)]
+    dev: bool,
+    /// File the plant editor saves to [default: the file of the plants]
+    #[arg(long, requires = "dev")]
+    dev_plants: Option<PathBuf>,
+    /// Earn achievements even with --dev or a script
+    #[arg(long)]
+    allow_achievements: bool,
+    /// Print the settings in effect, config.toml with the flags over it, and
+    /// exit
+    #[arg(long)]
+    print_config: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Watch a game started with `--publish` without interfering with it
+    Spectate { path: PathBuf },
+    /// Check a plants file and print what is wrong with it. Exits non-zero
+    /// on errors, warnings alone pass.
+    Validate { path: PathBuf },
+    /// Play many games without a terminal and print score statistics. The
+    /// other flags still set up the games, `--seed` seeds the first one.
+    Simulate {
+        #[arg(long, default_value_t = 1000)]
+        games: usize,
+        #[arg(long, value_enum, default_value_t = PolicyName::Greedy)]
+        policy: PolicyName,
+        /// Rounds per game [default: --rounds, or 20 without one]
+        #[arg(long)]
+        rounds: Option<u32>,
+        /// Print the report as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print totals over every finished solo game: games, rounds, average
+    /// scores per board and difficulty, favourite plants and objectives.
+    Stats {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Play one run for a bot, in newline-delimited JSON. The other flags
+    /// set up the run like they do for the terminal.
+    Serve {
+        /// Commands on stdin, the state after each on stdout
+        #[arg(long, required = true)]
+        stdio: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct GlobalSetting {
+    width: usize,
+    height: usize,
+    seed: u64,
+    pest_chance: f32,
+    cloud_chance: f32,
+    round_limit: Option<u32>,
+    actions_per_round: u32,
+    carryover_water: bool,
+    overplant: bool,
+    preset: Option<BoardPreset>,
+    players: usize,
+    /// The CPU's policy with `--vs-ai`
+    ai: Option<PolicyName>,
+    spread_multiplier: f32,
+    /// Per plant at the end of a round, 0 with `--no-passives`
+    tile_points: f32,
+    /// For a board without a free plantable tile, 0 with `--no-passives`
+    full_board_bonus: f32,
+    show_fate: bool,
+    forecast: bool,
+    maturing_at: u32,
+    wrap_cursor: bool,
+    family_threshold: usize,
+    mode: GameMode,
+    /// With the explicit flags, mutators and the mode already laid over it
+    difficulty: Difficulty,
+    mutators: Vec<Mutator>,
+    harvest_mode: HarvestMode,
+    overripe_rounds: u32,
+    freshness: Option<u32>,
+    stale_cards: StaleCards,
+    draft_every: Option<u32>,
+    /// `--board`, checked against the plants
+    board: Option<BoardLayout>,
+    /// `--puzzle`, its board is in `board`
+    puzzle: Option<Puzzle>,
+}
+
+impl GlobalSetting {
+    fn load(args: &Args) -> Result<GlobalSetting, std::io::Error> {
+        // The config file and the setup screen don't go through clap
+        if !(setup::MIN_SIZE..=setup::MAX_SIZE).contains(&args.dim) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "board size {} is not between {} and {}",
+                    args.dim,
+                    setup::MIN_SIZE,
+                    setup::MAX_SIZE
+                ),
+            ));
+        }
+        let mut difficulty = Difficulty::new(args.difficulty);
+        if let Some(chance) = args.pest_chance {
+            difficulty.pest_chance = chance;
+        }
+        if let Some(chance) = args.drop_chance {
+            difficulty.drop_chance = chance;
+        }
+        if args.rounds.is_some() {
+            difficulty.round_limit = args.rounds;
+        }
+        let mut mutators = Vec::new();
+        for &mutator in &args.mutator {
+            if !mutators.contains(&mutator) {
+                mutators.push(mutator);
+            }
+        }
+        mutators::adjust_difficulty(&mutators, &mut difficulty);
+        difficulty.round_limit = args.mode.round_limit(difficulty.round_limit);
+        Ok(GlobalSetting {
+            width: args.dim,
+            height: args.dim,
+            seed: args.seed.unwrap_or_else(rand::random),
+            pest_chance: difficulty.pest_chance,
+            cloud_chance: args.cloud_chance,
+            round_limit: difficulty.round_limit,
+            actions_per_round: args.actions,
+            carryover_water: args.carryover_water,
+            overplant: args.overplant,
+            preset: None,
+            players: match args.vs_ai {
+                true => 2,
+                false => args.players as usize,
+            },
+            ai: args.vs_ai.then_some(args.ai_policy),
+            spread_multiplier: args.spread_multiplier,
+            tile_points: if args.no_passives {
+                0.0
+            } else {
+                args.tile_points
+            },
+            full_board_bonus: if args.no_passives {
+                0.0
+            } else {
+                FULL_BOARD_BONUS
+            },
+            show_fate: args.show_fate,
+            forecast: args.forecast,
+            maturing_at: args.maturing_at,
+            mode: args.mode,
+            wrap_cursor: args.wrap_cursor,
+            family_threshold: args.family_threshold,
+            difficulty,
+            mutators,
+            harvest_mode: args.harvest_mode,
+            overripe_rounds: args.overripe_rounds,
+            freshness: args.freshness,
+            stale_cards: args.stale_cards,
+            draft_every: args.draft_every,
+            board: None,
+            puzzle: None,
+        })
+    }
+
+    /// What a deserialized run has until it is handed its settings.
+    fn detached() -> Arc<GlobalSetting> {
+        Arc::new(GlobalSetting::for_board(0, 0))
+    }
+
+    /// Settings for showing someone else's board, nothing is simulated.
+    fn for_board(width: usize, height: usize) -> GlobalSetting {
+        GlobalSetting {
+            width,
+            height,
+            seed: 0,
+            pest_chance: 0.0,
+            cloud_chance: 0.0,
+            round_limit: None,
+            actions_per_round: 0,
+            carryover_water: false,
+            overplant: false,
+            preset: None,
+            players: 1,
+            ai: None,
+            spread_multiplier: 0.0,
+            tile_points: 0.0,
+            full_board_bonus: 0.0,
+            show_fate: false,
+            forecast: false,
+            maturing_at: 2,
+            mode: GameMode::Standard,
+            wrap_cursor: false,
+            family_threshold: 0,
+            difficulty: Difficulty::normal(),
+            mutators: Vec::new(),
+            harvest_mode: HarvestMode::Auto,
+            overripe_rounds: 0,
+            freshness: None,
+            stale_cards: StaleCards::Compost,
+            draft_every: None,
+            board: None,
+            puzzle: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum State {
+    Choosing,
+    Placing,
+    NextRound,
+    /// The board cursor moves freely to look at tiles, nothing is placed
+    Inspect,
+    /// A round that ends a stretch of `--draft-every` offers its rewards,
+    /// nothing else can be done until one is taken or skipped
+    Drafting,
+    /// The run is over, only the end screen is left
+    GameOver,
+}
+
+/// The steps of a round update, run by `update_game` in the order of
+/// `ROUND_PHASES`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RoundPhase {
+    /// Pests may infest a permanent plant, never one placed this round
+    Pests,
+    /// Permanent plants age and grow, infested ones shrink. Nothing grows
+    /// in a drought.
+    Grow,
+    /// Rain grows every plant, a storm uproots one plant placed this round
+    Sky,
+    /// Watered plants grow one extra size, in time for the harvest
+    Water,
+    /// Plants at max age are harvested in row-major order, pest victims die
+    Mature,
+    /// Plants still standing score a little each, a full board a bonus.
+    /// Tiles the harvest just emptied don't count
+    Passives,
+    /// Permanent plants may creep onto an empty neighbor tile, the copy is
+    /// promoted right away like a placed plant
+    Spread,
+    /// Plants placed this round become permanent. This runs after `Grow`, so
+    /// a plant only starts aging the round after it was placed.
+    Promote,
+    /// Clouds move on after they watered or dried their tiles
+    Weather,
+}
+
+/// How plants at max age leave the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+enum HarvestMode {
+    /// Harvested in the `Mature` phase of the round they get ripe
+    #[default]
+    Auto,
+    /// Overripe for a few rounds, picked by the player for full points or
+    /// withered for none
+    Manual,
+}
+
+/// What becomes of a card that stayed in hand for longer than `--freshness`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+enum StaleCards {
+    /// Goes onto the compost, like c would put it there
+    #[default]
+    Compost,
+    /// Is lost
+    Discard,
+}
+
+/// Rounds of freshness from which on the hand shows a card's in warning
+/// colors.
+const STALE_SOON: u32 = 1;
+
+/// Water handed out at the start of every round.
+const WATER_PER_ROUND: u32 = 3;
+
+/// Extra share of the points for every round of the harvest streak, up to
+/// `MAX_STREAK_MULTIPLIER` times the points.
+const STREAK_BONUS: f32 = 0.1;
+const MAX_STREAK_MULTIPLIER: f32 = 2.0;
+
+/// What harvests are worth after `streak` productive rounds in a row.
+fn streak_multiplier(streak: u32) -> f32 {
+    (1.0 + STREAK_BONUS * streak as f32).min(MAX_STREAK_MULTIPLIER)
+}
+
+/// Points per family for having `--family-threshold` or more of them on
+/// the board at once.
+const FAMILY_BONUS: f32 = 2.0;
+
+/// Points for a board without a free plantable tile at the end of a round.
+const FULL_BOARD_BONUS: f32 = 3.0;
+
+/// Cards a reserve holds at most, the oldest are lost beyond that.
+const RESERVE_LIMIT: usize = 100;
+
+/// The seat the CPU plays with `--vs-ai`, it moves second.
+const AI_SEAT: usize = 1;
+
+/// Moves the CPU may take in one turn before it is made to end it, in case
+/// its policy stops making progress.
+const MAX_AI_STEPS: usize = 1_000;
+
+/// Points lost for digging up a plant from an earlier round with
+/// `--overplant`.
+const OVERPLANT_PENALTY: f32 = 2.0;
+
+const ROUND_PHASES: [RoundPhase; 9] = [
+    RoundPhase::Pests,
+    RoundPhase::Grow,
+    RoundPhase::Sky,
+    RoundPhase::Water,
+    RoundPhase::Mature,
+    RoundPhase::Passives,
+    RoundPhase::Spread,
+    RoundPhase::Promote,
+    RoundPhase::Weather,
+];
+
+/// Order of the Plants list. The hand itself stays in the order the cards
+/// came in, only the list is sorted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum HandSort {
+    #[default]
+    Dealt,
+    Name,
+    /// Best projected points on the cursor tile first
+    Points,
+    MaxAge,
+}
+
+impl HandSort {
+    fn next(self) -> HandSort {
+        match self {
+            HandSort::Dealt => HandSort::Name,
+            HandSort::Name => HandSort::Points,
+            HandSort::Points => HandSort::MaxAge,
+            HandSort::MaxAge => HandSort::Dealt,
+        }
+    }
+}
+
+impl Display for HandSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandSort::Dealt => f.write_str("dealt"),
+            HandSort::Name => f.write_str("name"),
+            HandSort::Points => f.write_str("points"),
+            HandSort::MaxAge => f.write_str("max age"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChoosingState {
+    /// Position of the selected card in the hand, not in the (sorted,
+    /// filtered) list. `None` exactly when the list is empty, with an empty
+    /// hand or a filter that hides every card. `Game::keep_selection`
+    /// restores this after every change to the hand.
+    index: Option<usize>,
+    choice: Option<Card>,
+    #[serde(default)]
+    sort: HandSort,
+    /// Only cards with this in their name are listed
+    #[serde(default)]
+    filter: String,
+    /// Identical cards share a row, which stands for the first of them. A
+    /// view preference, it comes from `--group-hand` and not from saves.
+    #[serde(skip)]
+    grouped: bool,
+}
+
+impl ChoosingState {
+    /// Selects the card listed after the selected one, `visible` being the
+    /// hand positions in list order.
+    fn on_down(&mut self, visible: &[usize]) {
+        if visible.is_empty() {
+            self.index = None;
+            return;
+        }
+        let pos = self
+            .index
+            .and_then(|index| visible.iter().position(|&i| i == index));
+        self.index = Some(visible[pos.map_or(0, |pos| (pos + 1) % visible.len())]);
+    }
+
+    fn on_up(&mut self, visible: &[usize]) {
+        if visible.is_empty() {
+            self.index = None;
+            return;
+        }
+        let pos = self
+            .index
+            .and_then(|index| visible.iter().position(|&i| i == index));
+        let len = visible.len();
+        self.index = Some(visible[pos.map_or(0, |pos| (pos + len - 1) % len)]);
+    }
+
+    /// Keeps the index inside a hand of `len` cards, clearing it when the
+    /// hand is empty and picking the first card when a hand appears.
+    fn clamp(&mut self, len: usize) {
+        self.index = match self.index {
+            _ if len == 0 => None,
+            Some(index) => Some(index.min(len - 1)),
+            None => Some(0),
+        };
+    }
+
+    fn _on_space(&mut self, _game: &mut Game) {}
+}
+
+impl Default for ChoosingState {
+    fn default() -> Self {
+        Self {
+            index: Some(0),
+            choice: None,
+            sort: HandSort::default(),
+            filter: String::new(),
+            grouped: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct PlacingState {
+    x: usize,
+    y: usize,
+}
+
+impl PlacingState {
+    /// In the middle of a board of `settings`, rounded up, but still on a
+    /// board one tile wide.
+    fn centered(settings: &GlobalSetting) -> PlacingState {
+        PlacingState {
+            x: ((settings.width as f64 / 2.0).round() as usize).min(settings.width - 1),
+            y: ((settings.height as f64 / 2.0).round() as usize).min(settings.height - 1),
+        }
+    }
+
+    fn on_up(&mut self, settings: &GlobalSetting) {
+        self.y = step(self.y, true, settings.height, settings.wrap_cursor);
+    }
+
+    fn on_down(&mut self, settings: &GlobalSetting) {
+        self.y = step(self.y, false, settings.height, settings.wrap_cursor);
+    }
+
+    fn on_right(&mut self, settings: &GlobalSetting) {
+        self.x = step(self.x, true, settings.width, settings.wrap_cursor);
+    }
+
+    fn on_left(&mut self, settings: &GlobalSetting) {
+        self.x = step(self.x, false, settings.width, settings.wrap_cursor);
+    }
+
+    /// Jumps to an edge of the board, wrapping or not.
+    fn on_edge(&mut self, action: Action, settings: &GlobalSetting) {
+        match action {
+            Action::TopEdge => self.y = settings.height - 1,
+            Action::BottomEdge => self.y = 0,
+            Action::LeftEdge => self.x = 0,
+            Action::RightEdge => self.x = settings.width - 1,
+            _ => {}
+        }
+    }
+
+    /// Straight to a tile, like one that was clicked.
+    fn on_move_to(&mut self, x: usize, y: usize) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn _on_space(self) {}
+}
+
+/// One tile on from `at` along a row or column of `len` tiles. Past the
+/// edge it stays put, or comes back in on the other side with `wrap`.
+fn step(at: usize, forward: bool, len: usize, wrap: bool) -> usize {
+    match (forward, wrap) {
+        (true, true) => (at + 1) % len,
+        (true, false) => (at + 1).min(len - 1),
+        (false, true) => (at + len - 1) % len,
+        (false, false) => at.saturating_sub(1),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Game {
+    state: State,
+    tile: Board<Tile>,
+    /// Ground under each tile, plain when missing
+    #[serde(default)]
+    terrain: Board<Terrain>,
+    hand: Vec<Card>,
+    /// Drops that didn't fit under the hand limit, face down and oldest
+    /// first. The hand refills from it at the start of every turn.
+    #[serde(default)]
+    reserve: Vec<Card>,
+    /// Every known plant, a `PlantId` is an index into it
+    id_to_plant: Vec<Plant>,
+    /// Every known item, an `ItemId` is an index into it
+    items: Vec<Item>,
+    /// Crossings that can drop an extra plant when neighbors mature
+    #[serde(default)]
+    hybrids: Vec<Hybrid>,
+    /// Score of the player on turn, by where it came from
+    ledger: ScoreLedger,
+    round: u32,
+    placing: PlacingState,
+    choosing: ChoosingState,
+    rng: XorShiftRng,
+    /// What the RNG was seeded with, to replay the run
+    #[serde(default)]
+    seed: u64,
+    /// Pests cleared this round, each one costs a card placement
+    pest_actions: usize,
+    /// Card placements allowed per round, 0 for unlimited
+    #[serde(default)]
+    actions_per_round: u32,
+    /// Cards placed this round
+    #[serde(default)]
+    placed: u32,
+    /// Rounds in a row in which a plant was harvested, it raises the
+    /// points of the harvests in the next one
+    #[serde(default)]
+    streak: u32,
+    /// Plants picked by hand this round, they keep the streak going too
+    #[serde(default)]
+    picked: u32,
+    /// Most plant families on the board at once that were paid for, every
+    /// count only pays once
+    #[serde(default)]
+    families_rewarded: usize,
+    /// Rain clouds and droughts currently over the board
+    clouds: Vec<Cloud>,
+    /// Weather of the running round, applied when it ends
+    #[serde(default)]
+    weather: Weather,
+    /// Weather of the round after this one, already rolled
+    #[serde(default)]
+    forecast: Weather,
+    /// Composted cards of the player on turn, each can fertilize one plant
+    compost: u32,
+    /// Milliseconds of the run outside the pause menu and the codex, kept
+    /// as a sum so resumed runs go on from it
+    #[serde(default)]
+    played_ms: u64,
+    /// Cards composted over the whole run, spent or not
+    #[serde(default)]
+    composted: u32,
+    /// Goals of this run, rolled at the start
+    #[serde(default)]
+    objectives: Vec<Objective>,
+    #[serde(default)]
+    difficulty: Level,
+    #[serde(default)]
+    mode: GameMode,
+    /// Rule changes of this run, already applied
+    #[serde(default)]
+    mutators: Vec<Mutator>,
+    #[serde(default)]
+    harvest_mode: HarvestMode,
+    /// Rounds a ripe plant stays pickable with manual harvests
+    #[serde(default)]
+    overripe_rounds: u32,
+    /// Rounds a card keeps in hand, `None` if cards never go stale
+    #[serde(default)]
+    freshness: Option<u32>,
+    #[serde(default)]
+    stale_cards: StaleCards,
+    /// Rounds between two drafts, `None` without drafts
+    #[serde(default)]
+    draft_every: Option<u32>,
+    /// The rewards offered while `State::Drafting`
+    #[serde(default)]
+    draft: Option<Draft>,
+    /// Actions this round on top of `actions_per_round`, from a draft
+    #[serde(default)]
+    bonus_actions: u32,
+    /// Hands and scores of a hot-seat game, empty with a single player. The
+    /// seat of the player on turn is stale, their cards and points live in
+    /// `hand` and `ledger`.
+    #[serde(default)]
+    seats: Vec<Seat>,
+    /// Player on turn
+    #[serde(default)]
+    turn: usize,
+    /// With `--vs-ai`, the policy of the CPU in `AI_SEAT`. Each player only
+    /// plays on their half of the board then.
+    #[serde(default)]
+    ai: Option<PolicyName>,
+    /// What the run was started with. Saves leave it out, a loaded run
+    /// goes on with the settings of whoever loads it.
+    #[serde(skip, default = "GlobalSetting::detached")]
+    settings: Arc<GlobalSetting>,
+    /// Latest `notify` message, not yet picked up by the status bar
+    #[serde(skip)]
+    notice: Option<String>,
+    /// Tiles harvested and their points, not yet picked up by the UI
+    #[serde(skip)]
+    harvests: Vec<(usize, usize, f32)>,
+    /// Water left to spend this round
+    #[serde(default)]
+    water: u32,
+    /// Tiles watered this round, once per water spent
+    #[serde(default)]
+    watered: Vec<(usize, usize)>,
+    /// Total score at the end of every round
+    #[serde(default)]
+    round_scores: Vec<f32>,
+    /// What every round update did, in order
+    #[serde(default)]
+    round_reports: Vec<RoundReport>,
+    /// Packs the plants come from, empty for the built-in plants. Drops
+    /// refer to plants by id, so a run only resumes on the same packs.
+    #[serde(default)]
+    packs: Vec<String>,
+    /// Placements and harvests per plant name
+    #[serde(default)]
+    plant_stats: BTreeMap<String, PlantStats>,
+    /// Plants whose `unlock_at_points` the score has passed, by name
+    #[serde(default)]
+    unlocked: BTreeSet<String>,
+    /// Bests of this run, the profile keeps those of all runs
+    #[serde(default)]
+    records: Records,
+    log: Vec<String>,
+}
+
+impl Game {
+    /// A fresh run on `settings` with their seed.
+    fn empty(pack: &Pack, settings: Arc<GlobalSetting>) -> Game {
+        let seed = settings.seed;
+        Game::new(pack, settings, seed)
+    }
+
+    /// A fresh run on `settings` and the plants of `pack`, `seed` decides
+    /// everything random in it.
+    fn new(pack: &Pack, settings: Arc<GlobalSetting>, seed: u64) -> Game {
+        let set = pack.plant_set().unwrap_or_else(|err| panic!("{}", err));
+        Game::with_plants(set, settings, seed)
+    }
+
+    /// A fresh run on `settings` and a loaded plant set.
+    fn with_plants(mut set: PlantSet, settings: Arc<GlobalSetting>, seed: u64) -> Game {
+        let mutators = settings.mutators.clone();
+        mutators::adjust_plants(&mutators, &mut set.plants);
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+        let objectives = objectives::roll(&mut rng, objectives::OBJECTIVES_PER_RUN, |name| {
+            set.id(name).is_some()
+        });
+        let weather = weather::roll(&mut rng);
+        let forecast = weather::roll(&mut rng);
+        let mut hybrids = set
+            .hybrids(&objectives)
+            .unwrap_or_else(|err| panic!("{}", err));
+        mutators::adjust_hybrids(&mutators, &mut hybrids);
+        if settings.puzzle.as_ref().is_some_and(|puzzle| !puzzle.drops) {
+            hybrids.clear();
+        }
+        let difficulty = &settings.difficulty;
+        let layout = settings.board.as_ref();
+        let hand = match layout.and_then(|layout| layout.hand.as_ref()) {
+            Some(names) => names.iter().map(|name| set.card(name)).collect(),
+            None => set
+                .hand
+                .iter()
+                .map(|name| match set.id(name) {
+                    Some(id) => Card::Plant(Box::new(set.plants[id.index()].clone())),
+                    None => panic!("Expected Plant <{}> of the starting hand to exist", name),
+                })
+                .collect::<Vec<Card>>(),
+        };
+        let players = settings.players;
+        let seats = if players > 1 {
+            (0..players)
+                .map(|player| Seat {
+                    // The first player's hand is checked out into `hand`
+                    hand: if player == 0 {
+                        Vec::new()
+                    } else {
+                        hand.clone()
+                    },
+                    reserve: Vec::new(),
+                    ledger: ScoreLedger::default(),
+                    compost: 0,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut terrain = match (layout, &settings.preset) {
+            (Some(layout), _) => layout.terrain.clone(),
+            (None, Some(preset)) => preset.layout(settings.width, settings.height),
+            (None, None) => Board::filled(settings.width, settings.height, Terrain::Plain),
+        };
+        mutators::adjust_terrain(&mutators, &mut terrain, &mut rng);
+        let mut tile = Board::from_fn(settings.width, settings.height, |_, _| Tile::Empty);
+        for laid in layout.iter().flat_map(|layout| &layout.plants) {
+            let mut plant = set.plant(&laid.name).clone();
+            plant.age = laid.age;
+            plant.size = curve::size_at(
+                plant.growth_curve,
+                plant.size_per_turn,
+                laid.age,
+                plant.max_age,
+            );
+            tile.set(laid.x, laid.y, Tile::Permanent(PlantedPlant::new(plant)));
+            // Laid out on purpose, no mutator puts rocks under it
+            if let Some(&soil) = layout.and_then(|layout| layout.terrain.get(laid.x, laid.y)) {
+                terrain.set(laid.x, laid.y, soil);
+            }
+        }
+
+        let mut game = Game {
+            state: State::Choosing,
+            tile,
+            terrain,
+            hand,
+            id_to_plant: set.plants,
+            items: set.items,
+            hybrids,
+            ledger: ScoreLedger::default(),
+            round: layout.and_then(|layout| layout.round).unwrap_or(0),
+            placing: PlacingState::centered(&settings),
+            choosing: ChoosingState::default(),
+            rng,
+            seed,
+            pest_actions: 0,
+            actions_per_round: settings.actions_per_round,
+            placed: 0,
+            streak: 0,
+            picked: 0,
+            families_rewarded: 0,
+            clouds: Vec::new(),
+            weather,
+            forecast,
+            compost: 0,
+            composted: 0,
+            reserve: Vec::new(),
+            played_ms: 0,
+            objectives,
+            difficulty: difficulty.level,
+            mode: settings.mode,
+            mutators,
+            harvest_mode: settings.harvest_mode,
+            overripe_rounds: settings.overripe_rounds,
+            freshness: settings.freshness,
+            stale_cards: settings.stale_cards,
+            draft_every: settings.draft_every,
+            draft: None,
+            bonus_actions: 0,
+            seats,
+            turn: 0,
+            ai: settings.ai,
+            settings: settings.clone(),
+            notice: None,
+            harvests: Vec::new(),
+            water: WATER_PER_ROUND,
+            watered: Vec::new(),
+            round_scores: Vec::new(),
+            round_reports: Vec::new(),
+            packs: set.packs,
+            plant_stats: BTreeMap::new(),
+            unlocked: BTreeSet::new(),
+            records: Records::default(),
+            log: Vec::new(),
+        };
+        if let Some(points) = layout.and_then(|layout| layout.points) {
+            game.ledger.add(ScoreCategory::Starting, points);
+        }
+        for warning in &set.warnings {
+            game.log(warning);
+        }
+        game.keep_selection();
+        game
+    }
+
+    fn log(&mut self, msg: impl Into<String>) {
+        self.log.push(format!("[{}] {}", self.round, msg.into()));
+    }
+
+    /// Logs something the round update did and keeps it for its report.
+    fn log_event(&mut self, report: &mut RoundReport, msg: String) {
+        self.log(msg.clone());
+        report.events.push(msg);
+    }
+
+    /// Reports the outcome of a player action in the status bar, and keeps
+    /// it in the event log.
+    fn notify(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        self.log(msg.clone());
+        self.notice = Some(msg);
+    }
+
+    /// The state as one sentence for `--plain`, naming the selected card or
+    /// what is under the cursor.
+    fn describe(&self) -> String {
+        match self.state {
+            State::Choosing => {
+                let visible = self.visible_hand();
+                let slot = self
+                    .choosing
+                    .index
+                    .and_then(|i| Some((visible.iter().position(|&v| v == i)?, i)));
+                match slot {
+                    Some((slot, i)) if self.stack_size(i) > 1 => format!(
+                        "Plants list, card {} of {}: {} ×{}",
+                        slot + 1,
+                        visible.len(),
+                        self.hand[i].name(),
+                        self.stack_size(i)
+                    ),
+                    Some((slot, i)) => format!(
+                        "Plants list, card {} of {}: {}",
+                        slot + 1,
+                        visible.len(),
+                        self.hand[i].name()
+                    ),
+                    None if self.hand.is_empty() => "Plants list, the hand is empty".into(),
+                    None => format!("Plants list, {} cards", visible.len()),
+                }
+            }
+            State::Placing | State::Inspect => {
+                let (x, y) = (self.placing.x, self.placing.y);
+                format!(
+                    "Cursor moved to {}, row {} column {}, {}",
+                    tile_name(x, y),
+                    y + 1,
+                    x + 1,
+                    self.describe_tile(x, y)
+                )
+            }
+            State::NextRound => format!("Next round panel, space ends round {}", self.round + 1),
+            State::Drafting => match &self.draft {
+                Some(draft) => format!(
+                    "Draft, reward {} of {}: {}, space takes it, escape takes none",
+                    draft.selected + 1,
+                    draft.rewards.len(),
+                    draft
+                        .rewards
+                        .get(draft.selected)
+                        .map(Reward::to_string)
+                        .unwrap_or_default()
+                ),
+                None => "Draft, nothing to take".into(),
+            },
+            State::GameOver => format!(
+                "Game over after {} rounds with {} points",
+                self.round,
+                format_points(self.total_points())
+            ),
+        }
+    }
+
+    /// "tile contains Shrub age 2 of 7", and whatever else is on the tile.
+    fn describe_tile(&self, x: usize, y: usize) -> String {
+        let tile = self.tile.get(x, y);
+        let mut text = match tile.and_then(Tile::plant) {
+            Some(p) => format!("tile contains {} age {} of {}", p.name, p.age, p.max_age),
+            None => "tile is empty".to_string(),
+        };
+        if let Some(Tile::Infested(..)) = tile {
+            text.push_str(", infested by pests");
+        }
+        if let Some(left) = tile
+            .and_then(Tile::plant)
+            .and_then(|p| self.overripe_left(p))
+        {
+            text.push_str(&format!(", overripe, {} rounds left to pick it", left));
+        }
+        if let (Some(Tile::Empty), Some(Card::Plant(plant))) = (tile, &self.choosing.choice) {
+            text.push_str(&format!(
+                ", {} here would score {} points",
+                plant.name,
+                format_points(self.projected_points(x, y, plant))
+            ));
+        }
+        let terrain = self.terrain_at(x, y);
+        if terrain != Terrain::Plain {
+            text.push_str(&format!(", {} ground", terrain));
+        }
+        match weather::weather_at(&self.clouds, x, y) {
+            Some(CloudKind::Rain) => text.push_str(", under a rain cloud"),
+            Some(CloudKind::Drought) => text.push_str(", under a drought"),
+            None => {}
+        }
+        let watered = self.watered_at(x, y);
+        if watered > 0 {
+            text.push_str(&format!(", watered {} times", watered));
+        }
+        text
+    }
+
+    /// The card the info panel is about: the plant under the cursor on the
+    /// board, otherwise the selected or chosen card.
+    fn shown_card(&self) -> Option<Card> {
+        let (x, y) = (self.placing.x, self.placing.y);
+        match self.state {
+            State::Choosing | State::NextRound | State::GameOver => self.selected_card(),
+            State::Drafting => match self
+                .draft
+                .as_ref()
+                .and_then(|draft| draft.rewards.get(draft.selected))
+            {
+                Some(Reward::Card(card)) => Some(card.clone()),
+                _ => None,
+            },
+            State::Placing => match self.plant_at(x, y) {
+                Some(plant) => Some(Card::Plant(Box::new(plant.clone()))),
+                // Preview the chosen card on an empty tile
+                None => self.choosing.choice.clone(),
+            },
+            State::Inspect => self
+                .plant_at(x, y)
+                .cloned()
+                .map(|p| Card::Plant(Box::new(p))),
+        }
+    }
+
+    fn selected_card(&self) -> Option<Card> {
+        self.choosing
+            .index
+            .and_then(|idx| self.hand.get(idx))
+            .cloned()
+    }
+
+    /// The plant or item of a name, if this game knows it.
+    fn card_named(&self, name: &str) -> Option<Card> {
+        let plant = self.id_to_plant.iter().find(|p| p.name == name);
+        let item = self.items.iter().find(|item| item.name == name);
+        plant
+            .cloned()
+            .map(|p| Card::Plant(Box::new(p)))
+            .or_else(|| item.cloned().map(Card::Item))
+    }
+
+    fn card(&self, id: CardId) -> Card {
+        match id {
+            CardId::Plant(id) => Card::Plant(Box::new(self.id_to_plant[id.index()].clone())),
+            CardId::Item(id) => Card::Item(self.items[id.index()].clone()),
+        }
+    }
+
+    fn card_name(&self, id: CardId) -> &str {
+        match id {
+            CardId::Plant(id) => &self.id_to_plant[id.index()].name,
+            CardId::Item(id) => &self.items[id.index()].name,
+        }
+    }
+
+    fn add_to_hand(&mut self, cards: impl IntoIterator<Item = Card>) {
+        self.hand.extend(cards);
+        self.keep_selection();
+    }
+
+    /// Points the selection at a listed card again after the hand changed,
+    /// or clears it when nothing is listed.
+    fn keep_selection(&mut self) {
+        self.choosing.clamp(self.hand.len());
+        self.keep_selection_visible();
+    }
+
+    /// Rounds `card` still keeps in hand, `None` if cards never go stale.
+    fn freshness_left(&self, card: &Card) -> Option<u32> {
+        let limit = self.freshness?;
+        Some(card.freshness().unwrap_or(limit))
+    }
+
+    /// With `--freshness`, every card held through the round loses a round
+    /// of freshness, and the ones out of it go stale.
+    fn age_hands(&mut self, report: &mut RoundReport) {
+        let Some(limit) = self.freshness else {
+            return;
+        };
+        let mut stale = Vec::new();
+        for idx in (0..self.hand.len()).rev() {
+            if wilt(&mut self.hand[idx], limit) {
+                stale.extend(self.remove_from_hand(idx).map(|card| (self.turn, card)));
+            }
+        }
+        stale.reverse();
+        for (owner, seat) in self.seats.iter_mut().enumerate() {
+            // The seat of the player on turn is stale, their cards are in `hand`
+            if owner == self.turn {
+                continue;
+            }
+            let (gone, kept) = seat
+                .hand
+                .drain(..)
+                .map(|mut card| (wilt(&mut card, limit), card))
+                .partition::<Vec<(bool, Card)>, _>(|&(gone, _)| gone);
+            seat.hand = kept.into_iter().map(|(_, card)| card).collect();
+            stale.extend(gone.into_iter().map(|(_, card)| (owner, card)));
+        }
+        for (owner, card) in stale {
+            let whose = match self.players() {
+                1 => String::new(),
+                _ => format!("Player {}'s ", owner + 1),
+            };
+            let msg = match self.stale_cards {
+                StaleCards::Compost => {
+                    match owner == self.turn {
+                        true => self.compost += 1,
+                        false => self.seats[owner].compost += 1,
+                    }
+                    self.composted += 1;
+                    format!("{}{} went stale and turned to compost", whose, card.name())
+                }
+                StaleCards::Discard => format!("{}{} went stale and was lost", whose, card.name()),
+            };
+            self.log_event(report, msg);
+        }
+    }
+
+    /// Takes a card out of the hand, moving the selection to the card listed
+    /// before it.
+    fn remove_from_hand(&mut self, idx: usize) -> Option<Card> {
+        if idx >= self.hand.len() {
+            return None;
+        }
+        let before = self.visible_hand();
+        let pos = before.iter().position(|&i| i == idx);
+        // A stack keeps its row while copies are left
+        let copy = (self.choosing.grouped && self.choosing.index == Some(idx))
+            .then(|| {
+                (0..self.hand.len())
+                    .find(|&i| i != idx && self.hand[i].stacks_with(&self.hand[idx]))
+            })
+            .flatten();
+        let card = self.hand.remove(idx);
+        if let Some(copy) = copy {
+            self.choosing.index = Some(if copy > idx { copy - 1 } else { copy });
+        } else if self.choosing.index == Some(idx) {
+            // Hand positions after the removed card moved down by one
+            self.choosing.index = pos
+                .and_then(|pos| before.get(pos.checked_sub(1)?))
+                .map(|&i| if i > idx { i - 1 } else { i });
+        } else if let Some(selected) = self.choosing.index.filter(|&i| i > idx) {
+            self.choosing.index = Some(selected - 1);
+        }
+        self.keep_selection();
+        Some(card)
+    }
+
+    /// Hand positions in the order the Plants list shows them, cards hidden
+    /// by the filter left out. While cards are grouped, each stack is only
+    /// listed by its first card.
+    fn visible_hand(&self) -> Vec<usize> {
+        let filter = self.choosing.filter.to_lowercase();
+        let mut visible = (0..self.hand.len())
+            .filter(|&i| self.hand[i].name().to_lowercase().contains(&filter))
+            .filter(|&i| self.stack_of(i) == i)
+            .collect::<Vec<usize>>();
+        let (x, y) = (self.placing.x, self.placing.y);
+        match self.choosing.sort {
+            HandSort::Dealt => {}
+            HandSort::Name => {
+                visible.sort_by(|&a, &b| self.hand[a].name().cmp(self.hand[b].name()))
+            }
+            // Items score nothing themselves and go last
+            HandSort::Points => visible.sort_by(|&a, &b| {
+                let points = |i: usize| match &self.hand[i] {
+                    Card::Plant(plant) => self.projected_points(x, y, plant),
+                    Card::Item(_) => f32::NEG_INFINITY,
+                };
+                points(b).total_cmp(&points(a))
+            }),
+            HandSort::MaxAge => visible.sort_by_key(|&i| match &self.hand[i] {
+                Card::Plant(plant) => plant.max_age,
+                Card::Item(_) => u32::MAX,
+            }),
+        }
+        visible
+    }
+
+    /// Moves the selection to the row of its stack, or to the first listed
+    /// card if the filter hides it.
+    fn keep_selection_visible(&mut self) {
+        let visible = self.visible_hand();
+        self.choosing.index = match self.choosing.index.map(|i| self.stack_of(i)) {
+            Some(i) if visible.contains(&i) => Some(i),
+            _ => visible.first().copied(),
+        };
+    }
+
+    /// The first card of the hand that stacks with the one at `idx`, the
+    /// one their row stands for while cards are grouped.
+    fn stack_of(&self, idx: usize) -> usize {
+        match self.choosing.grouped {
+            true => (0..idx)
+                .find(|&i| self.hand[i].stacks_with(&self.hand[idx]))
+                .unwrap_or(idx),
+            false => idx,
+        }
+    }
+
+    /// Cards in the row of `idx`, 1 unless cards are grouped.
+    fn stack_size(&self, idx: usize) -> usize {
+        match self.choosing.grouped {
+            true => self
+                .hand
+                .iter()
+                .filter(|card| card.stacks_with(&self.hand[idx]))
+                .count(),
+            false => 1,
+        }
+    }
+
+    fn toggle_groups(&mut self) {
+        self.choosing.grouped = !self.choosing.grouped;
+        self.keep_selection_visible();
+        self.notify(match self.choosing.grouped {
+            true => "Identical cards share a row",
+            false => "Every card has its own row",
+        });
+    }
+
+    fn cycle_sort(&mut self) {
+        self.choosing.sort = self.choosing.sort.next();
+        self.notify(format!("Plants sorted by {}", self.choosing.sort));
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.choosing.filter = filter;
+        self.keep_selection_visible();
+    }
+
+    fn on_space(&mut self) {
+        if self.out_of_moves() {
+            self.next_round();
+            return;
+        }
+        if self.hand.is_empty() && matches!(self.state, State::Choosing | State::Placing) {
+            self.notify("Hand is empty — press Tab to end the round");
+            return;
+        }
+
+        match self.state {
+            State::Choosing => {
+                self.choosing.choice = self.selected_card();
+                self.state = State::Placing;
+            }
+            State::Placing => {
+                let (x, y) = (self.placing.x, self.placing.y);
+                if self.hand.len() <= self.pest_actions {
+                    self.notify("Busy clearing pests, no placements left this round");
+                } else if self.actions_left() == Some(0) {
+                    self.notify("No actions left this round");
+                } else if let Some(Card::Item(item)) = self.choosing.choice.clone() {
+                    if self.can_use_item(x, y, &item) {
+                        self.choosing.choice = None;
+                        self.use_item(x, y, &item);
+                        self.card_played();
+                    } else if self.may_tend(
+                        x,
+                        y,
+                        &format!("{} only works on your own plants", item.name),
+                    ) {
+                        self.notify(format!("{} has to be used on a plant", item.name));
+                    }
+                } else if self.can_overplant(x, y) {
+                    if let Some(Card::Plant(plant)) = self.choosing.choice.take() {
+                        self.overplant(x, y, &plant);
+                        self.card_played();
+                    }
+                } else if let Err(err) = self.can_place_plant(x, y) {
+                    self.notify(err.to_string());
+                } else if let Some(Card::Plant(plant)) = self.choosing.choice.take() {
+                    self.place_plant(x, y, &plant);
+                    self.notify(format!("Placed {} at {}", plant.name, tile_name(x, y)));
+                    self.card_played();
+                } else {
+                    // Nothing was chosen, the board is only looked at
+                    self.state = State::Inspect;
+                }
+            }
+            State::NextRound => self.next_round(),
+            State::Inspect | State::Drafting | State::GameOver => {}
+        }
+    }
+
+    /// Places the selected plant on the free tile closest to the board
+    /// center, going through the same checks as placing it by hand.
+    fn on_quick_place(&mut self) {
+        if self.hand.is_empty() {
+            self.on_space();
+            return;
+        }
+        let Some(Card::Plant(_)) = self.selected_card() else {
+            self.notify("Only plants can be quick-placed, items need a target");
+            return;
+        };
+        let tile = self
+            .tile
+            .spiral(self.width() / 2, self.height() / 2)
+            .into_iter()
+            .find(|&(x, y)| self.can_place_plant(x, y).is_ok());
+        let Some((x, y)) = tile else {
+            self.notify("No free tile to plant on");
+            return;
+        };
+        let cursor = self.placing;
+        self.placing = PlacingState { x, y };
+        self.on_space();
+        self.on_space();
+        // Refused, e.g. without actions left
+        if self.state == State::Placing {
+            self.choosing.choice = None;
+            self.state = State::Choosing;
+            self.placing = cursor;
+        }
+    }
+
+    /// Takes the selected card out of the hand once it was placed or used,
+    /// it costs one action.
+    fn card_played(&mut self) {
+        self.placed += 1;
+        self.check_objectives();
+        if let Some(idx) = self.choosing.index {
+            self.remove_from_hand(idx);
+            self.state = State::Choosing;
+        }
+        self.end_turn_if_spent();
+    }
+
+    /// Jumps straight to a hand slot. While placing, the chosen card is
+    /// swapped for the one in that slot. Returns false for empty slots.
+    fn quick_select(&mut self, slot: usize) -> bool {
+        let Some(&idx) = self.visible_hand().get(slot) else {
+            return false;
+        };
+        self.choosing.index = Some(idx);
+        if self.state == State::Placing {
+            self.choosing.choice = Some(self.hand[idx].clone());
+        }
+        true
+    }
+
+    /// Moves to the next panel in on-screen order: the board on the left,
+    /// then the card chooser and the round panel on the right.
+    fn on_tab(&mut self) {
+        match self.state {
+            State::Placing | State::Inspect => self.leave_board(State::Choosing),
+            State::Choosing => self.state = State::NextRound,
+            State::NextRound => self.enter_board(),
+            State::Drafting | State::GameOver => {}
+        }
+    }
+
+    /// Moves to the previous panel in on-screen order.
+    fn on_back_tab(&mut self) {
+        match self.state {
+            State::Placing | State::Inspect => self.leave_board(State::NextRound),
+            State::Choosing => self.enter_board(),
+            State::NextRound => self.state = State::Choosing,
+            State::Drafting | State::GameOver => {}
+        }
+    }
+
+    /// Places the chosen card, or just looks around without one.
+    fn enter_board(&mut self) {
+        self.state = match self.choosing.choice {
+            Some(_) => State::Placing,
+            None => State::Inspect,
+        };
+    }
+
+    /// Drops the chosen card, the chooser may pick another one before the
+    /// board is entered again.
+    fn leave_board(&mut self, state: State) {
+        self.choosing.choice = None;
+        self.state = state;
+    }
+
+    /// Enters Inspect mode, or leaves it for the card chooser.
+    fn on_inspect(&mut self) {
+        match self.state {
+            State::Inspect => self.state = State::Choosing,
+            State::Drafting | State::GameOver => {}
+            _ => self.state = State::Inspect,
+        }
+    }
+
+    /// Ends the turn. The round only moves on once every player had theirs.
+    fn next_round(&mut self) {
+        if matches!(self.state, State::Drafting | State::GameOver) {
+            return;
+        }
+        if self.turn + 1 < self.players() {
+            self.switch_to(self.turn + 1);
+        } else {
+            let report = self.update_game();
+            self.round_reports.push(report);
+            let drafting = self.state == State::Drafting;
+            if self.players() > 1 && self.state != State::GameOver {
+                self.switch_to(0);
+            } else if self.state != State::GameOver {
+                self.refill_hand();
+            }
+            // In a hot-seat game the first player drafts
+            if drafting {
+                self.state = State::Drafting;
+            }
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.settings.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.settings.height
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Rounds played so far.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn players(&self) -> usize {
+        self.seats.len().max(1)
+    }
+
+    /// Hands the board to another player in a hot-seat game.
+    fn switch_to(&mut self, player: usize) {
+        let seat = &mut self.seats[self.turn];
+        std::mem::swap(&mut seat.hand, &mut self.hand);
+        std::mem::swap(&mut seat.reserve, &mut self.reserve);
+        std::mem::swap(&mut seat.compost, &mut self.compost);
+        seat.ledger = self.ledger.clone();
+        let seat = &mut self.seats[player];
+        std::mem::swap(&mut seat.hand, &mut self.hand);
+        std::mem::swap(&mut seat.reserve, &mut self.reserve);
+        std::mem::swap(&mut seat.compost, &mut self.compost);
+        self.ledger = seat.ledger.clone();
+        self.turn = player;
+        self.refill_hand();
+
+        self.placed = 0;
+        self.pest_actions = 0;
+        self.bonus_actions = 0;
+        self.choosing = ChoosingState {
+            sort: self.choosing.sort,
+            ..ChoosingState::default()
+        };
+        self.keep_selection();
+        self.state = State::Choosing;
+        let msg = match (self.ai, player) {
+            (Some(_), AI_SEAT) => String::from("The CPU's turn"),
+            (Some(_), _) => String::from("Your turn"),
+            (None, _) => format!("Player {}'s turn", player + 1),
+        };
+        self.log(msg);
+    }
+
+    /// Whether the player on turn may play on column `x`. Against the CPU
+    /// the board is split down the middle, the middle column of an odd
+    /// board belongs to neither.
+    fn on_own_side(&self, x: usize) -> bool {
+        let half = self.width() / 2;
+        match (self.ai, self.turn) {
+            (None, _) => true,
+            (Some(_), AI_SEAT) => x >= self.width() - half,
+            (Some(_), _) => x < half,
+        }
+    }
+
+    /// Points of all players together.
+    pub fn total_points(&self) -> f32 {
+        (0..self.players())
+            .map(|player| self.points_of(player))
+            .sum()
+    }
+
+    fn points_of(&self, player: usize) -> f32 {
+        self.ledger_of(player).total()
+    }
+
+    /// Score of the player on turn.
+    fn points(&self) -> f32 {
+        self.ledger.total()
+    }
+
+    fn ledger_of(&self, player: usize) -> &ScoreLedger {
+        match self.seats.get(player) {
+            Some(seat) if player != self.turn => &seat.ledger,
+            _ => &self.ledger,
+        }
+    }
+
+    /// The ledgers of all players together.
+    fn total_ledger(&self) -> ScoreLedger {
+        (0..self.players()).fold(ScoreLedger::default(), |total, player| {
+            total.merged(self.ledger_of(player))
+        })
+    }
+
+    /// Adds points to whoever owns the plant they came from.
+    fn credit(&mut self, owner: usize, category: ScoreCategory, points: f32) {
+        match self.seats.get_mut(owner) {
+            Some(seat) if owner != self.turn => seat.ledger.add(category, points),
+            _ => self.ledger.add(category, points),
+        }
+        self.check_unlocks();
+    }
+
+    /// Credits a harvest of `base` points, the streak multiplier on top.
+    /// Returns what the harvest scored in all.
+    fn credit_harvest(&mut self, owner: usize, base: f32) -> f32 {
+        let points = base * self.multiplier();
+        self.credit(owner, ScoreCategory::Harvest, base);
+        self.credit(owner, ScoreCategory::Streak, points - base);
+        points
+    }
+
+    /// Announces every plant whose threshold the total score just reached.
+    /// From then on it is part of the drops.
+    fn check_unlocks(&mut self) {
+        let total = self.total_points();
+        let reached = self
+            .id_to_plant
+            .iter()
+            .filter(|p| p.unlock_at_points > 0.0 && total >= p.unlock_at_points)
+            .filter(|p| !self.unlocked.contains(p.name.as_ref()))
+            .map(|p| (p.name.to_string(), p.unlock_at_points))
+            .collect::<Vec<_>>();
+        for (name, points) in reached {
+            self.notify(format!(
+                "{} unlocked at {} points",
+                name,
+                format_points(points)
+            ));
+            self.unlocked.insert(name);
+        }
+    }
+
+    /// Whether a card can come up in drops yet. Items are never locked.
+    fn is_unlocked(&self, id: CardId) -> bool {
+        match id {
+            CardId::Plant(id) => {
+                let plant = &self.id_to_plant[id.index()];
+                plant.unlock_at_points <= 0.0 || self.unlocked.contains(plant.name.as_ref())
+            }
+            CardId::Item(_) => true,
+        }
+    }
+
+    /// Which drops of `plant` on (x, y) can happen. Those naming a locked
+    /// plant can't, neither can those whose neighbor is missing. Off the
+    /// board, `at` is `None` and no neighbor is ever there.
+    fn open_drops(&self, plant: &Plant, at: Option<(usize, usize)>) -> Vec<bool> {
+        plant
+            .drops
+            .iter()
+            .map(|drop| {
+                drop.ids.iter().all(|&id| self.is_unlocked(id)) && self.neighbor_met(drop, at)
+            })
+            .collect()
+    }
+
+    /// Whether the neighbor a drop requires, if any, is next to (x, y).
+    fn neighbor_met(&self, drop: &Drop, at: Option<(usize, usize)>) -> bool {
+        let Some(name) = &drop.requires_adjacent else {
+            return true;
+        };
+        at.is_some_and(|(x, y)| {
+            self.tile
+                .neighbors(x, y)
+                .any(|(nx, ny)| self.plant_at(nx, ny).is_some_and(|p| p.name == *name))
+        })
+    }
+
+    /// Gives drops to the hand of whoever owns the plant they came from.
+    /// What doesn't fit under the hand limit goes to their reserve. Returns
+    /// the names of the cards.
+    fn give(&mut self, owner: usize, cards: impl IntoIterator<Item = Card>) -> Vec<String> {
+        let held = match self.seats.get(owner) {
+            Some(seat) if owner != self.turn => seat.hand.len(),
+            _ => self.hand.len(),
+        };
+        let mut cards = cards.into_iter().collect::<Vec<Card>>();
+        let names = cards.iter().map(|card| card.name().to_string()).collect();
+        if let Some(limit) = mutators::hand_limit(&self.mutators) {
+            let overflow = cards.split_off(cards.len().min(limit.saturating_sub(held)));
+            if !overflow.is_empty() {
+                let names = overflow.iter().map(Card::name).collect::<Vec<&str>>();
+                self.log(format!(
+                    "Hand is full, {} went to the reserve",
+                    names.join(", ")
+                ));
+                self.put_in_reserve(owner, overflow);
+            }
+        }
+        match self.seats.get_mut(owner) {
+            Some(seat) if owner != self.turn => seat.hand.extend(cards),
+            _ => self.add_to_hand(cards),
+        }
+        names
+    }
+
+    /// Adds cards to the back of a player's reserve. Past `RESERVE_LIMIT`
+    /// the oldest ones are lost.
+    fn put_in_reserve(&mut self, owner: usize, cards: Vec<Card>) {
+        let reserve = match self.seats.get_mut(owner) {
+            Some(seat) if owner != self.turn => &mut seat.reserve,
+            _ => &mut self.reserve,
+        };
+        reserve.extend(cards);
+        let over = reserve.len().saturating_sub(RESERVE_LIMIT);
+        let lost = reserve.drain(..over).collect::<Vec<Card>>();
+        if !lost.is_empty() {
+            let names = lost.iter().map(Card::name).collect::<Vec<&str>>();
+            self.log(format!("The reserve is full, lost {}", names.join(", ")));
+        }
+    }
+
+    /// Fills the hand up to its limit from the front of the reserve.
+    fn refill_hand(&mut self) {
+        let Some(limit) = mutators::hand_limit(&self.mutators) else {
+            return;
+        };
+        let room = limit
+            .saturating_sub(self.hand.len())
+            .min(self.reserve.len());
+        if room == 0 {
+            return;
+        }
+        let cards = self.reserve.drain(..room).collect::<Vec<Card>>();
+        let names = cards.iter().map(Card::name).collect::<Vec<&str>>();
+        self.log(format!("Drew {} from the reserve", names.join(", ")));
+        self.add_to_hand(cards);
+    }
+
+    fn hand_full(&self) -> bool {
+        mutators::hand_limit(&self.mutators).is_some_and(|limit| self.hand.len() >= limit)
+    }
+
+    /// Whether a card in hand could still go on the board this turn.
+    fn can_still_place(&self) -> bool {
+        self.hand.len() > self.pest_actions
+            && self.actions_left() != Some(0)
+            && self.hand.iter().any(|card| {
+                self.tile.iter_coords().any(|(x, y)| match card {
+                    Card::Plant(_) => self.can_place_plant(x, y).is_ok(),
+                    Card::Item(item) => self.can_use_item(x, y, item),
+                })
+            })
+    }
+
+    /// Whether anything is left this turn that takes a card or an action: a
+    /// card with a tile to go on, a pest to clear or a ripe plant to pick.
+    /// Watering and compost don't count, they only help plants already out.
+    fn has_legal_move(&self) -> bool {
+        let pests = self.actions_left() != Some(0)
+            && self
+                .tile
+                .iter()
+                .any(|tile| matches!(tile, Tile::Infested(..)));
+        let picks = self.tile.iter_coords().any(|(x, y)| self.can_pick(x, y));
+        self.can_still_place() || pests || picks
+    }
+
+    /// Choosing or placing with nothing left to do, space ends the round.
+    fn out_of_moves(&self) -> bool {
+        matches!(self.state, State::Choosing | State::Placing) && !self.has_legal_move()
+    }
+
+    /// Whether the plant is harvested when the round ends. Without auto
+    /// harvests only plants about to get ripe count.
+    fn matures_next_round(&self, p: &Plant) -> bool {
+        p.age + 1 >= p.max_age && self.overripe_left(p).is_none()
+    }
+
+    /// Plants harvested when the round ends and the points they bring, if
+    /// no pest gets to them first.
+    fn maturing_next_round(&self) -> (usize, f32) {
+        let mut count = 0;
+        let mut points = 0.0;
+        for (x, y) in self.tile.iter_coords() {
+            let Some(Tile::Permanent(p)) = self.tile.get(x, y) else {
+                continue;
+            };
+            if !self.matures_next_round(p) {
+                continue;
+            }
+            let stats = p.stats();
+            let mut size = p.size;
+            if self.weather != Weather::Drought {
+                let clouds = weather::weather_at(&self.clouds, x, y);
+                size += growth(&stats, clouds, self.terrain_at(x, y));
+            }
+            size = size.saturating_sub(stats.damage);
+            if self.weather == Weather::Rain {
+                size += 1;
+            }
+            size += self.watered.iter().filter(|&&tile| tile == (x, y)).count() as u32;
+            count += 1;
+            points += size as f32 * stats.points_per_size;
+        }
+        (count, points)
+    }
+
+    /// Plants per species, what matures next round and the free tiles, in
+    /// a single pass over the board.
+    fn board_summary(&self) -> BoardSummary {
+        let mut summary = BoardSummary::default();
+        for (x, y) in self.tile.iter_coords() {
+            let tile = self.tile.get(x, y).unwrap_or(&Tile::Empty);
+            let Some(plant) = tile.plant() else {
+                if self.terrain_at(x, y).can_plant() {
+                    summary.free += 1;
+                }
+                continue;
+            };
+            if matches!(tile, Tile::Permanent(_)) && self.matures_next_round(plant) {
+                summary.maturing += 1;
+            }
+            match summary.species.iter_mut().find(|s| s.name == plant.name) {
+                Some(species) => species.count += 1,
+                None => summary.species.push(SpeciesCount {
+                    name: plant.name.to_string(),
+                    short_display: plant.short_display,
+                    rarity: plant.rarity,
+                    color: plant.color(),
+                    count: 1,
+                }),
+            }
+        }
+        summary
+            .species
+            .sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        summary
+    }
+
+    /// Overripe plants that wither if they aren't picked this round.
+    fn withering_next_round(&self) -> usize {
+        self.tile
+            .iter()
+            .filter_map(|tile| match tile {
+                Tile::Permanent(p) => self.overripe_left(p),
+                _ => None,
+            })
+            .filter(|&left| left <= 1)
+            .count()
+    }
+
+    fn hands_empty(&self) -> bool {
+        self.hand.is_empty()
+            && self.reserve.is_empty()
+            && self
+                .seats
+                .iter()
+                .all(|seat| seat.hand.is_empty() && seat.reserve.is_empty())
+    }
+
+    fn place_plant(&mut self, x: usize, y: usize, plant: &Plant) {
+        let mut plant = plant.clone();
+        plant.owner = self.turn;
+        self.plant_stats
+            .entry(plant.name.to_string())
+            .or_default()
+            .placed += 1;
+        self.tile.set(x, y, Tile::New(PlantedPlant::new(plant)));
+        self.note_board();
+    }
+
+    /// Whether a plant may go on (x, y), or why not.
+    fn can_place_plant(&self, x: usize, y: usize) -> Result<(), PlacementError> {
+        let terrain = self.terrain_at(x, y);
+        match self.tile.get(x, y) {
+            _ if !self.on_own_side(x) => Err(PlacementError::OtherSide),
+            _ if !terrain.can_plant() => Err(PlacementError::Terrain(terrain)),
+            Some(Tile::Empty) => Ok(()),
+            _ => Err(PlacementError::Occupied),
+        }
+    }
+
+    /// The plant closest to (x, y) along rows and columns, and how many
+    /// steps away it is.
+    fn nearest_plant(&self, x: usize, y: usize) -> Option<(&Plant, usize, (usize, usize))> {
+        self.tile
+            .iter_coords()
+            .filter(|&tile| tile != (x, y))
+            .filter_map(|(px, py)| {
+                let p = self.plant_at(px, py)?;
+                Some((p, px.abs_diff(x) + py.abs_diff(y), (px, py)))
+            })
+            .min_by_key(|&(_, steps, _)| steps)
+    }
+
+    /// What the neighbors of (x, y) would do for `plant` growing there:
+    /// drops that need them and hybrids they cross into.
+    fn neighbor_bonuses(&self, x: usize, y: usize, plant: &Plant) -> Vec<String> {
+        let mut bonuses = Vec::new();
+        for (nx, ny) in self.tile.neighbors(x, y) {
+            let Some(neighbor) = self.plant_at(nx, ny) else {
+                continue;
+            };
+            let at = format!("{} at {}", neighbor.name, tile_name(nx, ny));
+            for drop in plant.drops.iter() {
+                let unlocked = drop.ids.iter().all(|&id| self.is_unlocked(id));
+                if unlocked && drop.requires_adjacent.as_deref() == Some(&*neighbor.name) {
+                    bonuses.push(format!("{} may drop {}", at, drop.plants.join(" + ")));
+                }
+            }
+            for hybrid in &self.hybrids {
+                if hybrid.partner(plant.id) == Some(neighbor.id)
+                    && self.is_unlocked(CardId::Plant(hybrid.child))
+                {
+                    let child = &self.id_to_plant[hybrid.child.index()].name;
+                    bonuses.push(format!("{} may cross into {}", at, child));
+                }
+            }
+        }
+        bonuses
+    }
+
+    /// Items only work on the player's own plants, a harvest needs one that
+    /// is healthy.
+    fn can_use_item(&self, x: usize, y: usize, item: &Item) -> bool {
+        if !self.on_own_side(x) || self.foreign_plant(x, y) {
+            return false;
+        }
+        match item.effect {
+            Effect::Grow { .. } | Effect::Status(_) => self.plant_at(x, y).is_some(),
+            Effect::Harvest { .. } => {
+                matches!(self.tile.get(x, y), Some(Tile::New(_) | Tile::Permanent(_)))
+            }
+        }
+    }
+
+    fn use_item(&mut self, x: usize, y: usize, item: &Item) {
+        match item.effect {
+            Effect::Grow { size } => {
+                if let Some(Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _)) =
+                    self.tile.get_mut(x, y)
+                {
+                    p.size += size;
+                    let msg = format!("Used {} on {} at {}", item.name, p.name, tile_name(x, y));
+                    self.notify(msg);
+                    self.note_board();
+                }
+            }
+            Effect::Harvest { share } => {
+                if let Some(Tile::New(p) | Tile::Permanent(p)) = self.tile.set(x, y, Tile::Empty) {
+                    let points = p.size as f32 * p.stats().points_per_size * share;
+                    self.credit(p.owner, ScoreCategory::Harvest, points);
+                    self.record_harvest(&p.name, points);
+                    self.harvests.push((x, y, points));
+                    self.watered.retain(|&w| w != (x, y));
+                    self.notify(format!(
+                        "{} harvested {} at {} for {} points",
+                        item.name,
+                        p.name,
+                        tile_name(x, y),
+                        format_points(points)
+                    ));
+                    self.on_harvest(&p.name);
+                }
+            }
+            Effect::Status(effect) => {
+                if let Some(Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _)) =
+                    self.tile.get_mut(x, y)
+                {
+                    effects::add(&mut p.effects, effect);
+                    let msg = format!("Used {} on {} at {}", item.name, p.name, tile_name(x, y));
+                    self.notify(msg);
+                }
+            }
+        }
+    }
+
+    fn terrain_at(&self, x: usize, y: usize) -> Terrain {
+        self.terrain.get(x, y).copied().unwrap_or_default()
+    }
+
+    fn plant_at(&self, x: usize, y: usize) -> Option<&Plant> {
+        self.tile.get(x, y).and_then(Tile::plant)
+    }
+
+    /// Whether the plant at (x, y) was placed by another player than the
+    /// one on turn. Hot-seat players only tend to their own plants.
+    fn foreign_plant(&self, x: usize, y: usize) -> bool {
+        self.plant_at(x, y).is_some_and(|p| p.owner != self.turn)
+    }
+
+    /// Whether the player on turn may tend the tile at (x, y): it has to
+    /// be on their half of the board and not hold another player's plant.
+    /// Says why not, with `refusal` for a foreign plant.
+    fn may_tend(&mut self, x: usize, y: usize, refusal: &str) -> bool {
+        if !self.on_own_side(x) {
+            self.notify(PlacementError::OtherSide.to_string());
+            return false;
+        }
+        if self.foreign_plant(x, y) {
+            self.notify(refusal);
+            return false;
+        }
+        true
+    }
+
+    /// Puts the plant under the cursor back into the hand and selects it.
+    fn on_delete(&mut self) {
+        let (x, y) = (self.placing.x, self.placing.y);
+        if self.foreign_plant(x, y) {
+            self.notify("Only your own plants can be taken back");
+            return;
+        }
+        match self.unplace(x, y) {
+            Some(plant) => {
+                let msg = format!(
+                    "Took {} at {} back into the hand",
+                    plant.name,
+                    tile_name(x, y)
+                );
+                self.add_to_hand([Card::Plant(Box::new(plant))]);
+                self.choosing.index = Some(self.hand.len() - 1);
+                self.keep_selection_visible();
+                self.notify(msg);
+            }
+            None => self.notify("Only plants placed this round can be taken back"),
+        }
+    }
+
+    /// Takes the plant the player on turn placed this round at (x, y) off
+    /// the board, giving back its placement and the water spent on it.
+    /// Every other tile, any older plant and those of other players are
+    /// left as they are.
+    fn unplace(&mut self, x: usize, y: usize) -> Option<Plant> {
+        if !matches!(self.tile.get(x, y), Some(Tile::New(p)) if p.owner == self.turn) {
+            return None;
+        }
+        let Some(Tile::New(planted)) = self.tile.set(x, y, Tile::Empty) else {
+            return None;
+        };
+        self.placed = self.placed.saturating_sub(1);
+        let before = self.watered.len();
+        self.watered.retain(|&w| w != (x, y));
+        self.water += (before - self.watered.len()) as u32;
+        Some(planted.plant)
+    }
+
+    /// Whether the chosen plant may replace the one at (x, y). Infested
+    /// plants have to be cleared first, and players only replace their own
+    /// plants: another player's can't go back into their hand or be dug up.
+    fn can_overplant(&self, x: usize, y: usize) -> bool {
+        self.settings.overplant
+            && self.on_own_side(x)
+            && matches!(self.choosing.choice, Some(Card::Plant(_)))
+            && match self.tile.get(x, y) {
+                Some(Tile::New(p) | Tile::Permanent(p)) => p.owner == self.turn,
+                _ => false,
+            }
+    }
+
+    /// The plant the chosen one would dig up at (x, y) for
+    /// `OVERPLANT_PENALTY`, `None` where nothing would be lost.
+    fn overplant_victim(&self, x: usize, y: usize) -> Option<&Plant> {
+        match self.tile.get(x, y) {
+            Some(Tile::Permanent(p)) if self.can_overplant(x, y) => Some(p),
+            _ => None,
+        }
+    }
+
+    /// Puts `plant` where another one is. One placed this round goes back
+    /// to the hand like with `unplace`, an older one is dug up for no points
+    /// and costs `OVERPLANT_PENALTY`.
+    fn overplant(&mut self, x: usize, y: usize, plant: &Plant) {
+        let tile = tile_name(x, y);
+        if let Some(old) = self.unplace(x, y) {
+            self.log(format!("Took {} at {} back into the hand", old.name, tile));
+            self.add_to_hand([Card::Plant(Box::new(old))]);
+        } else if let Some(Tile::Permanent(old)) = self.tile.set(x, y, Tile::Empty) {
+            self.ledger
+                .add(ScoreCategory::Penalties, -OVERPLANT_PENALTY);
+            self.watered.retain(|&w| w != (x, y));
+            self.plant_stats
+                .entry(old.name.to_string())
+                .or_default()
+                .overplanted += 1;
+            self.log(format!(
+                "Dug up {} at {} (-{})",
+                old.name,
+                tile,
+                format_points(OVERPLANT_PENALTY)
+            ));
+        }
+        self.place_plant(x, y, plant);
+        self.notify(format!("Placed {} at {}", plant.name, tile));
+    }
+
+    /// Turns the selected hand card into compost.
+    fn on_compost(&mut self) {
+        if let Some(plant) = self
+            .choosing
+            .index
+            .and_then(|idx| self.remove_from_hand(idx))
+        {
+            self.compost += 1;
+            self.composted += 1;
+            self.log(format!("Composted {}", plant.name()));
+        }
+    }
+
+    /// Spends compost on the plant under the cursor, it grows one more size
+    /// per turn for the rest of its life.
+    fn on_fertilize(&mut self) {
+        if self.compost == 0 {
+            self.notify("No compost left, compost a card with c first");
+            return;
+        }
+        let (x, y) = (self.placing.x, self.placing.y);
+        if !self.may_tend(x, y, "Only your own plants can be fertilized") {
+            return;
+        }
+        if let Some(Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _)) =
+            self.tile.get_mut(x, y)
+        {
+            p.size_per_turn += 1;
+            let msg = format!("Fertilized {} at {}", p.name, tile_name(x, y));
+            self.compost -= 1;
+            self.log(msg);
+        }
+    }
+
+    /// Spends one water on the plant under the cursor, it grows one extra
+    /// size when the round ends.
+    fn on_water(&mut self) {
+        if self.water == 0 {
+            self.notify("No water left this round");
+            return;
+        }
+        let (x, y) = (self.placing.x, self.placing.y);
+        if !self.may_tend(x, y, "Only your own plants can be watered") {
+            return;
+        }
+        if let Some(p) = self.plant_at(x, y) {
+            let msg = format!("Watered {} at {}", p.name, tile_name(x, y));
+            self.water -= 1;
+            self.watered.push((x, y));
+            self.log(msg);
+        }
+    }
+
+    /// How often the tile was watered this round.
+    fn watered_at(&self, x: usize, y: usize) -> u32 {
+        self.watered.iter().filter(|&&w| w == (x, y)).count() as u32
+    }
+
+    /// Clears the pest on the tile under the cursor. The plant survives, but
+    /// the effort costs one card placement this round.
+    fn on_clear_pest(&mut self) {
+        let (x, y) = (self.placing.x, self.placing.y);
+        if !self.may_tend(x, y, "Only your own plants can be cleared of pests") {
+            return;
+        }
+        if let Some(Tile::Infested(..)) = self.tile.get(x, y) {
+            if let Some(Tile::Infested(plant, _)) = self.tile.set(x, y, Tile::Empty) {
+                self.log(format!(
+                    "Cleared pest from {} at {}",
+                    plant.name,
+                    tile_name(x, y)
+                ));
+                self.tile.set(x, y, Tile::Permanent(plant));
+                self.pest_actions += 1;
+                self.end_turn_if_spent();
+            }
+        }
+    }
+
+    /// Actions left this round, `None` without a budget. Clearing a pest
+    /// costs an action just like placing a card.
+    pub fn actions_left(&self) -> Option<u32> {
+        if self.actions_per_round == 0 {
+            return None;
+        }
+        let spent = self.placed + self.pest_actions as u32;
+        Some((self.actions_per_round + self.bonus_actions).saturating_sub(spent))
+    }
+
+    /// Moves on to the round end once the action budget is used up.
+    fn end_turn_if_spent(&mut self) {
+        if self.actions_left() == Some(0) {
+            self.state = State::NextRound;
+        }
+    }
+
+    /// Rolls for a pest on a random permanent plant. Freshly placed plants
+    /// are never infested.
+    fn spawn_pest(&mut self, report: &mut RoundReport) {
+        if self.rng.gen::<f32>() >= self.settings.pest_chance {
+            return;
+        }
+
+        let candidates = self
+            .tile
+            .iter_coords()
+            .filter(|&(x, y)| matches!(self.tile.get(x, y), Some(Tile::Permanent(_))))
+            .collect::<Vec<(usize, usize)>>();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let (x, y) = candidates[self.rng.gen_range(0, candidates.len())];
+        if let Some(Tile::Permanent(plant)) = self.tile.set(x, y, Tile::Empty) {
+            let msg = format!("Pests infested {} at {}", plant.name, tile_name(x, y));
+            self.log_event(report, msg);
+            self.tile.set(x, y, Tile::Infested(plant, 0));
+        }
+    }
+
+    /// Moves every cloud one column along its path, drops the ones that left
+    /// the board and possibly rolls a new one.
+    fn advance_clouds(&mut self, report: &mut RoundReport) {
+        self.clouds = self
+            .clouds
+            .iter()
+            .map(Cloud::advanced)
+            .filter(|cloud| cloud.on_board(self.width()))
+            .collect();
+
+        if self.rng.gen::<f32>() < self.settings.cloud_chance {
+            let (width, height) = (self.width(), self.height());
+            let cloud = Cloud::spawn(&mut self.rng, width, height);
+            let msg = format!("{} drifts in from the {}", cloud.kind, cloud.entry);
+            self.log_event(report, msg);
+            self.clouds.push(cloud);
+        }
+    }
+
+    /// Plays out the round and reports what came of it.
+    fn update_game(&mut self) -> RoundReport {
+        let mut report = RoundReport {
+            round: self.round,
+            ..RoundReport::default()
+        };
+        // Before the harvest, drops of this round are still fresh
+        self.age_hands(&mut report);
+        // Plants placed this round don't grow yet, their effects wait too
+        let growing = self
+            .tile
+            .iter_coords()
+            .filter(|&(x, y)| {
+                matches!(
+                    self.tile.get(x, y),
+                    Some(Tile::Permanent(_) | Tile::Infested(..))
+                )
+            })
+            .collect::<Vec<(usize, usize)>>();
+        for phase in ROUND_PHASES {
+            match phase {
+                RoundPhase::Pests => self.spawn_pest(&mut report),
+                RoundPhase::Grow => self.grow_plants(),
+                RoundPhase::Sky => self.apply_weather(&mut report),
+                RoundPhase::Water => self.water_plants(),
+                RoundPhase::Mature => self.mature_plants(&mut report),
+                RoundPhase::Passives => self.score_board(&mut report),
+                RoundPhase::Spread => self.spread_plants(&mut report),
+                RoundPhase::Promote => self.promote_plants(),
+                RoundPhase::Weather => self.advance_clouds(&mut report),
+            }
+            self.note_board();
+        }
+        for (x, y) in growing {
+            if let Some(Tile::Permanent(p) | Tile::Infested(p, _)) = self.tile.get_mut(x, y) {
+                effects::tick(&mut p.effects);
+            }
+        }
+        self.check_families(&mut report);
+        self.pest_actions = 0;
+        self.placed = 0;
+        self.bonus_actions = 0;
+        self.watered.clear();
+        if self.settings.carryover_water {
+            self.water += WATER_PER_ROUND;
+        } else {
+            self.water = WATER_PER_ROUND;
+        }
+        self.round += 1;
+        self.records.longest_run = self.round;
+        let weather = self.weather;
+        self.weather = self.forecast;
+        self.forecast = weather::roll(&mut self.rng);
+
+        let nothing_left = self.hands_empty() && self.tile.iter().all(|t| matches!(t, Tile::Empty));
+        match self.mode.ending(self.rounds_left(), nothing_left) {
+            Some(Ending::RoundLimit) => {
+                self.final_harvest(&mut report);
+                self.state = State::GameOver;
+            }
+            Some(Ending::NothingLeft) => {
+                self.log_event(&mut report, String::from("Nothing is left to grow"));
+                self.state = State::GameOver;
+            }
+            None => {
+                self.log(format!("Weather: {}", self.weather));
+                if self.weather != weather {
+                    report
+                        .events
+                        .push(format!("The weather turns to {}", self.weather));
+                }
+            }
+        }
+        report.streak_before = self.streak;
+        self.streak = match report.matured.is_empty() && self.picked == 0 {
+            true => 0,
+            false => self.streak + 1,
+        };
+        report.streak = self.streak;
+        self.picked = 0;
+        self.check_objectives();
+        if let Some(puzzle) = self.settings.puzzle.as_ref() {
+            if self.state == State::GameOver {
+                let verdict = match puzzle.solved(self.total_points()) {
+                    true => "Puzzle solved",
+                    false => "Puzzle failed",
+                };
+                self.log_event(
+                    &mut report,
+                    format!(
+                        "{}: {} of {} points",
+                        verdict,
+                        format_points(self.total_points()),
+                        format_points(puzzle.target)
+                    ),
+                );
+            }
+        }
+        if self.state != State::GameOver && draft::due(self.round, self.draft_every) {
+            let draft = self.roll_draft();
+            let offered = draft
+                .rewards
+                .iter()
+                .map(Reward::to_string)
+                .collect::<Vec<String>>();
+            self.log_event(
+                &mut report,
+                format!("A draft offers {}", offered.join(", ")),
+            );
+            self.draft = Some(draft);
+            self.state = State::Drafting;
+        }
+        self.round_scores.push(self.total_points());
+        report
+    }
+
+    /// Rolls the rewards of a draft: plants the drops could give, a rare
+    /// one if there is any, water and an extra action with an action budget.
+    /// No plant is offered twice.
+    fn roll_draft(&mut self) -> Draft {
+        let mut plants = self
+            .id_to_plant
+            .iter()
+            .filter(|p| self.is_unlocked(CardId::Plant(p.id)))
+            .map(|p| p.id)
+            .collect::<Vec<PlantId>>();
+        let rare = plants
+            .iter()
+            .copied()
+            .filter(|id| {
+                matches!(
+                    self.id_to_plant[id.index()].rarity,
+                    Rarity::Rare | Rarity::Legendary
+                )
+            })
+            .collect::<Vec<PlantId>>();
+        let mut pool = vec![Reward::Water(draft::WATER_REWARD)];
+        if self.actions_per_round > 0 {
+            pool.push(Reward::ExtraAction);
+        }
+        if !rare.is_empty() {
+            let id = rare[self.rng.gen_range(0, rare.len())];
+            plants.retain(|&plant| plant != id);
+            pool.push(Reward::Card(self.card(CardId::Plant(id))));
+        }
+        for _ in 0..2 {
+            if plants.is_empty() {
+                break;
+            }
+            let id = plants.swap_remove(self.rng.gen_range(0, plants.len()));
+            pool.push(Reward::Card(self.card(CardId::Plant(id))));
+        }
+        let mut rewards = Vec::new();
+        while rewards.len() < draft::DRAFT_CHOICES && !pool.is_empty() {
+            rewards.push(pool.swap_remove(self.rng.gen_range(0, pool.len())));
+        }
+        Draft {
+            rewards,
+            selected: 0,
+        }
+    }
+
+    /// Plays an action of a running draft. Nothing else is allowed until
+    /// a reward is taken or the draft skipped.
+    fn on_draft(&mut self, action: Action) -> bool {
+        let Some(draft) = self.draft.as_mut() else {
+            self.state = State::Choosing;
+            return false;
+        };
+        match action {
+            Action::Up => draft.on_up(),
+            Action::Down => draft.on_down(),
+            Action::Select(slot) => return draft.select(slot),
+            Action::Place => {
+                let reward = self.draft.take().and_then(Draft::take);
+                match reward.clone() {
+                    Some(Reward::Card(card)) => {
+                        self.give(self.turn, [card]);
+                    }
+                    Some(Reward::ExtraAction) => self.bonus_actions += 1,
+                    Some(Reward::Water(water)) => self.water += water,
+                    None => {}
+                }
+                match reward {
+                    Some(reward) => self.notify(format!("Took {} from the draft", reward)),
+                    None => self.notify("Nothing to take from the draft"),
+                }
+                self.state = State::Choosing;
+            }
+            Action::SkipDraft => {
+                self.draft = None;
+                self.notify("Skipped the draft");
+                self.state = State::Choosing;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Swaps in freshly loaded plants. Plants on the board keep their stats,
+    /// cards are refreshed. Ids can change between loads, so everything in
+    /// play is matched up by name once more.
+    fn reload_plants(&mut self, mut set: PlantSet) -> Result<(), String> {
+        mutators::adjust_plants(&self.mutators, &mut set.plants);
+        let mut hybrids = set.hybrids(&self.objectives)?;
+        mutators::adjust_hybrids(&self.mutators, &mut hybrids);
+        let cards = self
+            .hand
+            .iter()
+            .chain(&self.reserve)
+            .chain(self.choosing.choice.as_ref())
+            .chain(self.seats.iter().flat_map(|seat| seat.hand.iter()))
+            .chain(self.seats.iter().flat_map(|seat| seat.reserve.iter()))
+            .map(Card::name);
+        let mut missing = cards
+            .chain(
+                self.tile
+                    .iter()
+                    .filter_map(Tile::plant)
+                    .map(|p| p.name.as_ref()),
+            )
+            .filter(|name| set.card_id(name).is_none())
+            .map(|name| format!("<{}>", name))
+            .collect::<Vec<String>>();
+        missing.sort();
+        missing.dedup();
+        if !missing.is_empty() {
+            return Err(format!("Still in play but missing: {}", missing.join(", ")));
+        }
+
+        let names = self
+            .id_to_plant
+            .iter()
+            .map(|p| CardId::Plant(p.id))
+            .chain((0..self.items.len()).map(|i| CardId::Item(ItemId(i as u32))))
+            .map(|id| (id, self.card_name(id).to_string()))
+            .collect::<HashMap<CardId, String>>();
+        let remap = |id: CardId| names.get(&id).and_then(|name| set.card_id(name));
+        for card in self
+            .hand
+            .iter_mut()
+            .chain(&mut self.reserve)
+            .chain(self.choosing.choice.as_mut())
+            .chain(
+                self.seats
+                    .iter_mut()
+                    .flat_map(|seat| seat.hand.iter_mut().chain(&mut seat.reserve)),
+            )
+        {
+            *card = set.card(card.name());
+        }
+        for tile in self.tile.iter_mut() {
+            if let Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _) = tile {
+                p.id = set.plant(&p.name).id;
+                set.resolve_drops(p);
+                if let Some(fate) = &mut p.fate {
+                    *fate = fate.iter().filter_map(|&id| remap(id)).collect();
+                }
+            }
+        }
+        for warning in &set.warnings {
+            self.log(warning);
+        }
+        self.notify(format!("Reloaded {} plants", set.plants.len()));
+        self.hybrids = hybrids;
+        self.id_to_plant = set.plants;
+        self.items = set.items;
+        Ok(())
+    }
+
+    /// The score as shown to the player, `points` itself keeps full precision.
+    fn score_display(&self) -> String {
+        format_points(self.points())
+    }
+
+    fn record_harvest(&mut self, name: &str, points: f32) {
+        let stats = self.plant_stats.entry(name.to_string()).or_default();
+        stats.harvested += 1;
+        stats.points += points;
+        self.records.note_harvest(name, points);
+    }
+
+    /// Keeps the run's records up to date with the plants on the board.
+    fn note_board(&mut self) {
+        let mut plants = 0;
+        for p in self.tile.iter().filter_map(Tile::plant) {
+            plants += 1;
+            self.records.note_size(&p.name, p.size);
+            self.records.note_age(&p.name, p.age);
+        }
+        self.records.most_plants = self.records.most_plants.max(plants);
+    }
+
+    /// Counts a harvested plant towards the harvest objectives.
+    fn on_harvest(&mut self, name: &str) {
+        let mut completed = Vec::new();
+        for (i, objective) in self.objectives.iter_mut().enumerate() {
+            if let Goal::Harvest { plant, .. } = &objective.goal {
+                if plant == name && objective.advance(objective.progress + 1) {
+                    completed.push(i);
+                }
+            }
+        }
+        for i in completed {
+            self.complete_objective(i);
+        }
+    }
+
+    /// Updates the objectives that depend on the board and the score, after
+    /// a placement and at the end of every round.
+    fn check_objectives(&mut self) {
+        let on_board = self.tile.iter().filter(|t| t.plant().is_some()).count() as u32;
+        let points = self.points();
+        let mut completed = Vec::new();
+        for (i, objective) in self.objectives.iter_mut().enumerate() {
+            let reached = match objective.goal {
+                Goal::PlantsOnBoard { .. } => objective.advance(on_board),
+                Goal::PointsBy { round, .. } => {
+                    let reached = objective.advance(points as u32);
+                    if objective.status == Status::Open && self.round > round {
+                        objective.status = Status::Failed;
+                    }
+                    reached
+                }
+                Goal::Harvest { .. } => false,
+            };
+            if reached {
+                completed.push(i);
+            }
+        }
+        for i in completed {
+            self.complete_objective(i);
+        }
+    }
+
+    /// Distinct families of the plants on the board.
+    fn families_on_board(&self) -> usize {
+        self.tile
+            .iter()
+            .filter_map(Tile::plant)
+            .filter_map(|p| p.family.as_deref())
+            .collect::<HashSet<&str>>()
+            .len()
+    }
+
+    /// Whether the plants are sorted into families at all.
+    fn has_families(&self) -> bool {
+        self.id_to_plant.iter().any(|p| p.family.is_some())
+    }
+
+    /// Pays the collection bonus when the board holds more families than
+    /// ever before in the run, from the threshold on.
+    /// Whether every tile that takes a plant has one.
+    fn board_full(&self) -> bool {
+        self.tile.iter_coords().all(|(x, y)| {
+            !self.terrain_at(x, y).can_plant() || !matches!(self.tile.get(x, y), Some(Tile::Empty))
+        })
+    }
+
+    /// Credits every plant on the board to its owner and the full board
+    /// bonus to the player whose turn it is, like the collection bonus.
+    fn score_board(&mut self, report: &mut RoundReport) {
+        let settings = self.settings.clone();
+        let owners = self
+            .tile
+            .iter()
+            .filter_map(Tile::plant)
+            .map(|p| p.owner)
+            .collect::<Vec<usize>>();
+        if settings.tile_points > 0.0 && !owners.is_empty() {
+            for &owner in &owners {
+                self.credit(owner, ScoreCategory::Passives, settings.tile_points);
+            }
+            let plants = owners.len();
+            report.passives.push(Passive {
+                name: format!(
+                    "{} plant{} on the board",
+                    plants,
+                    if plants == 1 { "" } else { "s" }
+                ),
+                points: settings.tile_points * plants as f32,
+            });
+        }
+        if settings.full_board_bonus > 0.0 && self.board_full() {
+            let turn = self.turn;
+            self.credit(turn, ScoreCategory::Passives, settings.full_board_bonus);
+            let bonus = format_points(settings.full_board_bonus);
+            self.log(format!("Full board bonus (+{})", bonus));
+            report.passives.push(Passive {
+                name: "Full board".to_string(),
+                points: settings.full_board_bonus,
+            });
+        }
+    }
+
+    fn check_families(&mut self, report: &mut RoundReport) {
+        let threshold = self.settings.family_threshold;
+        let families = self.families_on_board();
+        if threshold == 0 || families < threshold || families <= self.families_rewarded {
+            return;
+        }
+        self.families_rewarded = families;
+        let bonus = FAMILY_BONUS * families as f32;
+        self.ledger.add(ScoreCategory::Families, bonus);
+        let msg = format!(
+            "Collection bonus: {} {} on the board (+{})",
+            families,
+            if families == 1 { "family" } else { "families" },
+            format_points(bonus)
+        );
+        self.log_event(report, msg);
+    }
+
+    fn complete_objective(&mut self, i: usize) {
+        let objective = &self.objectives[i];
+        let msg = format!(
+            "Objective complete: {} (+{})",
+            objective,
+            format_points(objective.bonus)
+        );
+        self.ledger.add(ScoreCategory::Objectives, objective.bonus);
+        self.notify(msg);
+    }
+
+    /// Chance that a harvest this round drops anything, after the decay
+    /// of the mode.
+    fn drop_chance(&self) -> f32 {
+        self.settings.difficulty.drop_chance * self.mode.drop_decay(self.round)
+    }
+
+    /// Rounds until the run ends, `None` without a round limit.
+    pub fn rounds_left(&self) -> Option<u32> {
+        self.settings
+            .round_limit
+            .map(|limit| limit.saturating_sub(self.round))
+    }
+
+    /// Harvests every healthy plant at its current size when the run ends,
+    /// without drops.
+    fn final_harvest(&mut self, report: &mut RoundReport) {
+        let mut total = 0.0;
+        for (x, y) in self.tile.iter_coords() {
+            if let Some(Tile::Permanent(p) | Tile::New(p)) = self.tile.set(x, y, Tile::Empty) {
+                let base = p.size as f32 * p.stats().points_per_size;
+                let points = self.credit_harvest(p.owner, base);
+                self.record_harvest(&p.name, points);
+                total += points;
+                report.matured.push(Matured {
+                    plant: p.name.to_string(),
+                    tile: tile_name(x, y),
+                    points,
+                    cards: Vec::new(),
+                    sown: Vec::new(),
+                });
+            }
+        }
+        self.log(format!("Final harvest: {} points", format_points(total)));
+    }
+
+    /// What `plant` on (x, y) is expected to score: its value at max age, or
+    /// at the final harvest if the run ends first. Clouds already on the
+    /// board are followed along their path, and so are the effects of the
+    /// plant until they run out.
+    fn projected_points(&self, x: usize, y: usize, plant: &Plant) -> f32 {
+        let advance = |clouds: Vec<Cloud>| -> Vec<Cloud> {
+            clouds
+                .iter()
+                .map(Cloud::advanced)
+                .filter(|cloud| cloud.on_board(self.width()))
+                .collect()
+        };
+
+        let mut clouds = self.clouds.clone();
+        let mut left = self.rounds_left().unwrap_or(u32::MAX);
+        // Cards and freshly placed plants sit out their first round
+        if !matches!(
+            self.tile.get(x, y),
+            Some(Tile::Permanent(_) | Tile::Infested(..))
+        ) {
+            left = left.saturating_sub(1);
+            clouds = advance(clouds);
+        }
+        let rounds = plant.max_age.saturating_sub(plant.age).max(1).min(left);
+        let terrain = self.terrain_at(x, y);
+        let mut status = match self.tile.get(x, y).and_then(Tile::planted) {
+            Some(p) => p.effects.clone(),
+            None => Vec::new(),
+        };
+
+        let mut size = plant.size;
+        let mut stats = effects::effective(plant, &status);
+        for age in plant.age..plant.age + rounds {
+            stats = effects::effective_at(plant, &status, age);
+            size += growth(&stats, weather::weather_at(&clouds, x, y), terrain);
+            size = size.saturating_sub(stats.damage);
+            effects::tick(&mut status);
+            clouds = advance(clouds);
+        }
+        size as f32 * stats.points_per_size
+    }
+
+    fn grow_plants(&mut self) {
+        let drought = self.weather == Weather::Drought;
+        for (x, y) in self.tile.iter_coords() {
+            let weather = weather::weather_at(&self.clouds, x, y);
+            let terrain = self.terrain_at(x, y);
+            match self.tile.get_mut(x, y) {
+                Some(Tile::Permanent(p)) => {
+                    let stats = p.stats();
+                    p.age += 1;
+                    // Overripe plants keep the size they were ripe at
+                    if !drought && p.age <= p.max_age {
+                        p.size += growth(&stats, weather, terrain);
+                    }
+                    p.size = p.size.saturating_sub(stats.damage);
+                }
+                Some(Tile::Infested(p, rounds)) => {
+                    let stats = p.stats();
+                    p.age += 1;
+                    p.size = p.size.saturating_sub(1 + stats.damage);
+                    *rounds += 1;
+                }
+                Some(Tile::Empty | Tile::New(_)) | None => {}
+            }
+        }
+    }
+
+    fn apply_weather(&mut self, report: &mut RoundReport) {
+        match self.weather {
+            Weather::Sunny | Weather::Drought => {}
+            Weather::Rain => {
+                for tile in self.tile.iter_mut() {
+                    if let Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _) = tile {
+                        p.size += 1;
+                    }
+                }
+            }
+            Weather::Storm => {
+                let new = self
+                    .tile
+                    .iter_coords()
+                    .filter(|&(x, y)| matches!(self.tile.get(x, y), Some(Tile::New(_))))
+                    .collect::<Vec<(usize, usize)>>();
+                if new.is_empty() {
+                    return;
+                }
+                let (x, y) = new[self.rng.gen_range(0, new.len())];
+                if let Some(Tile::New(p)) = self.tile.set(x, y, Tile::Empty) {
+                    let msg = format!("The storm uprooted {} at {}", p.name, tile_name(x, y));
+                    self.log_event(report, msg);
+                }
+            }
+        }
+    }
+
+    fn water_plants(&mut self) {
+        for &(x, y) in &self.watered {
+            if let Some(Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _)) =
+                self.tile.get_mut(x, y)
+            {
+                p.size += 1;
+            }
+        }
+    }
+
+    /// Harvests row by row, so drops of earlier tiles never depend on what
+    /// later tiles do this round. With manual harvests ripe plants stay
+    /// until they are picked or wither.
+    fn mature_plants(&mut self, report: &mut RoundReport) {
+        for (x, y) in self.tile.iter_coords() {
+            match self.tile.get(x, y) {
+                Some(Tile::Permanent(p)) if self.withered(p) => {
+                    let msg = format!("{} at {} withered unpicked", p.name, tile_name(x, y));
+                    self.tile.set(x, y, Tile::Empty);
+                    self.log_event(report, msg);
+                }
+                Some(Tile::Permanent(p))
+                    if p.age >= p.max_age && self.harvest_mode == HarvestMode::Auto =>
+                {
+                    report.matured.extend(self.harvest(x, y));
+                }
+                Some(Tile::Infested(p, _)) if p.size == 0 || p.age >= p.max_age => {
+                    let msg = format!("{} at {} was eaten by pests", p.name, tile_name(x, y));
+                    self.tile.set(x, y, Tile::Empty);
+                    self.log_event(report, msg);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Scores the ripe plant at (x, y) with its drops and hybrids, and
+    /// clears the tile. `None` if there is no plant to harvest.
+    fn harvest(&mut self, x: usize, y: usize) -> Option<Matured> {
+        let Some(Tile::Permanent(p)) = self.tile.get(x, y) else {
+            return None;
+        };
+        let base = p.size as f32 * p.stats().points_per_size;
+        let points = base * self.multiplier();
+        let owner = p.owner;
+        let name = p.name.to_string();
+        let (fate, target) = match &p.fate {
+            Some(fate) => (fate.clone(), p.fate_target),
+            // Drops that depend on neighbors, or saved before drops were rolled up front
+            None => {
+                let open = self.open_drops(p, Some((x, y)));
+                roll_fate(p, &open, self.drop_chance(), &mut self.rng)
+            }
+        };
+        let drops = fate.iter().map(|&id| self.card(id)).collect::<Vec<Card>>();
+        let hybrids = self.cross_breed(x, y);
+        self.tile.set(x, y, Tile::Empty);
+        self.log(format!(
+            "{} at {} matured for {} points{}",
+            name,
+            tile_name(x, y),
+            format_points(points),
+            self.streak_note()
+        ));
+        self.credit_harvest(owner, base);
+        self.record_harvest(&name, points);
+        self.harvests.push((x, y, points));
+        let (drops, sown) = match target {
+            DropTarget::Hand => (drops, Vec::new()),
+            DropTarget::AdjacentTile => self.sow(x, y, owner, drops),
+        };
+        let mut cards = self.give(owner, drops);
+        for child in hybrids {
+            self.log(format!("New hybrid discovered! {}", child.name));
+            cards.extend(self.give(owner, [Card::Plant(Box::new(child))]));
+        }
+        self.on_harvest(&name);
+        Some(Matured {
+            plant: name,
+            tile: tile_name(x, y),
+            points,
+            cards,
+            sown,
+        })
+    }
+
+    /// Plants the drops of the harvest on (x, y) as new on random free
+    /// neighbor tiles, one tile each. Returns the cards left for the hand
+    /// and where the others took root, like `Grass at B2`.
+    fn sow(
+        &mut self,
+        x: usize,
+        y: usize,
+        owner: usize,
+        drops: Vec<Card>,
+    ) -> (Vec<Card>, Vec<String>) {
+        let mut left = Vec::new();
+        let mut sown = Vec::new();
+        for card in drops {
+            let Card::Plant(mut plant) = card else {
+                left.push(card);
+                continue;
+            };
+            let free = self
+                .tile
+                .neighbors(x, y)
+                .filter(|&(nx, ny)| self.can_place_plant(nx, ny).is_ok())
+                .collect::<Vec<(usize, usize)>>();
+            if free.is_empty() {
+                left.push(Card::Plant(plant));
+                continue;
+            }
+            let (nx, ny) = free[self.rng.gen_range(0, free.len())];
+            plant.owner = owner;
+            self.log(format!(
+                "{} from {} took root at {}",
+                plant.name,
+                tile_name(x, y),
+                tile_name(nx, ny)
+            ));
+            sown.push(format!("{} at {}", plant.name, tile_name(nx, ny)));
+            self.tile.set(nx, ny, Tile::New(PlantedPlant::new(*plant)));
+        }
+        let homeless = left
+            .iter()
+            .filter(|card| matches!(card, Card::Plant(_)))
+            .map(Card::name)
+            .collect::<Vec<&str>>();
+        if !homeless.is_empty() {
+            self.log(format!(
+                "No free tile next to {}, {} went to the hand",
+                tile_name(x, y),
+                homeless.join(", ")
+            ));
+        }
+        (left, sown)
+    }
+
+    /// Rounds a plant placed now still grows before the run ends, when
+    /// those are too few for `plant` to mature. `None` without a round
+    /// limit or with time enough.
+    fn too_late(&self, plant: &Plant) -> Option<u32> {
+        // Freshly placed plants sit out their first round
+        let left = self.rounds_left()?.saturating_sub(1);
+        (plant.max_age.saturating_sub(plant.age) > left).then_some(left)
+    }
+
+    /// Whether `plant` is wasted when played now: too late to mature with
+    /// auto harvests. Manual harvests still pick it at the final harvest.
+    fn wasted(&self, plant: &Plant) -> bool {
+        self.harvest_mode == HarvestMode::Auto && self.too_late(plant).is_some()
+    }
+
+    /// The chosen plant if placing it on (x, y) would waste it.
+    fn wasted_placement(&self, x: usize, y: usize) -> Option<&Plant> {
+        match &self.choosing.choice {
+            Some(Card::Plant(plant)) if self.can_place_plant(x, y).is_ok() => {
+                self.wasted(plant).then_some(plant)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a manual harvest can pick the plant at (x, y).
+    fn can_pick(&self, x: usize, y: usize) -> bool {
+        self.harvest_mode == HarvestMode::Manual
+            && matches!(self.tile.get(x, y), Some(Tile::Permanent(p)) if p.age >= p.max_age)
+    }
+
+    /// What a harvest is worth this round for the streak so far.
+    fn multiplier(&self) -> f32 {
+        streak_multiplier(self.streak)
+    }
+
+    /// The multiplier for a log line, nothing without a streak.
+    fn streak_note(&self) -> String {
+        match self.streak {
+            0 => String::new(),
+            _ => format!(" (streak x{:.1})", self.multiplier()),
+        }
+    }
+
+    /// Overripe for longer than the window, it withers in the `Mature` phase.
+    fn withered(&self, p: &Plant) -> bool {
+        self.harvest_mode == HarvestMode::Manual && p.age >= p.max_age + self.overripe_rounds
+    }
+
+    /// Rounds an overripe plant can still be picked in, counting this one.
+    fn overripe_left(&self, p: &Plant) -> Option<u32> {
+        (self.harvest_mode == HarvestMode::Manual && p.age >= p.max_age)
+            .then(|| (p.max_age + self.overripe_rounds).saturating_sub(p.age))
+    }
+
+    /// Picks the ripe plant under the cursor, with manual harvests.
+    fn on_pick(&mut self) {
+        let (x, y) = (self.placing.x, self.placing.y);
+        if self.harvest_mode == HarvestMode::Auto {
+            self.notify("Plants are harvested on their own in this run");
+        } else if let Some(picked) = self.can_pick(x, y).then(|| self.harvest(x, y)).flatten() {
+            self.picked += 1;
+            self.notify(format!(
+                "Picked {} for {} points",
+                picked.plant,
+                format_points(picked.points)
+            ));
+        } else if let Some(Tile::Permanent(p)) = self.tile.get(x, y) {
+            let msg = format!("{} is ripe in {} rounds", p.name, p.max_age - p.age);
+            self.notify(msg);
+        } else {
+            self.notify("Nothing ripe to pick here");
+        }
+    }
+
+    /// Rolls every hybrid the plant at (x,y) forms with an orthogonal
+    /// neighbor, returning the children that came out of it.
+    fn cross_breed(&mut self, x: usize, y: usize) -> Vec<Plant> {
+        let id = match self.plant_at(x, y) {
+            Some(p) => p.id,
+            None => return Vec::new(),
+        };
+        let neighbors = self.tile.neighbors(x, y).collect::<Vec<_>>();
+
+        let mut children = Vec::new();
+        for hybrid in &self.hybrids {
+            let partner = match hybrid.partner(id) {
+                Some(partner) => partner,
+                None => continue,
+            };
+            let adjacent = neighbors
+                .iter()
+                .any(|&(nx, ny)| self.plant_at(nx, ny).is_some_and(|p| p.id == partner));
+            if !self.is_unlocked(CardId::Plant(hybrid.child)) {
+                continue;
+            }
+            if adjacent && self.rng.gen::<f32>() < hybrid.chance {
+                children.push(self.id_to_plant[hybrid.child.index()].clone());
+            }
+        }
+        children
+    }
+
+    /// Every spreading plant rolls once per empty neighbor it could grow on,
+    /// and spreads at most once per round.
+    fn spread_plants(&mut self, report: &mut RoundReport) {
+        let multiplier = self.settings.spread_multiplier;
+        for (x, y) in self.tile.iter_coords() {
+            let (id, owner, chance) = match self.tile.get(x, y) {
+                Some(Tile::Permanent(p)) if p.spread_chance > 0.0 => {
+                    (p.id, p.owner, p.spread_chance * multiplier)
+                }
+                _ => continue,
+            };
+            for (nx, ny) in self.tile.neighbors(x, y) {
+                if self.can_place_plant(nx, ny).is_err() || self.rng.gen::<f32>() >= chance {
+                    continue;
+                }
+                let mut copy = self.id_to_plant[id.index()].clone();
+                copy.owner = owner;
+                let msg = format!(
+                    "{} spread from {} to {}",
+                    copy.name,
+                    tile_name(x, y),
+                    tile_name(nx, ny)
+                );
+                self.log_event(report, msg);
+                self.tile.set(nx, ny, Tile::New(PlantedPlant::new(copy)));
+                break;
+            }
+        }
+    }
+
+    /// Also rolls the drops of every promoted plant, so they are settled
+    /// long before the harvest. Plants with drops that depend on their
+    /// neighbors roll when they mature, once the neighbors are known.
+    fn promote_plants(&mut self) {
+        for (x, y) in self.tile.iter_coords() {
+            if let Some(Tile::New(p)) = self.tile.get(x, y) {
+                let mut plant = p.clone();
+                if plant.drops.iter().all(|d| d.requires_adjacent.is_none()) {
+                    let open = self.open_drops(&plant, None);
+                    let (fate, target) =
+                        roll_fate(&plant, &open, self.drop_chance(), &mut self.rng);
+                    plant.fate = Some(fate);
+                    plant.fate_target = target;
+                }
+                self.tile.set(x, y, Tile::Permanent(plant));
+            }
+        }
+    }
+}
+
+/// How much a healthy plant grows in one round under the given weather.
+fn growth(stats: &Stats, weather: Option<CloudKind>, terrain: Terrain) -> u32 {
+    let bonus = match terrain {
+        Terrain::Fertile => 1,
+        _ => 0,
+    };
+    match weather {
+        _ if stats.halted => 0,
+        Some(CloudKind::Rain) => stats.size_per_turn + 1 + bonus,
+        Some(CloudKind::Drought) => 0,
+        None => stats.size_per_turn + bonus,
+    }
+}
+
+/// Formats points to one decimal without a trailing `.0`, hiding the float
+/// noise that adds up over many fractional harvests.
+fn format_points(points: f32) -> String {
+    let text = format!("{:.1}", points);
+    match text.strip_suffix(".0") {
+        Some("-0") => "0".to_string(),
+        Some(whole) => whole.to_string(),
+        None => text,
+    }
+}
+
+/// Formats a duration as `m:ss`, or `h:mm:ss` from an hour on.
+fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        hours => format!("{}:{:02}:{:02}", hours, secs / 60 % 60, secs % 60),
+    }
+}
+
+/// Rolls what `plant` drops at maturity out of its `open` drops, and where
+/// it goes. A harvest only comes up empty with a `drop_chance` below 1, or
+/// when every drop is locked.
+fn roll_fate<R: Rng>(
+    plant: &Plant,
+    open: &[bool],
+    drop_chance: f32,
+    rng: &mut R,
+) -> (Vec<CardId>, DropTarget) {
+    if drop_chance < 1.0 && rng.gen::<f32>() >= drop_chance {
+        return (Vec::new(), DropTarget::Hand);
+    }
+    get_drop(plant, open, rng).map_or_else(
+        || (Vec::new(), DropTarget::Hand),
+        |drop| (drop.ids.clone(), drop.target),
+    )
+}
+
+/// Takes a round of freshness off a card held through it, with cards
+/// keeping for `limit` rounds. True once it has gone stale.
+fn wilt(card: &mut Card, limit: u32) -> bool {
+    let freshness = card.freshness_mut();
+    let left = freshness.unwrap_or(limit).saturating_sub(1);
+    *freshness = Some(left);
+    left == 0
+}
+
+/// "2x Grass" for a drop of one kind, "Grass + Tall Grass" otherwise.
+fn drop_label(plants: &[String]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for name in plants {
+        match counts.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+    counts
+        .iter()
+        .map(|&(name, count)| match count {
+            1 => name.to_string(),
+            _ => format!("{}x {}", count, name),
+        })
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+/// What the plant on (x, y), about to mature, will drop: the settled fate
+/// with `--show-fate`, otherwise every outcome with its chance.
+fn fate_line(plant: &Plant, (x, y): (usize, usize), game: &Game, theme: &Theme) -> Line<'static> {
+    let label = Style::default().fg(theme.label);
+    let settings = &game.settings;
+    if let (true, Some(fate)) = (settings.show_fate, &plant.fate) {
+        let fate = if fate.is_empty() {
+            tr("info.nothing").to_string()
+        } else {
+            let names = fate
+                .iter()
+                .map(|&id| game.card_name(id).to_string())
+                .collect::<Vec<String>>();
+            drop_label(&names)
+        };
+        return Line::from(vec![
+            Span::styled(format!("{}: ", tr("info.will_drop")), label),
+            Span::raw(fate),
+        ]);
+    }
+    let drop_chance = game.drop_chance().clamp(0.0, 1.0);
+    let mut outcomes = plant
+        .drops
+        .iter()
+        .zip(plant.drop_probabilities(&game.open_drops(plant, Some((x, y)))))
+        .filter(|(_, p)| *p > 0.0)
+        .map(|(drop, p)| {
+            format!(
+                "{} ({:.0}%)",
+                drop_label(&drop.plants),
+                p * drop_chance * 100.0
+            )
+        })
+        .collect::<Vec<String>>();
+    if drop_chance < 1.0 || outcomes.is_empty() {
+        outcomes.push(format!(
+            "{} ({:.0}%)",
+            tr("info.nothing"),
+            (1.0 - drop_chance) * 100.0
+        ));
+    }
+    Line::from(vec![
+        Span::styled(format!("{}: ", tr("info.will_drop_one_of")), label),
+        Span::raw(outcomes.join(" / ")),
+    ])
+}
+
+/// Rolls one of the plant's `open` drops, weighted by their chances. Always
+/// picks a drop as long as the open chances add up to something positive.
+fn get_drop<'a, R: Rng>(plant: &'a Plant, open: &[bool], rng: &mut R) -> Option<&'a Drop> {
+    if open.iter().all(|&open| open) {
+        return plant.drops.roll(rng.gen::<f32>());
+    }
+    let probabilities = plant.drop_probabilities(open);
+    // The last drop that can happen catches rounding errors at the top end
+    let last = probabilities.iter().rposition(|&p| p > 0.0)?;
+    let rnd = rng.gen::<f32>();
+
+    let mut running = 0.0;
+    for (i, (d, p)) in plant.drops.iter().zip(probabilities).enumerate() {
+        running += p;
+        if p > 0.0 && (rnd < running || i == last) {
+            return Some(d);
+        }
+    }
+    None
+}
+
+/// Whether the main loop keeps going after an action.
+#[derive(Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    /// Keeps going, but nothing on screen changed
+    Unchanged,
+    Quit,
+}
+
+/// Longest the screen goes without a draw, whether anything changed or not.
+const HEARTBEAT: Duration = Duration::from_secs(1);
+/// Longest a burst of keys holds back the draw.
+const MAX_FRAME_GAP: Duration = Duration::from_millis(50);
+
+/// `--debug-fps`: the draws of the last second.
+#[derive(Debug, Default)]
+struct FpsMeter {
+    draws: VecDeque<Instant>,
+}
+
+impl FpsMeter {
+    fn record(&mut self, now: Instant) {
+        self.draws.push_back(now);
+        while self
+            .draws
+            .front()
+            .is_some_and(|&draw| now.duration_since(draw) > Duration::from_secs(1))
+        {
+            self.draws.pop_front();
+        }
+    }
+
+    fn per_second(&self) -> usize {
+        self.draws.len()
+    }
+}
+
+/// Ticks per half period of the cursor blink.
+const BLINK_TICKS: u64 = 5;
+/// Ticks a status bar message stays up.
+const STATUS_TICKS: u64 = 20;
+/// Ticks a harvest effect lasts, the tile flashes for the first few.
+const EFFECT_TICKS: u32 = 10;
+const FLASH_TICKS: u32 = 3;
+/// Ticks between the popups of harvests in the same round.
+const EFFECT_STAGGER: u32 = 2;
+
+/// A harvest shown on the board for a moment after the plant is gone: the
+/// tile flashes and the points float up from it.
+#[derive(Debug, Clone, Copy)]
+struct TileEffect {
+    x: usize,
+    y: usize,
+    points: f32,
+    /// Ticks to wait before it shows, so popups come one after another
+    delay: u32,
+    /// Ticks left once it shows
+    ticks: u32,
+}
+
+impl TileEffect {
+    /// Ticks it has been showing, `None` while it still waits.
+    fn age(&self) -> Option<u32> {
+        (self.delay == 0).then(|| EFFECT_TICKS - self.ticks)
+    }
+}
+
+struct App {
+    game: Game,
+    list_state: ListState,
+    theme: Theme,
+    /// This board's high scores, filled in once the run is over
+    high_scores: Option<HighScores>,
+    /// Selection in the plant codex while it is open
+    codex: Option<ListState>,
+    /// The card in the info panel is shown full-screen, toggled with m
+    details: bool,
+    /// The report of the round that just ended pops up, until space
+    summary: bool,
+    /// Whether round summaries pop up at all, off in `--plain` and without
+    /// a terminal
+    summaries: bool,
+    /// Animation ticks since the start
+    ticks: u64,
+    /// Status bar message and the tick it was shown at
+    status: Option<(String, u64)>,
+    /// `--export`, written once the run is over
+    export: Option<PathBuf>,
+    /// The pause menu while it is open
+    pause: Option<PauseMenu>,
+    /// The save dialog or the load menu while one is open
+    slots: Option<SlotMenu>,
+    /// Keys go into the Plants filter after `/` until Enter or Esc
+    typing_filter: bool,
+    /// Column letters and row numbers on the board, toggled with g
+    grid_labels: bool,
+    /// Where the canvas board was last drawn, for mouse clicks. `None`
+    /// with the other renderers.
+    board_geometry: Option<BoardGeometry>,
+    /// The action being applied is the CPU's, not the keyboard's
+    ai_moving: bool,
+    /// Moves the CPU took this turn, see `MAX_AI_STEPS`
+    ai_steps: usize,
+    /// Percent of the width for the board, before `board_percent` narrows it
+    board_split: u16,
+    /// The config file `board_split` is kept in, only for the terminal UI
+    config_path: Option<PathBuf>,
+    /// Canvas or box-drawing board, `--plain` goes over both
+    renderer: Renderer,
+    /// Space in the round panel asked to confirm ending the round with
+    /// cards left that could still be placed. Any other action cancels it.
+    confirm_end: bool,
+    /// Space was pressed once on a plant `--overplant` would dig up
+    confirm_overplant: bool,
+    /// Space was pressed once to place a plant that can't mature before
+    /// the round limit
+    confirm_wasted: bool,
+    /// `--plain`: a text board, and a status line after every change
+    plain: bool,
+    /// The time played in the status bar and on the end screen, off with
+    /// `--no-timer`
+    timer: bool,
+    /// `--auto-advance`: the round ends once no move is left
+    auto_advance: bool,
+    /// Something changed since the last draw
+    needs_redraw: bool,
+    /// `--debug-fps`
+    fps: Option<FpsMeter>,
+    /// `--tutorial`: the step the player is at, `None` once it is over
+    tutorial: Option<Tutorial>,
+    /// The last state `--plain` described, to only repeat it on changes
+    described: String,
+    /// Seeds and bought themes, `None` with `--no-profile`
+    profile: Option<Profile>,
+    /// Seeds the finished run banked, for the end screen
+    banked: Option<u32>,
+    /// Profile records this run broke, announced once each
+    new_records: Vec<&'static str>,
+    /// The reserve is listed over the board
+    reserve: bool,
+    /// The score breakdown is shown over the board
+    breakdown: bool,
+    /// Whether the run counts for achievements, not with `--dev` or a
+    /// script unless `--allow-achievements`
+    earn_achievements: bool,
+    /// Whether harvests get effects on the board, off in `--plain` and
+    /// without a terminal
+    animate: bool,
+    effects: Vec<TileEffect>,
+    /// Plants every run is played with, read again by F5
+    pack: Pack,
+    /// `--dev`: the plant editor, shown while `editing`
+    editor: Option<Editor>,
+    editing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseItem {
+    Resume,
+    /// Play the same board again with the same seed
+    Restart,
+    /// Start over with a new seed
+    NewRun,
+    /// Those of the profile, earned and not
+    Achievements,
+    Quit,
+}
+
+const PAUSE_ITEMS: [PauseItem; 5] = [
+    PauseItem::Resume,
+    PauseItem::Restart,
+    PauseItem::NewRun,
+    PauseItem::Achievements,
+    PauseItem::Quit,
+];
+
+impl Display for PauseItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PauseItem::Resume => f.write_str(tr("pause.resume")),
+            PauseItem::Restart => f.write_str(tr("pause.restart")),
+            PauseItem::NewRun => f.write_str(tr("pause.new_run")),
+            PauseItem::Achievements => f.write_str(tr("pause.achievements")),
+            PauseItem::Quit => f.write_str(tr("pause.quit")),
+        }
+    }
+}
+
+struct PauseMenu {
+    list: ListState,
+    /// Quit was picked during a run and waits for a second Enter
+    confirm_quit: bool,
+    /// The achievements are shown instead of the items
+    achievements: bool,
+}
+
+struct HighScores {
+    entries: Vec<ScoreEntry>,
+    /// Where this run placed, `None` if it didn't make the table
+    rank: Option<usize>,
+}
+
+impl App {
+    fn new(theme: Theme, pack: Pack, settings: Arc<GlobalSetting>) -> App {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        App {
+            game: Game::empty(&pack, settings),
+            list_state,
+            theme,
+            high_scores: None,
+            codex: None,
+            details: false,
+            summary: false,
+            summaries: false,
+            ticks: 0,
+            status: None,
+            export: None,
+            pause: None,
+            slots: None,
+            typing_filter: false,
+            grid_labels: false,
+            board_geometry: None,
+            ai_moving: false,
+            ai_steps: 0,
+            board_split: 70,
+            config_path: None,
+            renderer: Renderer::Canvas,
+            confirm_end: false,
+            confirm_overplant: false,
+            confirm_wasted: false,
+            plain: false,
+            timer: false,
+            auto_advance: false,
+            needs_redraw: true,
+            fps: None,
+            tutorial: None,
+            described: String::new(),
+            profile: None,
+            banked: None,
+            new_records: Vec::new(),
+            reserve: false,
+            breakdown: false,
+            earn_achievements: false,
+            animate: false,
+            effects: Vec::new(),
+            pack,
+            editor: None,
+            editing: false,
+        }
+    }
+
+    /// Queues an effect for every harvest the game reported, after the ones
+    /// still waiting.
+    fn queue_effects(&mut self) {
+        let harvests = std::mem::take(&mut self.game.harvests);
+        if !self.animate {
+            return;
+        }
+        let mut delay = self.effects.iter().map(|e| e.delay + EFFECT_STAGGER).max();
+        for (x, y, points) in harvests {
+            let wait = delay.unwrap_or(0);
+            self.effects.push(TileEffect {
+                x,
+                y,
+                points,
+                delay: wait,
+                ticks: EFFECT_TICKS,
+            });
+            delay = Some(wait + EFFECT_STAGGER);
+        }
+    }
+
+    /// Next theme, skipping cosmetic ones the profile doesn't own.
+    fn cycle_theme(&mut self) {
+        let mut name = self.theme.name.next();
+        while !self.owns_theme(name) {
+            name = name.next();
+        }
+        self.theme = Theme::new(name);
+    }
+
+    fn owns_theme(&self, name: ThemeName) -> bool {
+        match &self.profile {
+            Some(profile) => profile.owns(name),
+            None => !name.cosmetic(),
+        }
+    }
+
+    /// Converts part of the final score into profile seeds.
+    fn bank_seeds(&mut self) {
+        let Some(profile) = self.profile.as_mut() else {
+            return;
+        };
+        let seeds = profile.bank(self.game.total_points());
+        match profile.save() {
+            Ok(()) => self.banked = Some(seeds),
+            Err(err) => self.game.log(format!("Couldn't save the profile: {}", err)),
+        }
+    }
+
+    /// Carries the run's bests over to the profile. Returns the news when a
+    /// record falls, further gains on the same record stay quiet.
+    fn check_records(&mut self) -> Option<String> {
+        if self.tutorial.is_some() {
+            return None;
+        }
+        let profile = self.profile.as_mut()?;
+        let broken = profile.records.merge(&self.game.records);
+        if broken.is_empty() {
+            return None;
+        }
+        if let Err(err) = profile.save() {
+            self.game.log(format!("Couldn't save the profile: {}", err));
+        }
+        let mut news = Vec::new();
+        for label in broken {
+            if !self.new_records.contains(&label) {
+                self.new_records.push(label);
+                news.push(format!("{}: {}", label, profile.records.value_of(label)));
+            }
+        }
+        if news.is_empty() {
+            return None;
+        }
+        let msg = format!("New record! {}", news.join(", "));
+        self.game.log(msg.clone());
+        Some(msg)
+    }
+
+    /// Unlocks the achievements `events` earned in the profile. Returns the
+    /// news when there are any.
+    fn check_achievements(&mut self, events: &[GameEvent]) -> Option<String> {
+        if !self.earn_achievements || self.tutorial.is_some() {
+            return None;
+        }
+        let profile = self.profile.as_mut()?;
+        let earned = achievements::check(&mut profile.achievements, events, &self.game);
+        if earned.is_empty() {
+            return None;
+        }
+        if let Err(err) = profile.save() {
+            self.game.log(format!("Couldn't save the profile: {}", err));
+        }
+        let names = earned.iter().map(|a| a.name).collect::<Vec<&str>>();
+        let msg = format!("Achievement unlocked! {}", names.join(", "));
+        self.game.log(msg.clone());
+        Some(msg)
+    }
+
+    /// Moves the split between board and side panel by a step and keeps
+    /// it in the config file for the next runs.
+    fn resize_board(&mut self, wider: bool) {
+        let split = match wider {
+            true => self.board_split + SPLIT_STEP,
+            false => self.board_split - SPLIT_STEP,
+        }
+        .clamp(MIN_BOARD_SPLIT, MAX_BOARD_SPLIT);
+        if split == self.board_split {
+            let edge = if wider { "wide" } else { "narrow" };
+            self.notify(format!("The board is as {} as it gets", edge));
+            return;
+        }
+        self.board_split = split;
+        let saved = self
+            .config_path
+            .as_deref()
+            .map(|path| config::set_value(path, "board_split", &split.to_string()));
+        match saved {
+            Some(Err(err)) => self.notify(format!("Couldn't save the board split: {}", err)),
+            _ => self.notify(format!("The board takes {}% of the width", split)),
+        }
+    }
+
+    /// What digging up the plant under the cursor costs, while it waits
+    /// for a second space.
+    fn overplant_prompt(&self) -> Option<String> {
+        let (x, y) = (self.game.placing.x, self.game.placing.y);
+        self.game.overplant_victim(x, y).map(|old| {
+            format!(
+                "Press space again to dig up {} at {} for -{} points",
+                old.name,
+                tile_name(x, y),
+                format_points(OVERPLANT_PENALTY)
+            )
+        })
+    }
+
+    /// What placing a plant that can't mature in time means, while it
+    /// waits for a second space.
+    fn wasted_prompt(&self) -> Option<String> {
+        let (x, y) = (self.game.placing.x, self.game.placing.y);
+        let plant = self.game.wasted_placement(x, y)?;
+        let left = self.game.too_late(plant)?;
+        Some(format!(
+            "{} needs {} rounds and only {} are left, press space again to plant it anyway",
+            plant.name, plant.max_age, left
+        ))
+    }
+
+    /// The prompt waiting for a second space, if any.
+    fn pending_prompt(&self) -> Option<String> {
+        match (self.confirm_overplant, self.confirm_wasted) {
+            (true, _) => self.overplant_prompt(),
+            (_, true) => self.wasted_prompt(),
+            _ => None,
+        }
+    }
+
+    /// Status line for `--plain`: what the action did, then where the
+    /// player is now if that changed.
+    fn narrate(&mut self, notice: Option<String>) {
+        let mut lines = notice.into_iter().collect::<Vec<String>>();
+        let view = self.game.describe();
+        if view != self.described {
+            lines.push(view.clone());
+            self.described = view;
+        }
+        if self.confirm_end {
+            lines.push("Cards could still be placed, press space again to end the round".into());
+        }
+        if let Some(prompt) = self.pending_prompt() {
+            lines.push(prompt);
+        }
+        if !lines.is_empty() {
+            self.notify(lines.join(". "));
+        }
+    }
+
+    /// Replaces the game with a fresh run on the same settings.
+    /// Swaps the run for the tutorial's, on its own plants and seed.
+    fn start_tutorial(&mut self) {
+        let settings = self.game.settings.clone();
+        self.game = Game::with_plants(tutorial::plant_set(), settings, tutorial::SEED);
+        self.tutorial = Some(Tutorial::default());
+        self.select(self.game.choosing.index);
+    }
+
+    /// Moves the tutorial on after `action`. Once it is done, or the run
+    /// ended early, a normal run starts with the regular plants.
+    fn check_tutorial(&mut self, action: Action, events: &[GameEvent]) {
+        let Some(tutorial) = self.tutorial.as_mut() else {
+            return;
+        };
+        if tutorial.advance(action, events, &self.game) || self.game.state == State::GameOver {
+            self.end_tutorial("Tutorial complete, this run is your own");
+        }
+    }
+
+    fn end_tutorial(&mut self, msg: &str) {
+        self.tutorial = None;
+        self.summary = false;
+        self.effects.clear();
+        self.restart(rand::random());
+        self.notify(msg);
+    }
+
+    fn restart(&mut self, seed: u64) {
+        // Puzzles always play on their own seed
+        let seed = self
+            .game
+            .settings
+            .puzzle
+            .as_ref()
+            .map_or(seed, |puzzle| puzzle.seed);
+        self.replace_game(Game::new(&self.pack, self.game.settings.clone(), seed));
+        self.notify(format!("New run with seed {}", seed));
+    }
+
+    /// Swaps the run for `game`, leaving nothing of the old one on screen.
+    fn replace_game(&mut self, game: Game) {
+        let grouped = self.game.choosing.grouped;
+        self.game = game;
+        self.game.choosing.grouped = grouped;
+        self.game.keep_selection_visible();
+        self.high_scores = None;
+        self.banked = None;
+        self.new_records.clear();
+        self.pause = None;
+        self.slots = None;
+        self.typing_filter = false;
+        self.select(self.game.choosing.index);
+    }
+
+    /// Opens the save dialog or the load menu, not during the tutorial.
+    fn open_slots(&mut self, kind: MenuKind) {
+        if self.tutorial.is_some() {
+            self.notify("Runs can be saved and loaded after the tutorial");
+            return;
+        }
+        if kind == MenuKind::Save && self.game.state == State::GameOver {
+            self.notify("The run is over, there is nothing left to save");
+            return;
+        }
+        match SlotMenu::open(kind, slots::slots_dir(), self.game.settings.clone()) {
+            Ok(menu) => self.slots = Some(menu),
+            Err(err) => self.notify(format!("Couldn't read the save slots: {}", err)),
+        }
+    }
+
+    fn on_slot_key(&mut self, key: KeyCode) {
+        let Some(menu) = self.slots.as_mut() else {
+            return;
+        };
+        match menu.on_key(key, &self.game, &self.pack.names) {
+            slots::Outcome::Open => {}
+            slots::Outcome::Closed => self.slots = None,
+            slots::Outcome::Saved(name) => {
+                self.slots = None;
+                self.notify(format!("Saved the run to slot {}", name));
+            }
+            slots::Outcome::Loaded(name, game) => {
+                self.summary = false;
+                self.effects.clear();
+                self.replace_game(*game);
+                // Saved before the selection was kept in line with the hand
+                self.game.keep_selection();
+                self.notify(format!("Loaded slot {}", name));
+            }
+        }
+    }
+
+    fn on_pause_menu(&mut self, action: Action) -> Flow {
+        let Some(pause) = self.pause.as_mut() else {
+            return Flow::Continue;
+        };
+        if pause.achievements {
+            if matches!(action, Action::Pause | Action::Confirm) {
+                pause.achievements = false;
+            }
+            return Flow::Continue;
+        }
+        let len = PAUSE_ITEMS.len();
+        let selected = pause.list.selected().unwrap_or(0);
+        match action {
+            Action::Pause => self.pause = None,
+            Action::Up => {
+                pause.confirm_quit = false;
+                pause.list.select(Some((selected + len - 1) % len));
+            }
+            Action::Down => {
+                pause.confirm_quit = false;
+                pause.list.select(Some((selected + 1) % len));
+            }
+            Action::Confirm => match PAUSE_ITEMS[selected] {
+                PauseItem::Resume => self.pause = None,
+                PauseItem::Restart => self.restart(self.game.seed),
+                PauseItem::NewRun => self.restart(rand::random()),
+                PauseItem::Achievements => pause.achievements = true,
+                // Losing a long run to one stray key press is worse than one more key
+                PauseItem::Quit if self.game.state != State::GameOver && !pause.confirm_quit => {
+                    pause.confirm_quit = true;
+                }
+                PauseItem::Quit => return Flow::Quit,
+            },
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    /// Shows a message in the status bar until it expires. Unlike
+    /// `Game::notify` it stays out of the event log.
+    fn notify(&mut self, msg: impl Into<String>) {
+        self.status = Some((msg.into(), self.ticks));
+        self.needs_redraw = true;
+    }
+
+    /// Advances time-based effects and the clock of the run by `elapsed`.
+    /// Only touches UI state and the time played, and returns whether
+    /// anything on screen changed.
+    fn on_tick(&mut self, elapsed: Duration) {
+        let second = self.game.played_ms / 1000;
+        if self.clock_running() {
+            self.game.played_ms += elapsed.as_millis() as u64;
+        }
+        let clock = self.timer && self.game.played_ms / 1000 != second;
+        let blink = self.blink_on();
+        self.ticks += 1;
+        let animating = !self.effects.is_empty();
+        for effect in &mut self.effects {
+            match effect.delay {
+                0 => effect.ticks -= 1,
+                _ => effect.delay -= 1,
+            }
+        }
+        self.effects.retain(|e| e.ticks > 0);
+        let expired = self
+            .status
+            .as_ref()
+            .is_some_and(|(_, shown)| self.ticks - shown >= STATUS_TICKS);
+        if expired {
+            self.status = None;
+        }
+        self.needs_redraw |= expired || animating || clock || blink != self.blink_on();
+    }
+
+    /// Whether the CPU of `--vs-ai` is on turn.
+    fn ai_turn(&self) -> bool {
+        self.game.ai.is_some() && self.game.turn == AI_SEAT && self.game.state != State::GameOver
+    }
+
+    /// Plays one move of the CPU the way a key would, so it follows the
+    /// same rules. It waits while the player has a menu or popup open.
+    fn step_ai(&mut self) -> Flow {
+        let Some(policy) = self.game.ai.filter(|_| self.ai_turn()) else {
+            return Flow::Unchanged;
+        };
+        let waiting = self.pause.is_some()
+            || self.slots.is_some()
+            || self.codex.is_some()
+            || self.editing
+            || self.details
+            || self.reserve
+            || self.breakdown
+            || self.summary
+            || self.typing_filter;
+        if waiting {
+            return Flow::Unchanged;
+        }
+        self.ai_steps += 1;
+        let action = match self.ai_steps > MAX_AI_STEPS {
+            true => Action::NextRound,
+            false => policy.policy()(&self.game),
+        };
+        tracing::trace!("The CPU plays {:?}", action);
+        self.ai_moving = true;
+        let flow = self.apply(action);
+        self.ai_moving = false;
+        flow
+    }
+
+    /// Plays the CPU's turn to its end at once, for scripts.
+    fn finish_ai_turn(&mut self) {
+        for _ in 0..=MAX_AI_STEPS {
+            if !self.ai_turn() {
+                break;
+            }
+            self.step_ai();
+        }
+    }
+
+    /// The clock stops in the pause menu, the slot menus and the codex, and
+    /// once the run is over.
+    fn clock_running(&self) -> bool {
+        self.pause.is_none()
+            && self.slots.is_none()
+            && self.codex.is_none()
+            && self.game.state != State::GameOver
+    }
+
+    /// Phase of the cursor blink and the pulse of plants about to mature.
+    fn blink_on(&self) -> bool {
+        (self.ticks / BLINK_TICKS).is_multiple_of(2)
+    }
+
+    /// Adds the finished run to the high score table and the history on
+    /// disk.
+    fn record_score(&mut self) {
+        // The tables are for solo runs, hot-seat games only show the winner
+        if self.game.players() > 1 {
+            self.high_scores = Some(HighScores {
+                entries: Vec::new(),
+                rank: None,
+            });
+            return;
+        }
+        let path = history::history_path();
+        let saved = History::load(&path).and_then(|mut history| {
+            history.push(GameSummary::from_game(&self.game));
+            history.save(&path)
+        });
+        if let Err(err) = saved {
+            self.game
+                .log(format!("Couldn't save the game history: {}", err));
+        }
+        let path = scores::scores_path();
+        let key = scores::board_key(&self.game.settings);
+        let result = ScoreTable::load(&path).and_then(|mut table| {
+            let rank = table.insert(key.clone(), ScoreEntry::from_game(&self.game));
+            table.save(&path)?;
+            Ok((table.entries(&key).to_vec(), rank))
+        });
+        match result {
+            Ok((entries, rank)) => self.high_scores = Some(HighScores { entries, rank }),
+            Err(err) => {
+                self.game.log(format!("Couldn't save high scores: {}", err));
+                self.high_scores = Some(HighScores {
+                    entries: Vec::new(),
+                    rank: None,
+                });
+            }
+        }
+    }
+
+    /// Writes the run for analysis and reports where it went.
+    fn export(&mut self, path: &Path) {
+        match export::write(path, &self.game) {
+            Ok(()) => self.notify(format!("Exported the run to {}", path.display())),
+            Err(err) => {
+                let msg = format!("Couldn't export the run: {}", err);
+                self.game.log(msg.clone());
+                self.notify(msg);
+            }
+        }
+    }
+
+    /// Highlights the card at hand position `index` in the Plants list.
+    /// Edits the Plants filter. Enter keeps it, Esc throws it away.
+    fn on_filter_key(&mut self, key: KeyCode) {
+        let mut filter = self.game.choosing.filter.clone();
+        match key {
+            KeyCode::Char(c) => filter.push(c),
+            KeyCode::Backspace => {
+                filter.pop();
+            }
+            KeyCode::Enter => self.typing_filter = false,
+            KeyCode::Esc => {
+                filter.clear();
+                self.typing_filter = false;
+            }
+            _ => return,
+        }
+        self.game.set_filter(filter);
+        self.select(self.game.choosing.index);
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        let visible = self.game.visible_hand();
+        let pos = index.and_then(|index| visible.iter().position(|&i| i == index));
+        self.list_state.select(pos);
+    }
+
+    /// Highlights the selected card again once the chooser has the focus.
+    fn show_selection(&mut self) {
+        if self.game.state == State::Choosing {
+            self.select(self.game.choosing.index);
+        }
+    }
+
+    fn unselect(&mut self) {
+        self.list_state.select(None);
+    }
+
+    /// Points the list highlight at the game's selection again after the
+    /// hand changed, unless the highlight is hidden while placing.
+    fn sync_selection(&mut self) {
+        if self.list_state.selected().is_some() {
+            self.select(self.game.choosing.index);
+        }
+    }
+
+    /// Keys of the plant editor. Changes go to the plant set right away,
+    /// plants already in play keep their numbers.
+    fn on_editor(&mut self, action: Action) {
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        match action {
+            Action::Editor => self.editing = false,
+            Action::CycleTheme => self.cycle_theme(),
+            Action::Up => editor.step_field(-1),
+            Action::Down => editor.step_field(1),
+            Action::Tab => editor.step_plant(1),
+            Action::BackTab => editor.step_plant(-1),
+            Action::Raise | Action::Lower => {
+                if let Some((name, field)) = editor.adjust(action == Action::Raise) {
+                    self.pack.plants = editor.plants.clone();
+                    if let Some(plant) = self.game.id_to_plant.iter_mut().find(|p| p.name == name) {
+                        field.adjust(plant, action == Action::Raise);
+                    }
+                }
+            }
+            Action::SavePlants => {
+                editor.msg = match editor.save() {
+                    Ok(path) => format!("Saved to {}", path.display()),
+                    Err(err) => err,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fn apply(&mut self, action: Action) -> Flow {
+        // The player's turn came in between, the CPU's next one counts anew
+        if !self.ai_moving {
+            self.ai_steps = 0;
+        }
+        if self.pause.is_some() {
+            return self.on_pause_menu(action);
+        }
+        if let Some(codex) = self.codex.as_mut() {
+            let len = self.game.id_to_plant.len();
+            let selected = codex.selected().unwrap_or(0);
+            match action {
+                Action::Codex => self.codex = None,
+                Action::CycleTheme => self.cycle_theme(),
+                Action::Up if len > 0 => codex.select(Some((selected + len - 1) % len)),
+                Action::Down if len > 0 => codex.select(Some((selected + 1) % len)),
+                _ => return Flow::Unchanged,
+            }
+            return Flow::Continue;
+        }
+        if self.editing {
+            self.on_editor(action);
+            return Flow::Continue;
+        }
+        if self.details {
+            match action {
+                Action::Details => self.details = false,
+                Action::CycleTheme => self.cycle_theme(),
+                _ => return Flow::Unchanged,
+            }
+            return Flow::Continue;
+        }
+        if self.reserve {
+            match action {
+                Action::Reserve => self.reserve = false,
+                Action::CycleTheme => self.cycle_theme(),
+                _ => return Flow::Unchanged,
+            }
+            return Flow::Continue;
+        }
+        if self.breakdown {
+            match action {
+                Action::ScoreBreakdown => self.breakdown = false,
+                Action::CycleTheme => self.cycle_theme(),
+                _ => return Flow::Unchanged,
+            }
+            return Flow::Continue;
+        }
+        if self.summary {
+            match action {
+                Action::Confirm | Action::Place => self.summary = false,
+                Action::CycleTheme => self.cycle_theme(),
+                _ => return Flow::Unchanged,
+            }
+            return Flow::Continue;
+        }
+
+        let confirming = std::mem::take(&mut self.confirm_end);
+        let overplanting = std::mem::take(&mut self.confirm_overplant);
+        let wasting = std::mem::take(&mut self.confirm_wasted);
+        let state = self.game.state;
+        let reports = self.game.round_reports.len();
+        let mark = engine::Mark::of(&self.game);
+        // Only the rules can turn an action down
+        let mut refused = false;
+        match action {
+            Action::Details => self.details = self.game.shown_card().is_some(),
+            Action::Reserve => self.reserve = true,
+            Action::ScoreBreakdown => self.breakdown = true,
+            Action::Codex => {
+                let mut codex = ListState::default();
+                codex.select(Some(0));
+                self.codex = Some(codex);
+            }
+            Action::Quit => return Flow::Quit,
+            Action::Editor => self.editing = self.editor.is_some(),
+            Action::Pause => {
+                let mut list = ListState::default();
+                list.select(Some(0));
+                self.pause = Some(PauseMenu {
+                    list,
+                    confirm_quit: false,
+                    achievements: false,
+                });
+            }
+            Action::CycleTheme => self.cycle_theme(),
+            Action::SkipTutorial => {
+                if self.tutorial.is_some() {
+                    self.end_tutorial("Tutorial skipped");
+                    return Flow::Continue;
+                }
+            }
+            Action::ToggleGrid => self.grid_labels = !self.grid_labels,
+            Action::SaveSlot => self.open_slots(MenuKind::Save),
+            Action::LoadSlot => self.open_slots(MenuKind::Load),
+            Action::ToggleRenderer => self.renderer = self.renderer.next(),
+            Action::WidenBoard => self.resize_board(true),
+            Action::NarrowBoard => self.resize_board(false),
+            Action::ReloadPlants => {
+                let res = self.pack.reload().and_then(|pack| {
+                    self.game.reload_plants(pack.plant_set()?)?;
+                    self.pack = pack;
+                    Ok(())
+                });
+                if let Err(err) = res {
+                    self.notify(err);
+                }
+            }
+            Action::Export => {
+                let path = self.export.clone().unwrap_or_else(export::default_path);
+                self.export(&path);
+            }
+            Action::Filter => {
+                if state == State::Choosing {
+                    self.typing_filter = true;
+                    self.select(self.game.choosing.index);
+                }
+            }
+            Action::Place
+                if state == State::NextRound && !confirming && self.game.can_still_place() =>
+            {
+                self.confirm_end = true;
+            }
+            Action::Place
+                if state == State::Placing
+                    && !overplanting
+                    && self.overplant_prompt().is_some() =>
+            {
+                self.confirm_overplant = true;
+            }
+            Action::Place
+                if state == State::Placing && !wasting && self.wasted_prompt().is_some() =>
+            {
+                self.confirm_wasted = true;
+            }
+            _ if self.ai_turn() && !self.ai_moving => {
+                self.notify("Wait for the CPU to end its turn");
+            }
+            _ => {
+                let applied = engine::play(&mut self.game, action);
+                refused = !applied;
+                match (state, action) {
+                    (_, Action::Tab | Action::BackTab) => self.show_selection(),
+                    (_, Action::Select(_)) if applied => self.select(self.game.choosing.index),
+                    (State::Choosing, Action::Place) => self.unselect(),
+                    (
+                        State::Choosing,
+                        Action::Down
+                        | Action::Up
+                        | Action::SortHand
+                        | Action::GroupHand
+                        | Action::ClearFilter
+                        | Action::QuickPlace,
+                    )
+                    | (State::Placing, Action::Place | Action::Delete) => {
+                        self.select(self.game.choosing.index)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        // Only right after the last move, so fresh rounds still get a look
+        let moved = matches!(state, State::Choosing | State::Placing);
+        if self.auto_advance && moved && self.game.out_of_moves() {
+            engine::play(&mut self.game, Action::NextRound);
+        }
+        let reported = self.game.round_reports.len() > reports
+            && self
+                .game
+                .round_reports
+                .last()
+                .is_some_and(|r| !r.is_empty());
+        if reported && self.summaries && self.game.state != State::GameOver {
+            self.summary = true;
+        }
+        let events = engine::events_since(&self.game, &mark);
+        if self.game.state != state {
+            tracing::debug!("{:?} -> {:?}", state, self.game.state);
+        }
+        trace::events(&events);
+        self.queue_effects();
+        let record = self.check_records();
+        let achieved = self.check_achievements(&events);
+        let news = [self.game.notice.take(), record, achieved]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<String>>();
+        // A refused key that said nothing, with no prompt it took down
+        let unchanged = refused
+            && !confirming
+            && !overplanting
+            && !wasting
+            && events.is_empty()
+            && news.is_empty();
+        let notice = (!news.is_empty()).then(|| news.join(". "));
+        if self.plain {
+            self.narrate(notice);
+        } else if let Some(msg) = notice {
+            self.notify(msg);
+        } else if let Some(prompt) = self.pending_prompt() {
+            self.notify(prompt);
+        }
+        self.check_tutorial(action, &events);
+        self.sync_selection();
+        if unchanged {
+            return Flow::Unchanged;
+        }
+        Flow::Continue
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Plant {
+    max_age: u32,
+    age: u32,
+    size_per_turn: u32,
+    size: u32,
+    points_per_size: f32,
+    class: char,
+    name: Cow<'static, str>,
+    /// The name in other languages by their code, like `{"de": "Gras"}`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    names: BTreeMap<String, String>,
+    short_display: char,
+    drops: DropTable,
+    #[serde(default)]
+    rarity: Rarity,
+    /// Player who placed it in a hot-seat game
+    #[serde(default)]
+    owner: usize,
+    /// Chance per round to creep onto an adjacent empty tile
+    #[serde(default)]
+    spread_chance: f32,
+    /// How `size_per_turn` is spread over its life
+    #[serde(default, skip_serializing_if = "GrowthCurve::is_linear")]
+    growth_curve: GrowthCurve,
+    /// Total score from which on drops may hand it out, 0 for from the start
+    #[serde(default)]
+    unlock_at_points: f32,
+    /// What it is good for, shown by the info panels
+    #[serde(default)]
+    description: Option<String>,
+    /// A line of color without any rules in it, for the codex
+    #[serde(default)]
+    flavor: Option<String>,
+    /// What it drops at maturity, rolled when it becomes permanent. Empty
+    /// if the harvest drops nothing.
+    #[serde(default)]
+    fate: Option<Vec<CardId>>,
+    /// Where the fate goes, from the drop it was rolled from
+    #[serde(default, skip_serializing_if = "DropTarget::is_hand")]
+    fate_target: DropTarget,
+    /// Rounds left in hand before it goes stale, from its first round end
+    /// there with `--freshness`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    freshness: Option<u32>,
+    /// Board and hand color instead of the theme's, a name or `#RRGGBB`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    /// Like `Grasses`, different families on the board at once earn a
+    /// collection bonus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    family: Option<String>,
+    /// Pack it comes from, empty for the built-in plants
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pack: String,
+    /// Assigned when the plant set is loaded
+    #[serde(default)]
+    id: PlantId,
+}
+
+/// Index of a plant in the loaded set. Names are only looked up while
+/// loading, everything after that goes through ids.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct PlantId(u32);
+
+impl PlantId {
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A plant or an item, as named by drops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum CardId {
+    Plant(PlantId),
+    Item(ItemId),
+}
+
+/// Anything that can be held in hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Card {
+    Plant(Box<Plant>),
+    Item(Item),
+}
+
+impl Card {
+    /// Same name and same base stats, so a grouped hand lists them as one.
+    fn stacks_with(&self, other: &Card) -> bool {
+        match (self, other) {
+            (Card::Plant(a), Card::Plant(b)) => {
+                a.name == b.name
+                    && a.max_age == b.max_age
+                    && a.size_per_turn == b.size_per_turn
+                    && a.points_per_size == b.points_per_size
+                    && a.growth_curve == b.growth_curve
+            }
+            (Card::Item(a), Card::Item(b)) => a.name == b.name,
+            _ => false,
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Card::Plant(plant) => &plant.name,
+            Card::Item(item) => &item.name,
+        }
+    }
+
+    /// The name in the chosen language, for the screens.
+    fn display_name(&self) -> &str {
+        match self {
+            Card::Plant(plant) => plant.display_name(),
+            Card::Item(item) => &item.name,
+        }
+    }
+
+    /// The plant's own color, items have none.
+    fn color(&self) -> Option<Color> {
+        match self {
+            Card::Plant(plant) => plant.color(),
+            Card::Item(_) => None,
+        }
+    }
+
+    fn rarity(&self) -> Rarity {
+        match self {
+            Card::Plant(plant) => plant.rarity,
+            Card::Item(item) => item.rarity,
+        }
+    }
+
+    fn freshness(&self) -> Option<u32> {
+        match self {
+            Card::Plant(plant) => plant.freshness,
+            Card::Item(item) => item.freshness,
+        }
+    }
+
+    fn freshness_mut(&mut self) -> &mut Option<u32> {
+        match self {
+            Card::Plant(plant) => &mut plant.freshness,
+            Card::Item(item) => &mut item.freshness,
+        }
+    }
+}
+
+/// How rare a plant is, colors its name wherever it is listed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+/// A hot-seat player's cards and score while someone else is on turn.
+#[derive(Serialize, Deserialize)]
+struct Seat {
+    hand: Vec<Card>,
+    #[serde(default)]
+    reserve: Vec<Card>,
+    ledger: ScoreLedger,
+    #[serde(default)]
+    compost: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Drop {
+    chance: f32,
+    /// Names as written in the plant file, of plants or items
+    plants: Vec<String>,
+    /// Only possible with a plant of this name on an orthogonal neighbor
+    /// tile when the plant matures
+    #[serde(default)]
+    requires_adjacent: Option<String>,
+    /// Where the plants go when the drop comes up
+    #[serde(default, skip_serializing_if = "DropTarget::is_hand")]
+    target: DropTarget,
+    /// `plants` resolved when the plant set is loaded
+    #[serde(default)]
+    ids: Vec<CardId>,
+}
+
+/// The drops of a plant with their chances already added up, so a roll
+/// doesn't have to. Saved and loaded as the plain list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Drop>", into = "Vec<Drop>")]
+struct DropTable {
+    drops: Vec<Drop>,
+    /// Probability of each drop or one before it, normalized to end at 1
+    cumulative: Vec<f32>,
+}
+
+impl DropTable {
+    /// Sets the chance of the drop at `index` and adds the chances up anew.
+    fn set_chance(&mut self, index: usize, chance: f32) {
+        let mut drops = std::mem::take(&mut self.drops);
+        if let Some(drop) = drops.get_mut(index) {
+            drop.chance = chance;
+        }
+        *self = DropTable::from(drops);
+    }
+
+    /// Rolls one drop with all of them open, weighted by their chances.
+    /// Always picks one as long as the chances add up to something positive.
+    fn roll(&self, rnd: f32) -> Option<&Drop> {
+        let last = self.iter().rposition(|d| d.chance > 0.0)?;
+        let at = self.cumulative.partition_point(|&c| c <= rnd);
+        self.drops.get(at.min(last))
+    }
+}
+
+impl From<Vec<Drop>> for DropTable {
+    fn from(drops: Vec<Drop>) -> DropTable {
+        let sum = drops.iter().map(|d| d.chance.max(0.0)).sum::<f32>();
+        let cumulative = drops
+            .iter()
+            .scan(0.0, |running, d| {
+                if sum > 0.0 {
+                    *running += d.chance.max(0.0) / sum;
+                }
+                Some(*running)
+            })
+            .collect();
+        DropTable { drops, cumulative }
+    }
+}
+
+impl From<DropTable> for Vec<Drop> {
+    fn from(table: DropTable) -> Vec<Drop> {
+        table.drops
+    }
+}
+
+impl FromIterator<Drop> for DropTable {
+    fn from_iter<I: IntoIterator<Item = Drop>>(drops: I) -> DropTable {
+        DropTable::from(drops.into_iter().collect::<Vec<Drop>>())
+    }
+}
+
+impl Deref for DropTable {
+    type Target = [Drop];
+
+    fn deref(&self) -> &[Drop] {
+        &self.drops
+    }
+}
+
+/// Where the cards of a drop end up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DropTarget {
+    #[default]
+    Hand,
+    /// Planted as new on random free neighbor tiles of the harvest. Items,
+    /// and plants that find no room, still go to the hand.
+    AdjacentTile,
+}
+
+impl DropTarget {
+    fn is_hand(&self) -> bool {
+        *self == DropTarget::Hand
+    }
+}
+
+/// A crossing as the plant files write it, by name, like
+/// `{"parents": ["Grass", "Shrub"], "child": "Fern", "chance": 0.3}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct HybridRule {
+    parents: [String; 2],
+    child: String,
+    chance: f32,
+}
+
+/// Two parent plants that, growing side by side, may drop `child` when
+/// either of them matures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Hybrid {
+    parents: [PlantId; 2],
+    child: PlantId,
+    chance: f32,
+}
+
+impl Hybrid {
+    /// The other parent when `id` is one of them.
+    fn partner(&self, id: PlantId) -> Option<PlantId> {
+        match self.parents {
+            [a, b] if a == id => Some(b),
+            [a, b] if b == id => Some(a),
+            _ => None,
+        }
+    }
+
+    fn involves(&self, id: PlantId) -> bool {
+        self.child == id || self.partner(id).is_some()
+    }
+}
+
+impl Plant {
+    /// The name in the chosen language, for the screens.
+    fn display_name(&self) -> &str {
+        lang::name(&self.names, &self.name)
+    }
+
+    /// Its own color from the plant file, checked when the set is loaded.
+    fn color(&self) -> Option<Color> {
+        self.color.as_deref().and_then(theme::parse_color)
+    }
+
+    /// The plant with its age, `w: 1/2`.
+    fn full(&self) -> String {
+        format!("{}: {}/{}", self.short_display, self.age, self.max_age)
+    }
+
+    /// The tile text when there is room, `w 1` with the rounds left until
+    /// the harvest.
+    fn countdown(&self) -> String {
+        format!(
+            "{} {}",
+            self.short_display,
+            self.max_age.saturating_sub(self.age)
+        )
+    }
+
+    /// The tile text on cramped boards.
+    fn compact(&self) -> char {
+        self.short_display
+    }
+
+    /// The chance of each entry in `drops`, normalized over the `open` ones
+    /// to sum up to 1. Closed drops get 0, and everything is zero if no open
+    /// drop has a positive weight.
+    fn drop_probabilities(&self, open: &[bool]) -> Vec<f32> {
+        let weights = self
+            .drops
+            .iter()
+            .zip(open)
+            .map(|(d, &open)| if open { d.chance.max(0.0) } else { 0.0 })
+            .collect::<Vec<f32>>();
+        let sum = weights.iter().sum::<f32>();
+        weights
+            .iter()
+            .map(|w| if sum > 0.0 { w / sum } else { 0.0 })
+            .collect()
+    }
+}
+
+impl Display for Plant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.full())
+    }
+}
+
+/// Where `F5` reloads the built-in plants from.
+const PLANTS_PATH: &str = "assets/packs/forest/plants.json";
+
+/// A checked list of plants, every drop names a plant of the set. The
+/// position of a plant is its id.
+pub struct PlantSet {
+    plants: Vec<Plant>,
+    name_to_id: HashMap<String, PlantId>,
+    /// Items drops may name besides plants
+    items: Vec<Item>,
+    name_to_item: HashMap<String, ItemId>,
+    /// What validation found odd without refusing the set
+    warnings: Vec<String>,
+    /// The starting hand the set was checked against
+    hand: Vec<String>,
+    /// Packs the plants come from, empty for the built-in plants
+    packs: Vec<String>,
+    /// Checked against the plants, resolved by `hybrids`
+    hybrid_rules: Vec<HybridRule>,
+}
+
+/// The plants the game comes with, the same as the forest pack.
+fn builtin_plants() -> Vec<Plant> {
+    vec![
+        Plant {
+            max_age: 2,
+            age: 0,
+            size_per_turn: 1,
+            size: 0,
+            points_per_size: 1.0,
+            class: 's',
+            name: Cow::Borrowed("Grass"),
+            names: BTreeMap::from([("de".into(), "Gras".into())]),
+            short_display: 'w',
+            drops: DropTable::from(vec![
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Grass".into(), "Grass".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Grass".into(), "Tall Grass".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Tall Grass".into(), "Tall Grass".into()],
+                    requires_adjacent: Some("Tall Grass".into()),
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+            ]),
+            rarity: Rarity::Common,
+            owner: 0,
+            spread_chance: 0.2,
+            growth_curve: GrowthCurve::Linear,
+            unlock_at_points: 0.0,
+            description: Some("Grows fast and spreads onto empty neighbor tiles. Harvests drop more Grass, sometimes Tall Grass.".into()),
+            flavor: Some("Nobody plants grass. It just shows up.".into()),
+            fate: None,
+            fate_target: DropTarget::Hand,
+            freshness: None,
+            color: None,
+            family: Some("Grasses".into()),
+            pack: String::new(),
+            id: PlantId::default(),
+        },
+        Plant {
+            max_age: 4,
+            age: 0,
+            size_per_turn: 1,
+            size: 0,
+            points_per_size: 1.0,
+            class: 's',
+            name: Cow::Borrowed("Tall Grass"),
+            names: BTreeMap::from([("de".into(), "Hohes Gras".into())]),
+            short_display: 'W',
+            drops: DropTable::from(vec![
+                Drop {
+                    chance: 5.0,
+                    plants: vec!["Tall Grass".into(), "Tall Grass".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Tall Grass".into(), "Shrub".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+            ]),
+            rarity: Rarity::Common,
+            owner: 0,
+            spread_chance: 0.0,
+            growth_curve: GrowthCurve::Linear,
+            unlock_at_points: 0.0,
+            description: Some("Takes twice as long as Grass and never spreads, but mostly drops more of itself and now and then a Shrub.".into()),
+            flavor: Some("Knee-high and proud of it.".into()),
+            fate: None,
+            fate_target: DropTarget::Hand,
+            freshness: None,
+            color: None,
+            family: Some("Grasses".into()),
+            pack: String::new(),
+            id: PlantId::default(),
+        },
+        Plant {
+            max_age: 7,
+            age: 0,
+            size_per_turn: 1,
+            size: 0,
+            points_per_size: 1.0,
+            class: 'S',
+            name: Cow::Borrowed("Shrub"),
+            names: BTreeMap::from([("de".into(), "Strauch".into())]),
+            short_display: 'Y',
+            drops: DropTable::from(vec![
+                Drop {
+                    chance: 5.0,
+                    plants: vec!["Shrub".into(), "Shrub".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+                Drop {
+                    chance: 2.0,
+                    plants: vec!["Shrub".into(), "Fertilizer".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+            ]),
+            rarity: Rarity::Common,
+            owner: 0,
+            spread_chance: 0.0,
+            growth_curve: GrowthCurve::Linear,
+            unlock_at_points: 0.0,
+            description: Some("Slow and steady, seven rounds to mature. Often drops Fertilizer, and next to Grass it may cross into a Fern.".into()),
+            flavor: Some("The backbone of any forest worth the name.".into()),
+            fate: None,
+            fate_target: DropTarget::Hand,
+            freshness: None,
+            color: None,
+            family: Some("Shrubs".into()),
+            pack: String::new(),
+            id: PlantId::default(),
+        },
+        Plant {
+            max_age: 5,
+            age: 0,
+            size_per_turn: 1,
+            size: 0,
+            points_per_size: 2.0,
+            class: 'S',
+            name: Cow::Borrowed("Fern"),
+            names: BTreeMap::from([("de".into(), "Farn".into())]),
+            short_display: 'F',
+            drops: DropTable::from(vec![
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Fern".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Scythe".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+                Drop {
+                    chance: 1.0,
+                    plants: vec!["Tonic".into()],
+                    requires_adjacent: None,
+                    target: DropTarget::Hand,
+                    ids: Vec::new(),
+                },
+            ]),
+            rarity: Rarity::Uncommon,
+            owner: 0,
+            spread_chance: 0.0,
+            growth_curve: GrowthCurve::Linear,
+            unlock_at_points: 20.0,
+            description: None,
+            flavor: None,
+            fate: None,
+            fate_target: DropTarget::Hand,
+            freshness: None,
+            color: None,
+            family: Some("Ferns".into()),
+            pack: String::new(),
+            id: PlantId::default(),
+        },
+    ]
+}
+
+/// The hybrids of the built-in plants, the same as the forest pack's.
+fn builtin_hybrids() -> Vec<HybridRule> {
+    vec![HybridRule {
+        parents: ["Grass".into(), "Shrub".into()],
+        child: "Fern".into(),
+        chance: 0.3,
+    }]
+}
+
+impl PlantSet {
+    /// Validates the set against the starting `hand`, then assigns the ids
+    /// and resolves every drop.
+    fn new(
+        mut plants: Vec<Plant>,
+        hybrids: Vec<HybridRule>,
+        items: Vec<Item>,
+        hand: &[String],
+    ) -> Result<PlantSet, String> {
+        let report = validate::check(&plants, &hybrids, &items, hand);
+        if !report.errors.is_empty() {
+            return Err(report.errors.join("; "));
+        }
+        let name_to_id = plants
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.name.to_string(), PlantId(i as u32)))
+            .collect::<HashMap<String, PlantId>>();
+        let name_to_item = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (item.name.clone(), ItemId(i as u32)))
+            .collect::<HashMap<String, ItemId>>();
+        let mut set = PlantSet {
+            plants: Vec::new(),
+            name_to_id,
+            items,
+            name_to_item,
+            warnings: report.warnings,
+            hand: hand.to_vec(),
+            packs: Vec::new(),
+            hybrid_rules: hybrids,
+        };
+        for (i, plant) in plants.iter_mut().enumerate() {
+            plant.id = PlantId(i as u32);
+            set.resolve_drops(plant);
+        }
+        set.plants = plants;
+        Ok(set)
+    }
+
+    fn id(&self, name: &str) -> Option<PlantId> {
+        self.name_to_id.get(name).copied()
+    }
+
+    /// The plant of a name that is known to be in the set.
+    fn plant(&self, name: &str) -> &Plant {
+        &self.plants[self.name_to_id[name].index()]
+    }
+
+    fn card_id(&self, name: &str) -> Option<CardId> {
+        self.id(name)
+            .map(CardId::Plant)
+            .or_else(|| self.name_to_item.get(name).copied().map(CardId::Item))
+    }
+
+    /// The card of a name that is known to be in the set.
+    fn card(&self, name: &str) -> Card {
+        match self.card_id(name) {
+            Some(CardId::Item(id)) => Card::Item(self.items[id.index()].clone()),
+            _ => Card::Plant(Box::new(self.plant(name).clone())),
+        }
+    }
+
+    /// Fills in the drop ids of `plant`. Names that aren't in the set are
+    /// left out of the drop.
+    fn resolve_drops(&self, plant: &mut Plant) {
+        plant.drops = std::mem::take(&mut plant.drops.drops)
+            .into_iter()
+            .map(|drop| Drop {
+                ids: drop
+                    .plants
+                    .iter()
+                    .filter_map(|name| self.card_id(name))
+                    .collect(),
+                ..drop
+            })
+            .collect();
+    }
+
+    /// Resolves the hybrid rules and checks the plants the objectives name.
+    fn hybrids(&self, objectives: &[Objective]) -> Result<Vec<Hybrid>, String> {
+        let mut unknown = Vec::new();
+        let mut lookup = |name: &str, what: &str| {
+            let id = self.id(name);
+            if id.is_none() {
+                unknown.push(format!("Expected Plant <{}> of {} to exist", name, what));
+            }
+            id.unwrap_or_default()
+        };
+        let hybrids = self
+            .hybrid_rules
+            .iter()
+            .map(|rule| Hybrid {
+                parents: rule.parents.clone().map(|name| lookup(&name, "a hybrid")),
+                child: lookup(&rule.child, "a hybrid"),
+                chance: rule.chance,
+            })
+            .collect();
+        for objective in objectives {
+            if let Goal::Harvest { plant, .. } = &objective.goal {
+                lookup(plant, "an objective");
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(unknown.join("; "));
+        }
+        Ok(hybrids)
+    }
+}
+
+/// What a plant file holds: a list of plants, or an object with the
+/// `plants` and the `hybrids` they cross into.
+#[derive(Debug, Clone, Default)]
+struct PlantFile {
+    plants: Vec<Plant>,
+    hybrids: Vec<HybridRule>,
+}
+
+/// Reads a plant file without checking how the plants fit together.
+fn read_plants(path: &Path) -> Result<PlantFile, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let (plants, hybrids) = match value {
+        serde_json::Value::Object(mut file) => (
+            file.remove("plants").unwrap_or_default(),
+            file.remove("hybrids"),
+        ),
+        plants => (plants, None),
+    };
+    let plants: Vec<serde_json::Value> =
+        serde_json::from_value(plants).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let hybrids = match hybrids {
+        Some(hybrids) => serde_json::from_value(hybrids)
+            .map_err(|err| format!("{}: invalid hybrids: {}", path.display(), err))?,
+        None => Vec::new(),
+    };
+
+    // Parse one by one so a bad field, like an unknown rarity, names its plant
+    let plants = plants
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let name = value["name"].as_str().unwrap_or("?").to_string();
+            serde_json::from_value(value)
+                .map_err(|err| format!("Invalid Plant <{}> (entry {}): {}", name, i, err))
+        })
+        .collect::<Result<Vec<Plant>, String>>()?;
+    Ok(PlantFile { plants, hybrids })
+}
+
+#[derive(Serialize, Deserialize)]
+enum Tile {
+    Empty,
+    New(PlantedPlant),
+    Permanent(PlantedPlant),
+    /// A permanent plant under pest attack, with the rounds it has been infested
+    Infested(PlantedPlant, u32),
+}
+
+impl Tile {
+    fn plant(&self) -> Option<&Plant> {
+        self.planted().map(|p| &p.plant)
+    }
+
+    fn planted(&self) -> Option<&PlantedPlant> {
+        match self {
+            Tile::Empty => None,
+            Tile::New(p) | Tile::Permanent(p) | Tile::Infested(p, _) => Some(p),
+        }
+    }
+}
+
+/// A plant on the board with the status effects it is under. Saves from
+/// before effects read as plants without any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PlantedPlant {
+    #[serde(flatten)]
+    plant: Plant,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    effects: Vec<StatusEffect>,
+}
+
+impl PlantedPlant {
+    fn new(plant: Plant) -> PlantedPlant {
+        PlantedPlant {
+            plant,
+            effects: Vec::new(),
+        }
+    }
+
+    fn stats(&self) -> Stats {
+        effects::effective(&self.plant, &self.effects)
+    }
+}
+
+impl Deref for PlantedPlant {
+    type Target = Plant;
+
+    fn deref(&self) -> &Plant {
+        &self.plant
+    }
+}
+
+impl DerefMut for PlantedPlant {
+    fn deref_mut(&mut self) -> &mut Plant {
+        &mut self.plant
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text(TileDetail::Full))
+    }
+}
+
+/// Why a plant can't go on a tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlacementError {
+    /// The other half of a board split with `--vs-ai`
+    OtherSide,
+    Occupied,
+    /// Nothing grows on it, like water
+    Terrain(Terrain),
+}
+
+impl Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementError::OtherSide => f.write_str("The other player's half of the board"),
+            PlacementError::Occupied => f.write_str("Tile occupied"),
+            PlacementError::Terrain(terrain) => write!(f, "Can't plant on {}", terrain),
+        }
+    }
+}
+
+/// How much of a tile's text fits into its share of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TileDetail {
+    /// `w 1`, the plant with the rounds it has left
+    Full,
+    /// Just the plant's `short_display`
+    Compact,
+    /// A colored block, for boards that barely fit
+    Block,
+}
+
+impl TileDetail {
+    /// The most detail that fits into a tile `cols` terminal cells wide, for
+    /// a plant text of `len` characters.
+    fn fitting(cols: f64, len: usize) -> TileDetail {
+        // The text starts a quarter into the tile rectangle
+        let budget = (cols * 0.675).floor() as usize;
+        if len <= budget {
+            TileDetail::Full
+        } else if cols >= 2.0 {
+            TileDetail::Compact
+        } else {
+            TileDetail::Block
+        }
+    }
+}
+
+impl Tile {
+    fn text(&self, detail: TileDetail) -> String {
+        match (self.plant(), detail) {
+            (None, _) => " ".to_string(),
+            (Some(p), TileDetail::Full) => p.countdown(),
+            (Some(p), TileDetail::Compact) => p.compact().to_string(),
+            (Some(_), TileDetail::Block) => "█".to_string(),
+        }
+    }
+}
+
+/// Maps the number keys to hand slots, `1` is the first card and `0` the tenth.
+fn digit_slot(c: char) -> Option<usize> {
+    match c {
+        '1'..='9' => c.to_digit(10).map(|d| d as usize - 1),
+        '0' => Some(9),
+        _ => None,
+    }
+}
+
+/// The number key shown in front of a hand slot, blank past the tenth card.
+fn slot_label(slot: usize) -> char {
+    match slot {
+        0..=8 => char::from_digit(slot as u32 + 1, 10).unwrap(),
+        9 => '0',
+        _ => ' ',
+    }
+}
+
+/// Maps a key to the action it triggers in the current state.
+fn key_action(app: &App, key: KeyCode) -> Option<Action> {
+    if app.pause.is_some() {
+        return match key {
+            KeyCode::Esc | KeyCode::Char('p') => Some(Action::Pause),
+            KeyCode::Up => Some(Action::Up),
+            KeyCode::Down => Some(Action::Down),
+            KeyCode::Enter | KeyCode::Char(' ') => Some(Action::Confirm),
+            _ => None,
+        };
+    }
+    if app.codex.is_some() {
+        return match key {
+            KeyCode::Esc | KeyCode::F(1) | KeyCode::Char('e') => Some(Action::Codex),
+            KeyCode::F(2) => Some(Action::CycleTheme),
+            KeyCode::Up => Some(Action::Up),
+            KeyCode::Down => Some(Action::Down),
+            _ => None,
+        };
+    }
+    if app.editing {
+        return match key {
+            KeyCode::Esc | KeyCode::F(6) => Some(Action::Editor),
+            KeyCode::F(2) => Some(Action::CycleTheme),
+            KeyCode::Up => Some(Action::Up),
+            KeyCode::Down => Some(Action::Down),
+            KeyCode::Tab => Some(Action::Tab),
+            KeyCode::BackTab => Some(Action::BackTab),
+            KeyCode::Right | KeyCode::Char('+' | '=') => Some(Action::Raise),
+            KeyCode::Left | KeyCode::Char('-') => Some(Action::Lower),
+            KeyCode::Char('s') => Some(Action::SavePlants),
+            _ => None,
+        };
+    }
+    if app.details {
+        return match key {
+            KeyCode::Esc | KeyCode::Char('m') => Some(Action::Details),
+            KeyCode::F(2) => Some(Action::CycleTheme),
+            _ => None,
+        };
+    }
+    if app.reserve {
+        return match key {
+            KeyCode::Esc | KeyCode::Char('r') => Some(Action::Reserve),
+            KeyCode::F(2) => Some(Action::CycleTheme),
+            _ => None,
+        };
+    }
+    if app.breakdown {
+        return match key {
+            KeyCode::Esc | KeyCode::Char('b') => Some(Action::ScoreBreakdown),
+            KeyCode::F(2) => Some(Action::CycleTheme),
+            _ => None,
+        };
+    }
+    if app.summary {
+        return match key {
+            KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Esc => Some(Action::Confirm),
+            KeyCode::F(2) => Some(Action::CycleTheme),
+            _ => None,
+        };
+    }
+
+    let action = match (&app.game.state, key) {
+        // Esc drops the filter first, it only pauses once nothing is filtered
+        (State::Choosing, KeyCode::Esc) if !app.game.choosing.filter.is_empty() => {
+            Action::ClearFilter
+        }
+        (_, KeyCode::Esc) if app.tutorial.is_some() => Action::SkipTutorial,
+        (State::Drafting, KeyCode::Esc) => Action::SkipDraft,
+        (_, KeyCode::Esc | KeyCode::Char('p')) => Action::Pause,
+        (_, KeyCode::F(1) | KeyCode::Char('e')) => Action::Codex,
+        (_, KeyCode::Char('m')) => Action::Details,
+        (_, KeyCode::Char('r')) => Action::Reserve,
+        (_, KeyCode::Char('b')) => Action::ScoreBreakdown,
+        (_, KeyCode::F(2)) => Action::CycleTheme,
+        (_, KeyCode::F(3)) => Action::ToggleRenderer,
+        (_, KeyCode::Char('g')) => Action::ToggleGrid,
+        (_, KeyCode::Char('S')) => Action::SaveSlot,
+        (_, KeyCode::Char('L')) => Action::LoadSlot,
+        (_, KeyCode::Char('>')) => Action::WidenBoard,
+        (_, KeyCode::Char('<')) => Action::NarrowBoard,
+        (_, KeyCode::F(5)) => Action::ReloadPlants,
+        (_, KeyCode::F(6)) if app.editor.is_some() => Action::Editor,
+        (_, KeyCode::Tab) => Action::Tab,
+        (_, KeyCode::BackTab) => Action::BackTab,
+        (State::Choosing, KeyCode::Enter) => Action::QuickPlace,
+        (State::Drafting, KeyCode::Enter) => Action::Place,
+        (_, KeyCode::Enter) => Action::NextRound,
+        (_, KeyCode::Char(' ')) => Action::Place,
+        (State::Choosing | State::Drafting, KeyCode::Up) => Action::Up,
+        (State::Choosing | State::Drafting, KeyCode::Down) => Action::Down,
+        (State::Choosing | State::Placing, KeyCode::Char('c')) => Action::Compost,
+        (State::Choosing, KeyCode::Char('o')) => Action::SortHand,
+        (State::Choosing, KeyCode::Char('G')) => Action::GroupHand,
+        (State::Choosing, KeyCode::Char('/')) => Action::Filter,
+        (State::Placing, KeyCode::Char('q')) => Action::Delete,
+        (State::Placing, KeyCode::Char('x')) => Action::ClearPest,
+        (State::Placing | State::Inspect, KeyCode::Char('h')) => Action::Harvest,
+        (State::Placing, KeyCode::Char('f')) => Action::Water,
+        (State::GameOver, KeyCode::Char('i')) => return None,
+        (State::GameOver, KeyCode::Char('x')) => Action::Export,
+        (_, KeyCode::Char('i')) => Action::Inspect,
+        (State::Placing | State::Inspect, KeyCode::Up | KeyCode::Char('w')) => Action::Up,
+        (State::Placing | State::Inspect, KeyCode::Down | KeyCode::Char('s')) => Action::Down,
+        (State::Placing | State::Inspect, KeyCode::Right | KeyCode::Char('d')) => Action::Right,
+        (State::Placing | State::Inspect, KeyCode::Left | KeyCode::Char('a')) => Action::Left,
+        (State::Placing | State::Inspect, KeyCode::PageUp) => Action::TopEdge,
+        (State::Placing | State::Inspect, KeyCode::PageDown) => Action::BottomEdge,
+        (State::Placing | State::Inspect, KeyCode::Home) => Action::LeftEdge,
+        (State::Placing | State::Inspect, KeyCode::End) => Action::RightEdge,
+        (State::Choosing | State::Placing | State::Drafting, KeyCode::Char(c)) => {
+            Action::Select(digit_slot(c)?)
+        }
+        _ => return None,
+    };
+    Some(action)
+}
+
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app: App,
+    mut publisher: Option<&mut Publisher>,
+    mut autosave: Option<&mut Autosave>,
+    script: Vec<Action>,
+    tick_ms: u64,
+) -> io::Result<()> {
+    let mut script = script.into_iter();
+    let tick_rate = Duration::from_millis(tick_ms);
+    let mut last_tick = Instant::now();
+    let mut last_draw = Instant::now();
+    loop {
+        // Keys that came in together are all played before the next draw,
+        // so a held arrow key doesn't queue up a frame for every step
+        let burst = script.len() == 0
+            && last_draw.elapsed() < MAX_FRAME_GAP
+            && event::poll(Duration::ZERO)?;
+        if (app.needs_redraw && !burst) || last_draw.elapsed() >= HEARTBEAT {
+            last_draw = Instant::now();
+            if let Some(fps) = app.fps.as_mut() {
+                fps.record(last_draw);
+            }
+            terminal.draw(|f| {
+                ui(f, &mut app);
+                if let Some(fps) = &app.fps {
+                    draw_fps(f, fps, &app.theme);
+                }
+            })?;
+            if let Some(publisher) = publisher.as_mut() {
+                publisher.publish(&app.game, Instant::now())?;
+            }
+            app.needs_redraw = false;
+        } else if let Some(publisher) = publisher.as_mut() {
+            // A throttled state goes out at the latest one tick late
+            publisher.flush(Instant::now())?;
+        }
+
+        // The CPU doesn't keep a script waiting
+        if script.len() > 0 {
+            app.finish_ai_turn();
+        }
+        // A script plays first, then the keyboard takes over.
+        let action = match script.next() {
+            Some(action) => Some(action),
+            None if event::poll(tick_rate.saturating_sub(last_tick.elapsed()))? => {
+                match event::read()? {
+                    // The filter takes every key while it is typed, not
+                    // even Esc may reach the pause menu
+                    Event::Key(key) if app.typing_filter => {
+                        app.on_filter_key(key.code);
+                        app.needs_redraw = true;
+                        None
+                    }
+                    // So do the slot menus, for the names typed into them
+                    Event::Key(key) if app.slots.is_some() => {
+                        app.on_slot_key(key.code);
+                        app.needs_redraw = true;
+                        None
+                    }
+                    Event::Key(key) => {
+                        let action = key_action(&app, key.code);
+                        tracing::trace!("Key {:?} plays {:?}", key.code, action);
+                        if action.is_none() {
+                            app.confirm_end = false;
+                            app.confirm_overplant = false;
+                            app.confirm_wasted = false;
+                            app.notify(format!("{:?} does nothing here", key.code));
+                        }
+                        action
+                    }
+                    Event::Resize(..) => {
+                        terminal.autoresize()?;
+                        app.needs_redraw = true;
+                        None
+                    }
+                    Event::Mouse(MouseEvent {
+                        kind: MouseEventKind::Down(MouseButton::Left),
+                        column,
+                        row,
+                        ..
+                    }) => app
+                        .board_geometry
+                        .and_then(|geometry| geometry.tile_at(column, row))
+                        .map(|(x, y)| Action::MoveTo(x, y)),
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+        if let Some(action) = action {
+            match app.apply(action) {
+                Flow::Quit => return Ok(()),
+                Flow::Continue => app.needs_redraw = true,
+                Flow::Unchanged => {}
+            }
+        }
+        if last_tick.elapsed() >= tick_rate {
+            app.on_tick(last_tick.elapsed());
+            // A move a tick, slow enough to follow the CPU's cursor
+            if app.step_ai() != Flow::Unchanged {
+                app.needs_redraw = true;
+            }
+            last_tick = Instant::now();
+        }
+        if let Some(autosave) = autosave.as_mut() {
+            autosave.update(&app.game)?;
+        }
+        if app.game.state == State::GameOver && app.high_scores.is_none() {
+            app.record_score();
+            app.bank_seeds();
+            if let Some(path) = app.export.clone() {
+                app.export(&path);
+            }
+        }
+    }
+}
+
+/// Plays a script without a terminal and prints the final state as JSON.
+fn run_headless(mut app: App, script: Vec<Action>) -> io::Result<()> {
+    for action in script {
+        if app.apply(action) == Flow::Quit {
+            break;
+        }
+        app.finish_ai_turn();
+    }
+    if let Some(path) = &app.export {
+        export::write(path, &app.game)?;
+    }
+    println!("{}", save::to_json(&app.game)?);
+    Ok(())
+}
+
+/// Range and step of the board's share of the width, in percent.
+const MIN_BOARD_SPLIT: u16 = 50;
+const MAX_BOARD_SPLIT: u16 = 85;
+const SPLIT_STEP: u16 = 5;
+
+/// Columns the side panel keeps on narrow terminals, as long as the board
+/// still gets half of them.
+const SIDE_MIN_WIDTH: u16 = 34;
+
+/// The board's share of a terminal `width` columns wide: `split`, or less
+/// where the side panel would get fewer than `SIDE_MIN_WIDTH` columns.
+fn board_percent(split: u16, width: u16) -> u16 {
+    let side = (SIDE_MIN_WIDTH * 100).div_ceil(width.max(1));
+    split
+        .min(100u16.saturating_sub(side))
+        .clamp(MIN_BOARD_SPLIT, MAX_BOARD_SPLIT)
+}
+
+/// Smallest terminal the game still lays out in.
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
+
+fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Stands in for the whole UI until the terminal is big enough again.
+fn draw_too_small(f: &mut Frame, theme: &Theme, area: Rect) {
+    let msg = tr_with("too_small", &[&MIN_WIDTH, &MIN_HEIGHT]);
+    let middle = Rect {
+        y: area.y + area.height / 2,
+        height: area.height - area.height / 2,
+        ..area
+    };
+    let paragraph = Paragraph::new(msg)
+        .style(Style::default().fg(theme.active))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, middle);
+}
+
+fn ui(f: &mut Frame, app: &mut App) {
+    if too_small(f.area()) {
+        draw_too_small(f, &app.theme, f.area());
+        return;
+    }
+    if app.codex.is_some() {
+        draw_codex(f, app, f.area());
+        return;
+    }
+    if let Some(editor) = app.editor.as_mut().filter(|_| app.editing) {
+        editor::draw(f, editor, &app.theme, f.area());
+        return;
+    }
+    if app.details {
+        draw_details(f, app, f.area());
+        return;
+    }
+
+    let banner = match app.tutorial {
+        Some(_) => TUTORIAL_BANNER_HEIGHT,
+        None => 0,
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(banner),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
+    if let Some(tutorial) = &app.tutorial {
+        draw_tutorial(f, tutorial, &app.theme, rows[0]);
+    }
+    let board = board_percent(app.board_split, f.area().width);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(board),
+                Constraint::Percentage(100 - board),
+            ]
+            .as_ref(),
+        )
+        .margin(1)
+        .split(rows[1]);
+    let board_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(chunks[0]);
+    let board_area = board_rows[0];
+    draw_board_summary(f, &app.game, &app.theme, board_rows[1]);
+
+    let overlay = Overlay {
+        blink: app.blink_on(),
+        labels: app.grid_labels,
+        effects: &app.effects,
+    };
+    app.board_geometry = None;
+    match app.renderer {
+        _ if app.plain => draw_plain_board(f, &app.game, &app.theme, board_area),
+        Renderer::Canvas => {
+            app.board_geometry = Some(draw_game_board(
+                f,
+                &app.game,
+                &Viewport::whole(app.game.width(), app.game.height()),
+                &app.theme,
+                overlay,
+                board_area,
+            ))
+        }
+        Renderer::Ascii => {
+            let model = BoardViewModel::new(&app.game, &app.theme, overlay.blink);
+            let board = AsciiBoard {
+                model: &model,
+                theme: &app.theme,
+                labels: overlay.labels,
+                effects: overlay.effects,
+            };
+            f.render_widget(board, board_area);
+        }
+    }
+    draw_side(f, app, chunks[1]);
+    draw_status_bar(f, app, rows[2]);
+
+    if app.game.state == State::GameOver {
+        draw_game_over(f, app, f.area());
+    }
+    if app.game.state == State::Drafting {
+        draw_draft(f, app, f.area());
+    }
+    if app.summary {
+        draw_summary(f, app, f.area());
+    }
+    if app.reserve {
+        draw_reserve(f, app, f.area());
+    }
+    if app.breakdown {
+        draw_breakdown(f, app, f.area());
+    }
+    if app.pause.is_some() {
+        draw_pause(f, app, f.area());
+    }
+    if let Some(menu) = app.slots.as_mut() {
+        slots::draw(f, menu, &app.pack.names, &app.theme, f.area());
+    }
+}
+
+/// `--debug-fps`: the draws per second in the top right corner, over
+/// whatever else is on screen.
+fn draw_fps(f: &mut Frame, fps: &FpsMeter, theme: &Theme) {
+    let text = format!(" {} draws/s ", fps.per_second());
+    let size = f.area();
+    let width = (text.len() as u16).min(size.width);
+    let area = Rect::new(
+        size.x + size.width - width,
+        size.y,
+        width,
+        1.min(size.height),
+    );
+    let style = Style::default()
+        .fg(theme.label)
+        .add_modifier(Modifier::REVERSED);
+    f.render_widget(Paragraph::new(Span::styled(text, style)), area);
+}
+
+/// The reserve in the order it refills the hand.
+fn draw_reserve(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let mut items = app
+        .game
+        .reserve
+        .iter()
+        .enumerate()
+        .map(|(i, card)| {
+            ListItem::new(format!("{:>3}. {}", i + 1, card.name()))
+                .style(theme.card(card.rarity(), card.color()))
+        })
+        .collect::<Vec<ListItem>>();
+    if items.is_empty() {
+        let hint = match mutators::hand_limit(&app.game.mutators) {
+            Some(_) => tr("reserve.empty_limit"),
+            None => tr("reserve.empty_unlimited"),
+        };
+        items.push(ListItem::new(hint).style(Style::default().fg(theme.label)));
+    }
+    let title = match app.game.reserve.len() {
+        1 => format!(" {} // {} ", tr("reserve.title"), tr("reserve.one_card")),
+        cards => format!(
+            " {} // {} ",
+            tr("reserve.title"),
+            tr_with("reserve.cards", &[&cards])
+        ),
+    };
+    let list = List::new(items).block(theme.panel(title, true));
+    let area = centered_rect(50, 60, area);
+    f.render_widget(Clear, area);
+    f.render_widget(list, area);
+}
+
+/// The rewards of a running draft, the selected one highlighted.
+fn draw_draft(f: &mut Frame, app: &App, area: Rect) {
+    let Some(draft) = &app.game.draft else {
+        return;
+    };
+    let theme = &app.theme;
+    let items = draft
+        .rewards
+        .iter()
+        .enumerate()
+        .map(|(i, reward)| {
+            let item = ListItem::new(format!("{}. {}", i + 1, reward.label()));
+            match reward {
+                Reward::Card(card) => item.style(theme.card(card.rarity(), card.color())),
+                Reward::ExtraAction | Reward::Water(_) => item,
+            }
+        })
+        .collect::<Vec<ListItem>>();
+    let title = format!(
+        " {} // {} ",
+        tr_with("draft.title", &[&app.game.round]),
+        tr("draft.hint")
+    );
+    let list = List::new(items)
+        .block(theme.panel(title, true))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol);
+    let mut state = ListState::default();
+    state.select(Some(draft.selected));
+    let area = centered_rect(50, 30, area);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+/// The ledger as a table, a column for every player.
+fn draw_breakdown(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let label = Style::default().fg(theme.label);
+    let ledgers = (0..app.game.players())
+        .map(|player| app.game.ledger_of(player))
+        .collect::<Vec<&ScoreLedger>>();
+    let categories = ledgers
+        .iter()
+        .flat_map(|ledger| ledger.lines().map(|(category, _)| category))
+        .collect::<BTreeSet<ScoreCategory>>();
+    let row = |name: String, points: &dyn Fn(&ScoreLedger) -> f32| {
+        let mut line = format!("{:<18}", name);
+        for ledger in &ledgers {
+            line.push_str(&format!("{:>10}", format_points(points(ledger))));
+        }
+        line
+    };
+    let mut lines = Vec::new();
+    if ledgers.len() > 1 {
+        let players = (1..=ledgers.len())
+            .map(|player| format!("{:>10}", format!("P{}", player)))
+            .collect::<String>();
+        lines.push(Line::from(Span::styled(
+            format!("{:<18}{}", "", players),
+            label,
+        )));
+    }
+    if categories.is_empty() {
+        lines.push(Line::from(Span::styled(tr("breakdown.nothing"), label)));
+    }
+    for category in categories {
+        let line = row(category.to_string(), &|ledger| ledger.get(category));
+        lines.push(Line::from(line));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        row(tr("breakdown.total").to_string(), &ScoreLedger::total),
+        theme.highlight(),
+    )));
+    let title = format!(" {} ", tr("breakdown.title"));
+    let paragraph = Paragraph::new(lines).block(theme.panel(title, true));
+    let area = centered_rect(50, 60, area);
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// What the last round update did, over the board until space.
+fn draw_summary(f: &mut Frame, app: &App, area: Rect) {
+    let Some(report) = app.game.round_reports.last() else {
+        return;
+    };
+    let theme = &app.theme;
+    let label = Style::default().fg(theme.label);
+    let mut lines = Vec::new();
+    if !report.matured.is_empty() {
+        lines.push(Line::from(Span::styled(tr("summary.matured"), label)));
+        for matured in &report.matured {
+            let points = format_points(matured.points);
+            let mut line = tr_with(
+                "summary.matured_line",
+                &[&matured.plant, &matured.tile, &points],
+            );
+            if !matured.cards.is_empty() {
+                line.push_str(&tr_with("summary.dropped", &[&matured.cards.join(", ")]));
+            }
+            if !matured.sown.is_empty() {
+                line.push_str(&tr_with("summary.sowed", &[&matured.sown.join(", ")]));
+            }
+            lines.push(Line::from(line));
+        }
+        lines.push(Line::from(""));
+    }
+    if !report.passives.is_empty() {
+        lines.push(Line::from(Span::styled(tr("summary.board"), label)));
+        for passive in &report.passives {
+            let points = format_points(passive.points);
+            let line = tr_with("summary.passive_line", &[&passive.name, &points]);
+            lines.push(Line::from(line));
+        }
+        lines.push(Line::from(""));
+    }
+    let cards = report.cards().collect::<Vec<&str>>();
+    if !cards.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", tr("summary.new_cards")), label),
+            Span::raw(cards.join(", ")),
+        ]));
+        lines.push(Line::from(""));
+    }
+    if !report.events.is_empty() {
+        lines.push(Line::from(Span::styled(tr("summary.events"), label)));
+        for event in &report.events {
+            lines.push(Line::from(event.clone()));
+        }
+        lines.push(Line::from(""));
+    }
+    let flash = Style::default()
+        .fg(theme.maturing)
+        .add_modifier(Modifier::BOLD);
+    if report.streak_grew() {
+        let multiplier = format!("{:.1}", streak_multiplier(report.streak));
+        let msg = tr_with("summary.streak_grew", &[&report.streak, &multiplier]);
+        lines.push(Line::from(Span::styled(msg, flash)));
+        lines.push(Line::from(""));
+    } else if report.streak_broke() {
+        let msg = tr_with("summary.streak_broke", &[&report.streak_before]);
+        lines.push(Line::from(Span::styled(msg, flash)));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(tr("summary.continue"), label)));
+    let title = format!(
+        " {} ",
+        tr_with(
+            "summary.title",
+            &[&(report.round + 1), &format_points(report.points())]
+        )
+    );
+    let paragraph = Paragraph::new(lines)
+        .block(theme.panel(title, true))
+        .wrap(Wrap { trim: true });
+    let area = centered_rect(60, 60, area);
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+fn draw_pause(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(pause) = app.pause.as_mut() else {
+        return;
+    };
+    if pause.achievements {
+        draw_achievements(f, app, area);
+        return;
+    }
+    let items = PAUSE_ITEMS
+        .iter()
+        .map(|item| match item {
+            PauseItem::Quit if pause.confirm_quit => ListItem::new(tr("pause.confirm_quit")),
+            _ => ListItem::new(item.to_string()),
+        })
+        .collect::<Vec<ListItem>>();
+    let list = List::new(items)
+        .block(app.theme.panel(format!(" {} ", tr("pause.title")), true))
+        .highlight_style(app.theme.highlight())
+        .highlight_symbol(app.theme.highlight_symbol);
+    let area = centered_rect(40, 30, area);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut pause.list);
+}
+
+/// Every achievement with the date it was unlocked, or locked.
+fn draw_achievements(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let unlocked = app.profile.as_ref().map(|profile| &profile.achievements);
+    let mut content = Vec::new();
+    for achievement in &achievements::ACHIEVEMENTS {
+        let (mark, style, when) = match unlocked.and_then(|u| u.get(achievement.id)) {
+            Some(&secs) => ("[x]", theme.highlight(), persist::format_date(secs)),
+            None => (
+                "[ ]",
+                Style::default().fg(theme.label),
+                tr("achievements.locked").to_string(),
+            ),
+        };
+        content.push(Line::from(vec![
+            Span::styled(format!("{} {:<16}", mark, achievement.name), style),
+            Span::raw(when),
+        ]));
+        content.push(Line::from(format!("    {}", achievement.description)));
+    }
+    content.extend([Line::from(""), Line::from(tr("achievements.back"))]);
+    let title = match (&app.profile, app.earn_achievements) {
+        (None, _) => tr("achievements.no_profile"),
+        (Some(_), false) => tr("achievements.not_counted"),
+        (Some(_), true) => tr("achievements.title"),
+    };
+    let title = format!(" {} ", title);
+    let paragraph = Paragraph::new(content)
+        .block(theme.panel(title, true))
+        .wrap(Wrap { trim: false });
+    let area = centered_rect(60, 60, area);
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
+    let msg = match &app.status {
+        Some((msg, _)) => msg.as_str(),
+        None if app.game.out_of_moves() => tr("status.no_moves"),
+        None => "",
+    };
+    let style = Style::default().fg(app.theme.label);
+    // The time played keeps to the right edge, the message gets the rest
+    let clock = match app.timer {
+        true => format!("{} ", format_duration(app.game.played_ms)),
+        false => String::new(),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(clock.len() as u16)].as_ref())
+        .split(area);
+    f.render_widget(Paragraph::new(format!(" {}", msg)).style(style), chunks[0]);
+    f.render_widget(Paragraph::new(clock).style(style), chunks[1]);
+}
+
+/// Rows of the tutorial banner over the board, borders included.
+const TUTORIAL_BANNER_HEIGHT: u16 = 4;
+
+/// The hint of the current tutorial step, in a banner across the top.
+fn draw_tutorial(f: &mut Frame, tutorial: &Tutorial, theme: &Theme, area: Rect) {
+    let Some(step) = tutorial.current() else {
+        return;
+    };
+    let title = format!(
+        " {} ",
+        tr_with(
+            "tutorial.title",
+            &[&tutorial.number(), &tutorial::STEPS.len()]
+        )
+    );
+    let paragraph = Paragraph::new(step.hint)
+        .style(Style::default().fg(theme.active))
+        .block(theme.panel(title, true))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// A rectangle of the given percentage size in the middle of `area`.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(vertical[1])[1]
+}
+
+fn draw_game_over(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let label = Style::default().fg(theme.label);
+    let game = &app.game;
+    let banked = app
+        .banked
+        .zip(app.profile.as_ref())
+        .map(|(seeds, profile)| {
+            Line::from(vec![
+                Span::styled(lang::label("game_over.seeds_banked"), label),
+                Span::raw(tr_with(
+                    "game_over.seeds_line",
+                    &[&seeds, &profile.seeds, &profile.name],
+                )),
+            ])
+        });
+    if game.players() > 1 {
+        let mut content = Vec::new();
+        for player in 0..game.players() {
+            content.push(Line::from(vec![
+                Span::styled(format!("{}: ", player_name(game, player, true)), label),
+                Span::raw(format_points(game.points_of(player))),
+            ]));
+        }
+        let best = (0..game.players())
+            .map(|player| game.points_of(player))
+            .fold(f32::MIN, f32::max);
+        let winners = (0..game.players())
+            .filter(|&player| game.points_of(player) == best)
+            .collect::<Vec<usize>>();
+        content.extend(banked);
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            match winners.as_slice() {
+                [AI_SEAT] if game.ai.is_some() => tr("game_over.cpu_wins").to_string(),
+                [_] if game.ai.is_some() => tr("game_over.you_win").to_string(),
+                [winner] => tr_with("game_over.wins", &[&(winner + 1)]),
+                _ => tr("game_over.draw").to_string(),
+            },
+            theme.highlight(),
+        )));
+        content.push(Line::from(""));
+        content.push(Line::from(tr("game_over.export_hint")));
+
+        let area = centered_rect(70, 60, area);
+        let title = format!(" {} ", tr("game_over.title"));
+        let paragraph = Paragraph::new(content).block(theme.panel(title, true));
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let mut summary = vec![
+        Span::styled(lang::label("label.score"), label),
+        Span::raw(app.game.score_display()),
+        Span::styled(format!("  {}", lang::label("label.rounds")), label),
+        Span::raw(app.game.round.to_string()),
+    ];
+    if app.timer {
+        summary.push(Span::styled(
+            format!("  {}", lang::label("label.time")),
+            label,
+        ));
+        summary.push(Span::raw(format_duration(app.game.played_ms)));
+    }
+    let mut content = vec![Line::from(summary)];
+    if let Some(puzzle) = app.game.settings.puzzle.as_ref() {
+        let score = app.game.total_points();
+        let mut line = vec![
+            Span::styled(lang::label("game_over.puzzle"), label),
+            Span::raw(format!(
+                "{}  ",
+                tr_with(
+                    "game_over.puzzle_line",
+                    &[&puzzle.name, &format_points(puzzle.target)]
+                )
+            )),
+            Span::styled(
+                match puzzle.solved(score) {
+                    true => tr("game_over.solved"),
+                    false => tr("game_over.failed"),
+                },
+                theme.highlight(),
+            ),
+        ];
+        if let Some(best) = puzzle.best {
+            line.push(Span::styled(
+                format!("  {}", lang::label("game_over.best_known")),
+                label,
+            ));
+            line.push(Span::raw(format_points(best)));
+        }
+        content.push(Line::from(line));
+        if let Some(description) = &puzzle.description {
+            content.push(Line::from(Span::raw(description.clone())));
+        }
+    }
+    for (category, points) in app.game.ledger.lines() {
+        content.push(Line::from(vec![
+            Span::styled(format!("  {:<18}", category), label),
+            Span::raw(format_points(points)),
+        ]));
+    }
+    let (hand, reserve) = (app.game.hand.len(), app.game.reserve.len());
+    if hand + reserve > 0 {
+        content.push(Line::from(vec![
+            Span::styled(lang::label("game_over.unplayed"), label),
+            Span::raw(match reserve {
+                0 => tr_with("game_over.in_hand", &[&hand]),
+                _ => tr_with("game_over.in_hand_reserve", &[&hand, &reserve]),
+            }),
+        ]));
+    }
+    content.extend(banked);
+    if let Some(profile) = &app.profile {
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(tr("game_over.records"), label)));
+        for (name, value) in profile.records.lines() {
+            let mut line = vec![Span::styled(format!("  {:<14}", name), label)];
+            match app.new_records.contains(&name) {
+                true => line.push(Span::styled(
+                    tr_with("game_over.new_record", &[&value]),
+                    theme.highlight(),
+                )),
+                false => line.push(Span::raw(value)),
+            }
+            content.push(Line::from(line));
+        }
+    }
+    content.extend([
+        Line::from(""),
+        Line::from(Span::styled(
+            tr_with(
+                "game_over.high_scores",
+                &[&scores::board_key(&app.game.settings)],
+            ),
+            label,
+        )),
+    ]);
+    if let Some(high_scores) = &app.high_scores {
+        for (rank, entry) in high_scores.entries.iter().enumerate() {
+            let style = if high_scores.rank == Some(rank) {
+                theme.highlight()
+            } else {
+                Style::default()
+            };
+            content.push(Line::from(Span::styled(entry.row(rank), style)));
+        }
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(tr("game_over.export_hint")));
+
+    let area = centered_rect(70, 60, area);
+    let title = format!(" {} ", tr("game_over.title"));
+    let paragraph = Paragraph::new(content).block(theme.panel(title, true));
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// The part of the board shown on the canvas, in tile coordinates.
+#[derive(Debug, Clone, Copy)]
+struct Viewport {
+    x: f64,
+    y: f64,
+    /// Magnification, 1.0 shows the whole board.
+    zoom: f64,
+    /// Tiles of the board across and down
+    width: f64,
+    height: f64,
+}
+
+impl Viewport {
+    const MAX_ZOOM: f64 = 8.0;
+
+    /// All of a board `width` by `height` tiles.
+    fn whole(width: usize, height: usize) -> Viewport {
+        Viewport {
+            x: 0.0,
+            y: 0.0,
+            zoom: 1.0,
+            width: width as f64,
+            height: height as f64,
+        }
+    }
+
+    fn visible_width(&self) -> f64 {
+        self.width / self.zoom
+    }
+
+    fn visible_height(&self) -> f64 {
+        self.height / self.zoom
+    }
+
+    fn pan(&mut self, dx: f64, dy: f64) {
+        self.x = (self.x + dx).clamp(0.0, self.width - self.visible_width());
+        self.y = (self.y + dy).clamp(0.0, self.height - self.visible_height());
+    }
+
+    fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * 2.0).min(Self::MAX_ZOOM);
+        self.pan(0.0, 0.0);
+    }
+
+    fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / 2.0).max(1.0);
+        self.pan(0.0, 0.0);
+    }
+
+    fn x_bounds(&self) -> [f64; 2] {
+        [self.x, self.x + self.visible_width()]
+    }
+
+    fn y_bounds(&self) -> [f64; 2] {
+        [self.y, self.y + self.visible_height()]
+    }
+}
+
+/// A terminal cell is about twice as tall as it is wide.
+const CELL_ASPECT: f64 = 2.0;
+
+/// Where the tiles of a `Viewport` land inside the board panel. Tiles get
+/// `CELL_ASPECT` times as many columns as rows so they look square, and the
+/// board is centered with what is left over as a margin. Drawing and mouse
+/// clicks both go through it, so a click hits the tile under it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BoardGeometry {
+    /// Inside the panel's border
+    inner: Rect,
+    /// Tiles of the board across and down
+    width: usize,
+    height: usize,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+}
+
+impl BoardGeometry {
+    fn new(view: &Viewport, area: Rect) -> Self {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        let [left, right] = view.x_bounds();
+        let [bottom, top] = view.y_bounds();
+        // The canvas puts its bounds on the first and last cell
+        let across = inner.width.saturating_sub(1) as f64;
+        let down = inner.height.saturating_sub(1) as f64;
+        let cols = (across / view.visible_width()).min(down / view.visible_height() * CELL_ASPECT);
+        if cols <= 0.0 {
+            return Self {
+                inner,
+                width: view.width as usize,
+                height: view.height as usize,
+                x_bounds: [left, right],
+                y_bounds: [bottom, top],
+            };
+        }
+        let margin_x = (across / cols - view.visible_width()) / 2.0;
+        let margin_y = (down * CELL_ASPECT / cols - view.visible_height()) / 2.0;
+        Self {
+            inner,
+            width: view.width as usize,
+            height: view.height as usize,
+            x_bounds: [left - margin_x, right + margin_x],
+            y_bounds: [bottom - margin_y, top + margin_y],
+        }
+    }
+
+    /// Terminal columns per tile.
+    fn cols(&self) -> f64 {
+        self.inner.width.saturating_sub(1) as f64 / (self.x_bounds[1] - self.x_bounds[0])
+    }
+
+    /// Terminal rows per tile.
+    fn rows(&self) -> f64 {
+        self.inner.height.saturating_sub(1) as f64 / (self.y_bounds[1] - self.y_bounds[0])
+    }
+
+    /// The tile drawn in a terminal cell, if any.
+    fn tile_at(&self, column: u16, row: u16) -> Option<(usize, usize)> {
+        let inner = self.inner;
+        if !(inner.left()..inner.right()).contains(&column)
+            || !(inner.top()..inner.bottom()).contains(&row)
+        {
+            return None;
+        }
+        // The middle of the cell, so rounding can't tip it into a neighbor
+        let x = self.x_bounds[0] + ((column - self.inner.x) as f64 + 0.5) / self.cols();
+        let y = self.y_bounds[1] - ((row - self.inner.y) as f64 + 0.5) / self.rows();
+        if !(0.0..self.width as f64).contains(&x) || !(0.0..self.height as f64).contains(&y) {
+            return None;
+        }
+        Some((x as usize, y as usize))
+    }
+}
+
+/// What the board draws on top of the tiles themselves.
+#[derive(Debug, Clone, Copy)]
+struct Overlay<'a> {
+    /// The animation phase, the cursor and plants harvested next round are
+    /// only highlighted while it is on
+    blink: bool,
+    /// Column letters and row numbers, where they fit
+    labels: bool,
+    effects: &'a [TileEffect],
+}
+
+/// What one tile shows, worked out once so every renderer tells the same
+/// story.
+struct TileView {
+    x: usize,
+    y: usize,
+    cursor: bool,
+    /// The tile's outline: the blinking cursor, a cloud, or the plant's
+    /// lifespan
+    frame: Color,
+    /// The plant text for every `TileDetail`, or the ghost preview
+    text: [String; 3],
+    style: Style,
+    /// What the ghost preview would harvest, only where it can go
+    points: Option<String>,
+    terrain: Option<(char, Color)>,
+    watered: u32,
+    cloud: Option<char>,
+}
+
+impl TileView {
+    fn text(&self, detail: TileDetail) -> &str {
+        let index = match detail {
+            TileDetail::Full => 0,
+            TileDetail::Compact => 1,
+            TileDetail::Block => 2,
+        };
+        &self.text[index]
+    }
+}
+
+/// Plants of one species on the board.
+#[derive(Debug, Clone, PartialEq)]
+struct SpeciesCount {
+    name: String,
+    short_display: char,
+    rarity: Rarity,
+    color: Option<Color>,
+    count: usize,
+}
+
+/// The board at a glance, for the strip under it.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct BoardSummary {
+    /// Most plants first, ties by name
+    species: Vec<SpeciesCount>,
+    /// Plants harvested when the round ends
+    maturing: usize,
+    /// Empty tiles a plant can go on
+    free: usize,
+}
+
+/// Species the board summary names, the others only count as `+n`.
+const SUMMARY_SPECIES: usize = 5;
+
+/// `w×4 W×2 +1 // 2 mature next round // 9 free`, in the plants' colors.
+fn draw_board_summary(f: &mut Frame, game: &Game, theme: &Theme, area: Rect) {
+    let summary = game.board_summary();
+    let label = Style::default().fg(theme.label);
+    let mut spans = Vec::new();
+    for species in summary.species.iter().take(SUMMARY_SPECIES) {
+        spans.push(Span::styled(
+            format!("{}×{}", species.short_display, species.count),
+            theme.card(species.rarity, species.color),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    match summary.species.len() {
+        0 => spans.push(Span::styled(format!("{} ", tr("board.no_plants")), label)),
+        len if len > SUMMARY_SPECIES => {
+            spans.push(Span::styled(format!("+{} ", len - SUMMARY_SPECIES), label));
+        }
+        _ => {}
+    }
+    let mature = match (game.harvest_mode, summary.maturing) {
+        (HarvestMode::Auto, 1) => tr("board.matures"),
+        (HarvestMode::Auto, _) => tr("board.mature"),
+        (HarvestMode::Manual, 1) => tr("board.gets_ripe"),
+        (HarvestMode::Manual, _) => tr("board.get_ripe"),
+    };
+    spans.push(Span::styled(
+        tr_with(
+            "board.summary",
+            &[&summary.maturing, &mature, &summary.free],
+        ),
+        label,
+    ));
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// The board as the renderers draw it.
+struct BoardViewModel {
+    title: String,
+    /// The board has the focus, the cursor only shows then
+    on_board: bool,
+    /// Tiles across and down
+    width: usize,
+    height: usize,
+    /// Row by row from the bottom, like `iter_coords`
+    tiles: Vec<TileView>,
+}
+
+impl BoardViewModel {
+    fn new(game: &Game, theme: &Theme, blink: bool) -> BoardViewModel {
+        let on_board = matches!(game.state, State::Placing | State::Inspect);
+        let maturing_at = game.settings.maturing_at;
+        let tiles = game
+            .tile
+            .iter_coords()
+            .map(|(x, y)| {
+                let cursor = on_board && x == game.placing.x && y == game.placing.y;
+                let tile = game.tile.get(x, y).unwrap_or(&Tile::Empty);
+                // The tile text and the frame around it count down together.
+                // Most urgent first: a last-round or overripe blink, maturing,
+                // the plant's own color, then aging and healthy
+                let lifespan = match tile {
+                    Tile::Permanent(p) => Some(match game.overripe_left(p) {
+                        Some(left) if left <= 1 && !blink => theme.inactive,
+                        Some(_) => theme.overripe,
+                        None if p.max_age - p.age <= 1 && !blink => theme.inactive,
+                        None => theme.lifespan_color(p.age, p.max_age, maturing_at, p.color()),
+                    }),
+                    _ => None,
+                };
+                let frame = match weather::weather_at(&game.clouds, x, y) {
+                    _ if cursor && blink => theme.active,
+                    Some(CloudKind::Rain) => theme.rain,
+                    Some(CloudKind::Drought) => theme.drought,
+                    None => lifespan.unwrap_or(theme.inactive),
+                };
+                // Pests and plants placed this round go over all of those
+                let text_color = match tile {
+                    Tile::Permanent(_) => lifespan.unwrap_or(theme.inactive),
+                    Tile::Infested(..) => theme.infested,
+                    Tile::New(_) => theme.new_plant,
+                    Tile::Empty => theme.inactive,
+                };
+                // The chosen plant shows faintly on the free tile under the
+                // cursor, a red X where it can't go. Taken tiles keep their plant
+                let ghost = match (cursor, game.state, tile, &game.choosing.choice) {
+                    (true, State::Placing, Tile::Empty, Some(Card::Plant(plant))) => Some(plant),
+                    _ => None,
+                };
+                let legal = game.can_place_plant(x, y).is_ok();
+                let (text, style, points) = match ghost {
+                    Some(plant) if legal => (
+                        [(); 3].map(|_| plant.short_display.to_string()),
+                        theme.ghost(),
+                        Some(format_points(game.projected_points(x, y, plant))),
+                    ),
+                    Some(_) => (
+                        [(); 3].map(|_| "X".to_string()),
+                        Style::default().fg(theme.infested),
+                        None,
+                    ),
+                    None => (
+                        [TileDetail::Full, TileDetail::Compact, TileDetail::Block]
+                            .map(|detail| tile.text(detail)),
+                        Style::default().fg(text_color),
+                        None,
+                    ),
+                };
+                let terrain = game.terrain_at(x, y);
+                let terrain = terrain.glyph().map(|glyph| {
+                    let color = match terrain {
+                        Terrain::Water => theme.rain,
+                        Terrain::Fertile => theme.active,
+                        _ => theme.inactive,
+                    };
+                    (glyph, color)
+                });
+                TileView {
+                    x,
+                    y,
+                    cursor,
+                    frame,
+                    text,
+                    style,
+                    points,
+                    terrain,
+                    watered: game.watered_at(x, y),
+                    cloud: game
+                        .clouds
+                        .iter()
+                        .find(|c| c.covers(x, y))
+                        .map(|cloud| cloud.glyph()),
+                }
+            })
+            .collect();
+        BoardViewModel {
+            title: board_title(game),
+            on_board,
+            width: game.width(),
+            height: game.height(),
+            tiles,
+        }
+    }
+}
+
+fn draw_game_board(
+    f: &mut Frame,
+    game: &Game,
+    view: &Viewport,
+    theme: &Theme,
+    overlay: Overlay,
+    area: Rect,
+) -> BoardGeometry {
+    let Overlay {
+        blink,
+        labels,
+        effects,
+    } = overlay;
+    let model = BoardViewModel::new(game, theme, blink);
+    let geometry = BoardGeometry::new(view, area);
+    let (cols, rows) = (geometry.cols(), geometry.rows());
+    // Labels go in the margin around the tiles, which only leaves room for
+    // them next to the tile text and annotations on roomy boards
+    let digits = game.height().to_string().len() as f64;
+    let labels = labels && rows >= 4.0 && cols >= 7.0 && cols * 0.3 >= digits;
+
+    let canvas = Canvas::default()
+        .block(theme.panel(model.title.clone(), model.on_board))
+        .background_color(theme.bg)
+        .paint(|ctx| {
+            let r_width = 0.7;
+            let r_height = 0.7;
+            for tile in &model.tiles {
+                let y_off = tile.y as f64 + (1.0 - r_height) / 2.0;
+                let x_off = tile.x as f64 + (1.0 - r_width) / 2.0;
+                let rect = Rectangle {
+                    x: x_off,
+                    y: y_off,
+                    width: r_width,
+                    height: r_height,
+                    color: tile.frame,
+                };
+
+                let detail = TileDetail::fitting(cols, tile.text(TileDetail::Full).chars().count());
+                let s = Span::styled(tile.text(detail).to_string(), tile.style);
+                ctx.layer();
+                ctx.print(x_off + r_width / 4.0, y_off + r_height / 2.0, s);
+                // Annotations need a row of their own and some room next to the text
+                let annotate = rows >= 2.0 && detail != TileDetail::Block;
+                // What the ghost would harvest goes right next to it
+                if let (true, Some(points)) = (annotate, &tile.points) {
+                    let points = Span::styled(points.clone(), Style::default().fg(theme.active));
+                    ctx.print(
+                        x_off + r_width / 4.0 + 2.0 / cols,
+                        y_off + r_height / 2.0,
+                        points,
+                    );
+                }
+                if let (true, Some((glyph, color))) = (annotate, tile.terrain) {
+                    let glyph = Span::styled(glyph.to_string(), Style::default().fg(color));
+                    ctx.print(x_off + r_width * 0.8, y_off + r_height / 4.0, glyph);
+                }
+                if annotate && tile.watered > 0 {
+                    let marker = Span::styled(
+                        format!("+{}", tile.watered),
+                        Style::default().fg(theme.rain),
+                    );
+                    ctx.print(x_off + r_width / 4.0, y_off + r_height / 4.0, marker);
+                }
+                if let (true, Some(cloud)) = (annotate, tile.cloud) {
+                    let glyph = Span::styled(cloud.to_string(), Style::default().fg(tile.frame));
+                    ctx.print(x_off, y_off + r_height, glyph);
+                }
+                ctx.draw(&rect);
+            }
+            if game.ai.is_some() {
+                draw_divider(ctx, view, theme);
+            }
+            if labels {
+                draw_axis_labels(ctx, view, theme);
+            }
+            draw_effects(ctx, effects, theme);
+        })
+        .x_bounds(geometry.x_bounds)
+        .y_bounds(geometry.y_bounds);
+    f.render_widget(canvas, area);
+    geometry
+}
+
+/// What the screens call a player: `You` and `CPU` against the CPU,
+/// otherwise `P1` and on, or `Player 1` in `full`.
+fn player_name(game: &Game, player: usize, full: bool) -> String {
+    match (game.ai, full) {
+        (Some(_), _) if player == AI_SEAT => tr("player.cpu").to_string(),
+        (Some(_), _) => tr("player.you").to_string(),
+        (None, true) => tr_with("game_over.player", &[&(player + 1)]),
+        (None, false) => format!("P{}", player + 1),
+    }
+}
+
+/// Difficulty, cursor tile, score and the round's resources, as the board
+/// panel's title.
+fn board_title(game: &Game) -> String {
+    let score = if game.players() > 1 {
+        let scores = (0..game.players())
+            .map(|player| {
+                format!(
+                    "{}: {}",
+                    player_name(game, player, false),
+                    format_points(game.points_of(player))
+                )
+            })
+            .collect::<Vec<String>>();
+        format!(
+            "{} // {}{}",
+            scores.join(" "),
+            lang::label("label.turn"),
+            player_name(game, game.turn, false)
+        )
+    } else {
+        format!("{}{}", lang::label("label.score"), game.score_display())
+    };
+    // The cursor's tile goes first, long titles are cut off at the border
+    let cursor = if matches!(game.state, State::Placing | State::Inspect) {
+        format!("// {} ", tile_name(game.placing.x, game.placing.y))
+    } else {
+        String::new()
+    };
+    let mut mode = game.difficulty.to_string();
+    if game.mode != GameMode::Standard {
+        mode.push_str(&format!(", {}", game.mode));
+    }
+    if !game.mutators.is_empty() {
+        mode.push_str(&format!(", {}", mutators::list(&game.mutators)));
+    }
+    let streak = match game.streak {
+        0 => String::new(),
+        streak => format!(
+            "// {}{} x{:.1} ",
+            lang::label("label.streak"),
+            streak,
+            game.multiplier()
+        ),
+    };
+    let mut title = format!(
+        " {} ({}) {}// {} {}// {}{} // {} // {}{} // {}{} ",
+        tr("board.title"),
+        mode,
+        cursor,
+        score,
+        streak,
+        lang::label("label.round"),
+        game.round,
+        game.weather,
+        lang::label("label.compost"),
+        game.compost,
+        lang::label("label.water"),
+        game.water
+    );
+    if let Some(left) = game.actions_left() {
+        title.push_str(&format!("// {}{} ", lang::label("label.actions"), left));
+    }
+    title
+}
+
+/// `--plain` stand-in for `draw_game_board`: the title as a line of its own,
+/// then one character per tile with the cursor in brackets, like `[W]`.
+fn draw_plain_board(f: &mut Frame, game: &Game, theme: &Theme, area: Rect) {
+    let on_board = matches!(game.state, State::Placing | State::Inspect);
+    let mut lines = vec![Line::from(board_title(game).trim().to_string())];
+    let header = (0..game.width())
+        .map(|x| format!("{:^3}", board::column_name(x)))
+        .collect::<String>();
+    lines.push(Line::from(format!("    {}", header)));
+    // Top row first, like on the canvas
+    for y in (0..game.height()).rev() {
+        let mut row = format!("{:>3} ", y + 1);
+        for x in 0..game.width() {
+            let glyph = match game.tile.get(x, y).and_then(Tile::plant) {
+                Some(p) => p.short_display,
+                None => game.terrain_at(x, y).glyph().unwrap_or('.'),
+            };
+            if on_board && (x, y) == (game.placing.x, game.placing.y) {
+                row.push_str(&format!("[{}]", glyph));
+            } else {
+                row.push_str(&format!(" {} ", glyph));
+            }
+        }
+        lines.push(Line::from(row));
+    }
+    let paragraph = Paragraph::new(lines)
+        .block(theme.panel(format!(" {} ", tr("board.title")), on_board))
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+/// Harvested tiles flash in alternating colors, then their points float up
+/// and dim before they disappear.
+fn draw_effects(ctx: &mut Context, effects: &[TileEffect], theme: &Theme) {
+    ctx.layer();
+    for effect in effects {
+        let Some(age) = effect.age() else {
+            continue;
+        };
+        let (x, y) = (effect.x as f64, effect.y as f64);
+        if age < FLASH_TICKS {
+            ctx.draw(&Rectangle {
+                x: x + 0.15,
+                y: y + 0.15,
+                width: 0.7,
+                height: 0.7,
+                color: if age % 2 == 0 {
+                    theme.active
+                } else {
+                    theme.maturing
+                },
+            });
+        }
+        let mut style = Style::default().fg(theme.new_plant);
+        if age * 3 >= EFFECT_TICKS * 2 {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        let rise = 0.5 * age as f64 / EFFECT_TICKS as f64;
+        let popup = Span::styled(format!("+{}", format_points(effect.points)), style);
+        ctx.print(x + 0.3, y + 0.5 + rise, popup);
+    }
+}
+
+/// Column letters along the bottom edge and row numbers along the left
+/// edge of the visible part of the board.
+/// Where the halves against the CPU end, two lines when an odd board
+/// leaves a middle column to neither.
+fn draw_divider(ctx: &mut Context, view: &Viewport, theme: &Theme) {
+    let [bottom, top] = view.y_bounds();
+    let width = view.width as usize;
+    let half = width / 2;
+    let mut edges = vec![half];
+    if width - half != half {
+        edges.push(width - half);
+    }
+    ctx.layer();
+    for x in edges {
+        ctx.draw(&canvas::Line {
+            x1: x as f64,
+            y1: bottom.max(0.0),
+            x2: x as f64,
+            y2: top.min(view.height),
+            color: theme.label,
+        });
+    }
+}
+
+fn draw_axis_labels(ctx: &mut Context, view: &Viewport, theme: &Theme) {
+    let style = Style::default().fg(theme.label);
+    let [left, right] = view.x_bounds();
+    let [bottom, top] = view.y_bounds();
+    ctx.layer();
+    for x in (left.floor() as usize..right.ceil() as usize).filter(|&x| (x as f64) < view.width) {
+        let center = x as f64 + 0.5;
+        if (left..right).contains(&center) {
+            let name = board::column_name(x);
+            ctx.print(center, bottom, Span::styled(name, style));
+        }
+    }
+    for y in (bottom.floor() as usize..top.ceil() as usize).filter(|&y| (y as f64) < view.height) {
+        let center = y as f64 + 0.5;
+        if (bottom..top).contains(&center) {
+            ctx.print(left, center, Span::styled((y + 1).to_string(), style));
+        }
+    }
+}
+
+fn draw_side(f: &mut Frame, app: &mut App, area: Rect) {
+    let next_round = next_round_lines(app);
+    let heights = side_heights(
+        area.height,
+        app.game.visible_hand().len(),
+        next_round.len() as u16 + 2,
+    );
+    let chunks = Layout::default()
+        .constraints(heights.map(Constraint::Length).as_ref())
+        .split(area);
+    draw_card_chooser(f, app, chunks[0]);
+    draw_card_info(f, app, chunks[1]);
+    draw_objectives(f, &app.game, &app.theme, chunks[2]);
+    draw_event_log(f, &app.game, &app.theme, chunks[3]);
+    draw_next_round(f, app, next_round, chunks[4]);
+}
+
+/// Rows of the side panels from top to bottom: Plants, card info,
+/// objectives, log and next round. Plants fits the hand within 5 rows and
+/// 60% of the height, the rest goes to the log. When that doesn't add up,
+/// the info panel gives up rows down to 6, next round down to 3 and Plants
+/// down to 5, in that order.
+fn side_heights(height: u16, cards: usize, next_round: u16) -> [u16; 5] {
+    let most = (height * 3 / 5).max(5);
+    let mut plants = (cards.min(most as usize) as u16 + 2).clamp(5, most);
+    let mut info = (height / 4).max(6);
+    let mut next = next_round.max(3);
+    let mut over = (plants + info + OBJECTIVES_HEIGHT + next).saturating_sub(height);
+    for (rows, min) in [(&mut info, 6), (&mut next, 3), (&mut plants, 5)] {
+        let cut = over.min(rows.saturating_sub(min));
+        *rows -= cut;
+        over -= cut;
+    }
+    let log = height.saturating_sub(plants + info + OBJECTIVES_HEIGHT + next);
+    [plants, info, OBJECTIVES_HEIGHT, log, next]
+}
+
+fn draw_card_chooser(f: &mut Frame, app: &mut App, area: Rect) {
+    let mut items: Vec<ListItem> = app
+        .game
+        .visible_hand()
+        .into_iter()
+        .enumerate()
+        .map(|(slot, idx)| {
+            let i = &app.game.hand[idx];
+            let style = match i {
+                Card::Plant(plant) if app.game.wasted(plant) => {
+                    Style::default().fg(app.theme.inactive)
+                }
+                _ => app.theme.card(i.rarity(), i.color()),
+            };
+            let mut spans = vec![
+                Span::raw(format!("{} ", slot_label(slot))),
+                Span::styled(i.display_name().to_string(), style),
+            ];
+            match app.game.stack_size(idx) {
+                1 => {}
+                count => spans.push(Span::raw(format!(" ×{}", count))),
+            }
+            if let Some(left) = app.game.freshness_left(i) {
+                let color = match left <= STALE_SOON {
+                    true => app.theme.overripe,
+                    false => app.theme.label,
+                };
+                spans.push(Span::styled(
+                    format!(" ({}r)", left),
+                    Style::default().fg(color),
+                ));
+            }
+            let lines = vec![Line::from(spans)];
+
+            ListItem::new(lines).style(Style::default())
+        })
+        .collect();
+
+    // Never selected, the selection is `None` while nothing is listed
+    if items.is_empty() {
+        let hint = match app.game.hand.is_empty() {
+            true => tr("hand.empty").to_string(),
+            false => tr_with("hand.no_match", &[&app.game.choosing.filter]),
+        };
+        items.push(ListItem::new(hint).style(Style::default().fg(app.theme.label)));
+    }
+    // The list keeps the selection in view, the title counts what is not
+    let hidden = items
+        .len()
+        .saturating_sub(area.height.saturating_sub(2) as usize);
+    let choosing = &app.game.choosing;
+    let mut title = format!(" {} ", tr("hand.title"));
+    if let Some(limit) = mutators::hand_limit(&app.game.mutators) {
+        title.push_str(&format!("{}/{} ", app.game.hand.len(), limit));
+    }
+    if !app.game.reserve.is_empty() {
+        title.push_str(&format!(
+            "{} ",
+            tr_with("hand.reserve", &[&app.game.reserve.len()])
+        ));
+    }
+    if choosing.sort != HandSort::Dealt {
+        title.push_str(&format!("{} ", tr_with("hand.sorted", &[&choosing.sort])));
+    }
+    if app.typing_filter || !choosing.filter.is_empty() {
+        let cursor = if app.typing_filter { "_" } else { "" };
+        title.push_str(&format!("/{}{} ", choosing.filter, cursor));
+    }
+    if hidden > 0 {
+        title.push_str(&format!("{} ", tr_with("hand.more", &[&hidden])));
+    }
+    let theme = &app.theme;
+    let items = List::new(items)
+        .block(theme.panel(title, app.game.state == State::Choosing))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol);
+
+    // We can now render the item list
+    f.render_stateful_widget(items, area, &mut app.list_state);
+}
+
+fn draw_event_log(f: &mut Frame, game: &Game, theme: &Theme, area: Rect) {
+    let rows = area.height.saturating_sub(2) as usize;
+    let skip = game.log.len().saturating_sub(rows);
+    let items: Vec<ListItem> = game.log[skip..]
+        .iter()
+        .map(|msg| ListItem::new(Line::from(msg.as_str())))
+        .collect();
+
+    let items = List::new(items).block(theme.panel(format!(" {} ", tr("log.title")), false));
+    f.render_widget(items, area);
+}
+
+/// One line per objective plus the borders.
+const OBJECTIVES_HEIGHT: u16 = objectives::OBJECTIVES_PER_RUN as u16 + 2;
+
+fn draw_objectives(f: &mut Frame, game: &Game, theme: &Theme, area: Rect) {
+    let lines: Vec<Line> = game
+        .objectives
+        .iter()
+        .map(|objective| {
+            let (mark, color) = match objective.status {
+                Status::Open => (' ', theme.inactive),
+                Status::Done => ('✓', theme.active),
+                Status::Failed => ('✗', theme.infested),
+            };
+            Line::from(Span::styled(
+                // The counter goes first, long goals get cut off at the border
+                format!(
+                    "{} {}/{} {} (+{})",
+                    mark,
+                    objective.progress.min(objective.target()),
+                    objective.target(),
+                    objective,
+                    format_points(objective.bonus)
+                ),
+                Style::default().fg(color),
+            ))
+        })
+        .collect();
+    // Families on the board against the threshold of the collection bonus
+    let threshold = game.settings.family_threshold;
+    let title = match game.has_families() && threshold > 0 {
+        true => format!(
+            " {} ",
+            tr_with(
+                "objectives.families",
+                &[&game.families_on_board(), &threshold]
+            )
+        ),
+        false => format!(" {} ", tr("objectives.title")),
+    };
+    let paragraph = Paragraph::new(lines).block(theme.panel(title, false));
+    f.render_widget(paragraph, area);
+}
+
+fn draw_next_round(f: &mut Frame, app: &App, content: Vec<Line>, area: Rect) {
+    let block = app.theme.panel("", app.game.state == State::NextRound);
+    let paragraph = Paragraph::new(content)
+        .block(block)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// The round panel's text: what ending the round does, the forecast and
+/// the clouds.
+fn next_round_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let mut content = vec![Line::from(tr("next.title"))];
+    if app.confirm_end {
+        let cards = app.game.hand.len();
+        content.push(Line::from(Span::styled(
+            match cards {
+                1 => tr("next.confirm_one").to_string(),
+                _ => tr_with("next.confirm", &[&cards]),
+            },
+            Style::default().fg(theme.maturing),
+        )));
+    }
+    if app.game.state != State::GameOver {
+        let (count, points) = app.game.maturing_next_round();
+        let [nothing, one, many] = match app.game.harvest_mode {
+            HarvestMode::Auto => [
+                "next.nothing_matures",
+                "next.one_matures",
+                "next.many_mature",
+            ],
+            HarvestMode::Manual => ["next.nothing_ripens", "next.one_ripens", "next.many_ripen"],
+        };
+        let points = format_points(points);
+        let text = match count {
+            0 => tr(nothing).to_string(),
+            1 => tr_with(one, &[&points]),
+            _ => tr_with(many, &[&count, &points]),
+        };
+        content.push(Line::from(Span::styled(
+            text,
+            Style::default().fg(theme.label),
+        )));
+        let withering = app.game.withering_next_round();
+        if withering > 0 {
+            content.push(Line::from(Span::styled(
+                match withering {
+                    1 => tr("next.withers_one").to_string(),
+                    _ => tr_with("next.withers", &[&withering]),
+                },
+                Style::default().fg(theme.overripe),
+            )));
+        }
+    }
+    if app.game.settings.forecast && app.game.state != State::GameOver {
+        content.push(Line::from(Span::styled(
+            tr_with("next.forecast", &[&app.game.forecast]),
+            Style::default().fg(theme.label),
+        )));
+    }
+    for cloud in app.game.clouds.iter() {
+        content.push(Line::from(Span::styled(
+            tr_with("next.cloud", &[&cloud.kind, &cloud.entry]),
+            Style::default().fg(theme.label),
+        )));
+    }
+    content
+}
+
+/// What playing `plant` this late comes to: a warning with auto harvests,
+/// what the final harvest pays for it with manual ones.
+fn too_late_line(
+    plant: &Plant,
+    (x, y): (usize, usize),
+    game: &Game,
+    theme: &Theme,
+) -> Option<Line<'static>> {
+    let left = game.too_late(plant)?;
+    let needs = plant.max_age.saturating_sub(plant.age);
+    Some(match game.harvest_mode {
+        HarvestMode::Auto => Line::from(Span::styled(
+            tr_with("info.will_not_mature", &[&left, &needs]),
+            Style::default().fg(theme.overripe),
+        )),
+        HarvestMode::Manual => Line::from(vec![
+            Span::styled(
+                lang::label("info.early_value"),
+                Style::default().fg(theme.label),
+            ),
+            Span::raw(tr_with(
+                "info.early_value_line",
+                &[&format_points(game.projected_points(x, y, plant)), &left],
+            )),
+        ]),
+    })
+}
+
+fn draw_card_info(f: &mut Frame, app: &mut App, area: Rect) {
+    let (x, y) = (app.game.placing.x, app.game.placing.y);
+    let card_opt = app.game.shown_card();
+    let on_board = matches!(app.game.state, State::Placing | State::Inspect);
+
+    let content = match card_opt {
+        Some(Card::Item(ref item)) => item_info(item, &app.theme),
+        Some(Card::Plant(ref plant)) => {
+            let mut lines = plant_info(
+                plant,
+                app.game.projected_points(x, y, plant),
+                on_board.then_some((x, y)),
+                &app.game,
+                &app.theme,
+            );
+            if let (true, Some(p)) = (on_board, app.game.plant_at(x, y)) {
+                let label = Style::default().fg(app.theme.label);
+                lines.insert(
+                    0,
+                    Line::from(vec![
+                        Span::styled(lang::label("info.age"), label),
+                        Span::raw(format!("{}/{}", p.age, p.max_age)),
+                        Span::styled(format!("  {}", lang::label("info.size")), label),
+                        Span::raw(p.size.to_string()),
+                    ]),
+                );
+                lines.insert(1, growth_bar(p, &app.theme));
+                let maturing = matches!(app.game.tile.get(x, y), Some(Tile::Permanent(_)))
+                    && p.max_age.saturating_sub(p.age) <= 1;
+                if maturing {
+                    lines.insert(2, fate_line(p, (x, y), &app.game, &app.theme));
+                }
+            } else {
+                // Cards haven't started growing yet
+                lines.insert(
+                    0,
+                    Line::from(vec![
+                        Span::styled(
+                            lang::label("info.lifetime"),
+                            Style::default().fg(app.theme.label),
+                        ),
+                        Span::raw(tr_with("info.rounds", &[&plant.max_age])),
+                    ]),
+                );
+                if let Some(line) = too_late_line(plant, (x, y), &app.game, &app.theme) {
+                    lines.insert(1, line);
+                }
+            }
+            // Right below the lifetime, the stats can be looked up elsewhere
+            if app.game.state == State::Placing && app.game.plant_at(x, y).is_none() {
+                let info = placement_info(plant, (x, y), &app.game, &app.theme);
+                lines.splice(1..1, info);
+            }
+            if app.game.state == State::Placing && app.game.plant_at(x, y).is_some() {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        lang::label("info.water"),
+                        Style::default().fg(app.theme.label),
+                    ),
+                    Span::raw(tr_with("info.water_line", &[&app.game.watered_at(x, y)])),
+                ]));
+                if let Some(Card::Item(item)) = &app.game.choosing.choice {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("{}: ", item.name),
+                            Style::default().fg(app.theme.label),
+                        ),
+                        Span::raw(item.effect.to_string()),
+                    ]));
+                }
+            }
+            lines
+        }
+        None => {
+            let mut lines = vec![Line::from(tr("info.empty"))];
+            if on_board {
+                lines.push(nearest_plant_line((x, y), &app.game, &app.theme));
+            }
+            lines
+        }
+    };
+    let mut content = content;
+    let terrain = app.game.terrain_at(x, y);
+    if on_board && terrain != Terrain::Plain {
+        content.push(Line::from(vec![
+            Span::styled(
+                lang::label("info.terrain"),
+                Style::default().fg(app.theme.label),
+            ),
+            Span::raw(terrain.to_string()),
+        ]));
+    }
+    let description = match &card_opt {
+        Some(Card::Plant(plant)) => plant.description.as_deref(),
+        _ => None,
+    };
+    fit_card_info(&mut content, description, area, &app.theme);
+
+    let title = match (&app.game.state, &card_opt) {
+        (State::Inspect, Some(card)) => {
+            format!(" {}{} ", lang::label("info.inspect"), card.display_name())
+        }
+        (State::Inspect, None) => format!(" {} ", tr("info.inspect")),
+        (_, Some(card)) => format!(" {} ", card.display_name()),
+        (_, None) => "".into(),
+    };
+    let title = match &card_opt {
+        Some(card) => app.theme.rarity_title(title, card.rarity()),
+        None => app.theme.title(title),
+    };
+
+    let block = app.theme.titled_panel(title, false);
+    let paragraph = Paragraph::new(content)
+        .block(block)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// Adds the description below the other lines of the panel and cuts what
+/// doesn't fit, with a hint at the detail view in the last row.
+fn fit_card_info(
+    lines: &mut Vec<Line<'static>>,
+    description: Option<&str>,
+    area: Rect,
+    theme: &Theme,
+) {
+    let width = area.width.saturating_sub(2).max(1) as usize;
+    let rows = area.height.saturating_sub(2) as usize;
+    let start = lines.len() + 1;
+    let wrapped = description.map_or_else(Vec::new, |text| wrap_words(text, width));
+    if !wrapped.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(wrapped.iter().cloned().map(Line::from));
+    }
+    let heights = lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(width))
+        .collect::<Vec<usize>>();
+    if heights.iter().sum::<usize>() <= rows || rows == 0 {
+        return;
+    }
+    let mut used = 0;
+    let keep = heights
+        .iter()
+        .take_while(|&&h| {
+            used += h;
+            used < rows
+        })
+        .count();
+    lines.truncate(keep);
+    if keep > start {
+        let mut last = wrapped[keep - 1 - start].clone();
+        while last.chars().count() + 1 > width {
+            last.pop();
+        }
+        last.push('…');
+        lines[keep - 1] = Line::from(last);
+    }
+    lines.push(Line::from(Span::styled(
+        tr("info.more"),
+        Style::default().fg(theme.label),
+    )));
+}
+
+/// Breaks a text into lines of at most `width` characters at spaces, like
+/// the `Wrap` of a paragraph, so the rows it takes can be counted.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let len = line.chars().count();
+        if len > 0 && len + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// The description and flavor of a plant, each after a gap, skipping the
+/// ones it doesn't have.
+fn plant_texts(plant: &Plant, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(description) = &plant.description {
+        lines.push(Line::from(""));
+        lines.push(Line::from(description.clone()));
+    }
+    if let Some(flavor) = &plant.flavor {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            flavor.clone(),
+            Style::default()
+                .fg(theme.inactive)
+                .add_modifier(Modifier::ITALIC),
+        )));
+    }
+    lines
+}
+
+/// The card of the info panel over the whole screen, with all of its text.
+fn draw_details(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme;
+    let (x, y) = (app.game.placing.x, app.game.placing.y);
+    let on_board = matches!(app.game.state, State::Placing | State::Inspect);
+    let (title, mut content) = match app.game.shown_card() {
+        Some(Card::Plant(plant)) => {
+            let mut lines = plant_info(
+                &plant,
+                app.game.projected_points(x, y, &plant),
+                on_board.then_some((x, y)),
+                &app.game,
+                theme,
+            );
+            lines.extend(plant_texts(&plant, theme));
+            (
+                theme.rarity_title(format!(" {} ", plant.display_name()), plant.rarity),
+                lines,
+            )
+        }
+        Some(Card::Item(item)) => (
+            theme.rarity_title(format!(" {} ", item.name), item.rarity),
+            item_info(&item, theme),
+        ),
+        None => (theme.title(""), Vec::new()),
+    };
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        tr("info.back"),
+        Style::default().fg(theme.label),
+    )));
+    let paragraph = Paragraph::new(content)
+        .block(theme.titled_panel(title, true))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, centered_rect(70, 80, area));
+}
+
+/// Age as a bar of block characters, tinted like the tile as the plant
+/// nears its harvest.
+fn growth_bar(plant: &Plant, theme: &Theme) -> Line<'static> {
+    const WIDTH: usize = 10;
+    let ratio = if plant.max_age == 0 {
+        1.0
+    } else {
+        (plant.age as f32 / plant.max_age as f32).min(1.0)
+    };
+    let filled = (ratio * WIDTH as f32).round() as usize;
+    let color = if plant.max_age.saturating_sub(plant.age) < 3 {
+        theme.maturing
+    } else if ratio >= 0.5 {
+        theme.new_plant
+    } else {
+        theme.active
+    };
+    Line::from(vec![
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(WIDTH - filled),
+            Style::default().fg(theme.inactive),
+        ),
+        Span::raw(format!(" {:.0}%", ratio * 100.0)),
+    ])
+}
+
+/// What an item card does, as shown by the info panel.
+fn item_info(item: &Item, theme: &Theme) -> Vec<Line<'static>> {
+    vec![
+        Line::from(item.effect.to_string()),
+        Line::from(Span::styled(
+            tr("info.use_item"),
+            Style::default().fg(theme.label),
+        )),
+    ]
+}
+
+/// Whether the chosen `plant` can go on the empty tile `at` and what its
+/// neighbors would do for it there.
+fn placement_info(
+    plant: &Plant,
+    (x, y): (usize, usize),
+    game: &Game,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let label = Style::default().fg(theme.label);
+    let mut lines = vec![Line::from(vec![
+        Span::styled(lang::label("info.placement"), label),
+        match game.can_place_plant(x, y) {
+            Ok(()) => Span::raw(tr("info.possible")),
+            Err(err) => Span::styled(err.to_string(), Style::default().fg(theme.infested)),
+        },
+    ])];
+    for bonus in game.neighbor_bonuses(x, y, plant) {
+        lines.push(Line::from(vec![
+            Span::styled(lang::label("info.neighbor"), label),
+            Span::raw(bonus),
+        ]));
+    }
+    lines.push(nearest_plant_line((x, y), game, theme));
+    lines
+}
+
+fn nearest_plant_line((x, y): (usize, usize), game: &Game, theme: &Theme) -> Line<'static> {
+    let nearest = match game.nearest_plant(x, y) {
+        Some((p, 1, (px, py))) => {
+            tr_with("info.nearest_one", &[&p.display_name(), &tile_name(px, py)])
+        }
+        Some((p, steps, (px, py))) => tr_with(
+            "info.nearest_many",
+            &[&p.display_name(), &tile_name(px, py), &steps],
+        ),
+        None => tr("info.none_yet").to_string(),
+    };
+    Line::from(vec![
+        Span::styled(
+            lang::label("info.nearest"),
+            Style::default().fg(theme.label),
+        ),
+        Span::raw(nearest),
+    ])
+}
+
+/// The stats, drop table and hybrids of a plant, as shown by the info panels.
+/// Drops that need a neighbor say whether the tile `at` has it, and the
+/// stats are those under the effects of the plant growing there.
+fn plant_info(
+    plant: &Plant,
+    points: f32,
+    at: Option<(usize, usize)>,
+    game: &Game,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let label = Style::default().fg(theme.label);
+    let status = at
+        .and_then(|(x, y)| game.tile.get(x, y))
+        .and_then(Tile::planted)
+        .map_or(&[][..], |p| &p.effects);
+    let stats = effects::effective(plant, status);
+    let changed = |value: String, base: String| match value == base {
+        true => value,
+        false => tr_with("info.base", &[&value, &base]),
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(lang::label("info.max_age"), label),
+            Span::raw(plant.max_age.to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled(lang::label("info.size_per_turn"), label),
+            Span::raw(changed(
+                stats.size_per_turn.to_string(),
+                plant.size_per_turn.to_string(),
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled(lang::label("info.points_per_size"), label),
+            Span::raw(changed(
+                stats.points_per_size.to_string(),
+                plant.points_per_size.to_string(),
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled(lang::label("info.points"), label),
+            Span::raw(format_points(points)),
+        ]),
+    ];
+    for effect in status {
+        lines.push(Line::from(vec![
+            Span::styled(lang::label("info.effect"), label),
+            Span::raw(match effect.remaining_rounds {
+                1 => tr_with("info.last_round", &[effect]),
+                n => tr_with("info.rounds_left", &[effect, &n]),
+            }),
+        ]));
+    }
+    if !plant.growth_curve.is_linear() {
+        lines.push(Line::from(vec![
+            Span::styled(lang::label("info.growth"), label),
+            Span::raw(plant.growth_curve.to_string()),
+        ]));
+    }
+    if plant.spread_chance > 0.0 {
+        lines.push(Line::from(vec![
+            Span::styled(lang::label("info.spreads"), label),
+            Span::raw(tr_with(
+                "info.per_round",
+                &[&format!("{:.0}", plant.spread_chance * 100.0)],
+            )),
+        ]));
+    }
+    if !game.is_unlocked(CardId::Plant(plant.id)) {
+        lines.push(Line::from(vec![
+            Span::styled(lang::label("info.unlocks_at"), label),
+            Span::raw(tr_with(
+                "info.points_value",
+                &[&format_points(plant.unlock_at_points)],
+            )),
+        ]));
+    }
+    if !plant.drops.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("{}:", tr("info.drops")),
+            label,
+        )));
+    }
+    let open = game.open_drops(plant, at);
+    for ((drop, p), open) in plant
+        .drops
+        .iter()
+        .zip(plant.drop_probabilities(&open))
+        .zip(open)
+    {
+        let text = drop.plants.join(" + ");
+        let locked = !drop.ids.iter().all(|&id| game.is_unlocked(id));
+        let needs = match &drop.requires_adjacent {
+            Some(name) if at.is_some() && open => {
+                format!(" ({})", tr_with("info.next_to_yes", &[name]))
+            }
+            Some(name) if at.is_some() && !locked => {
+                format!(" ({})", tr_with("info.next_to_no", &[name]))
+            }
+            Some(name) => format!(" ({})", tr_with("info.next_to", &[name])),
+            None => String::new(),
+        };
+        lines.push(match open {
+            true => Line::from(format!("{:>3.0}% {}{}", p * 100.0, text, needs)),
+            false if locked => Line::from(Span::styled(
+                format!("- {} ({})", text, tr("info.locked")),
+                Style::default().fg(theme.inactive),
+            )),
+            false => Line::from(Span::styled(
+                format!("- {}{}", text, needs),
+                Style::default().fg(theme.inactive),
+            )),
+        });
+    }
+    let (hybrids, plants) = (&game.hybrids, &game.id_to_plant);
+    let hybrids = hybrids
+        .iter()
+        .filter(|h| h.involves(plant.id))
+        .collect::<Vec<_>>();
+    if !hybrids.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("{}:", tr("info.hybrids")),
+            label,
+        )));
+    }
+    for hybrid in hybrids {
+        let cross = format!(
+            "{} + {} -> {}",
+            plants[hybrid.parents[0].index()].display_name(),
+            plants[hybrid.parents[1].index()].display_name(),
+            plants[hybrid.child.index()].display_name()
+        );
+        lines.push(match game.is_unlocked(CardId::Plant(hybrid.child)) {
+            true => Line::from(format!("{:>3.0}% {}", hybrid.chance * 100.0, cross)),
+            false => Line::from(Span::styled(
+                format!("- {} ({})", cross, tr("info.locked")),
+                Style::default().fg(theme.inactive),
+            )),
+        });
+    }
+    lines
+}
+
+/// Every known plant on the left, the selected one's details on the right.
+fn draw_codex(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+        .margin(1)
+        .split(area);
+
+    // Plants come pack by pack, a run with several gets a header per pack
+    let grouped = app.game.packs.len() > 1;
+    let mut items = Vec::new();
+    let mut rows = Vec::new();
+    for (idx, p) in app.game.id_to_plant.iter().enumerate() {
+        let first = idx == 0 || app.game.id_to_plant[idx - 1].pack != p.pack;
+        if grouped && first {
+            items.push(ListItem::new(Span::styled(
+                p.pack.clone(),
+                Style::default().fg(app.theme.label),
+            )));
+        }
+        rows.push(items.len());
+        items.push(codex_item(app, p));
+    }
+    let theme = &app.theme;
+    let list = List::new(items)
+        .block(theme.panel(format!(" {} ", tr("codex.title")), true))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol);
+
+    let plant = app
+        .codex
+        .as_ref()
+        .and_then(|state| state.selected())
+        .and_then(|idx| app.game.id_to_plant.get(idx));
+    let (title, content) = match plant {
+        Some(plant) => {
+            // A fresh plant growing its whole life in fair weather
+            let size = curve::size_at(
+                plant.growth_curve,
+                plant.size_per_turn,
+                plant.max_age,
+                plant.max_age,
+            );
+            let points = size as f32 * plant.points_per_size;
+            let mut lines = plant_info(plant, points, None, &app.game, theme);
+            lines.extend(plant_texts(plant, theme));
+            (
+                theme.rarity_title(format!(" {} ", plant.display_name()), plant.rarity),
+                lines,
+            )
+        }
+        None => (theme.title(""), Vec::new()),
+    };
+    let detail = Paragraph::new(content)
+        .block(theme.titled_panel(title, false))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(detail, chunks[1]);
+    if let Some(state) = app.codex.as_mut() {
+        // The selection counts plants, the list also has the headers
+        let mut shown = ListState::default();
+        shown.select(state.selected().and_then(|idx| rows.get(idx).copied()));
+        f.render_stateful_widget(list, chunks[0], &mut shown);
+    }
+}
+
+fn codex_item(app: &App, p: &Plant) -> ListItem<'static> {
+    if !app.game.is_unlocked(CardId::Plant(p.id)) {
+        let locked = tr_with(
+            "codex.locked",
+            &[
+                &p.short_display,
+                &p.display_name(),
+                &format_points(p.unlock_at_points),
+            ],
+        );
+        return ListItem::new(Span::styled(
+            locked,
+            Style::default().fg(app.theme.inactive),
+        ));
+    }
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{} ", p.short_display)),
+        Span::styled(p.display_name().to_string(), app.theme.rarity(p.rarity)),
+    ]))
+}
+
+/// Dev and scripted runs don't count for achievements, unless they are
+/// allowed to with `--allow-achievements`.
+fn earns_achievements(args: &Args) -> bool {
+    args.allow_achievements || !(args.dev || args.script.is_some())
+}
+
+/// The whole game as the binary plays it: flags, config file and then the
+/// terminal, a headless script or one of the subcommands.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    // Flags go over the file, which is read before the terminal is touched
+    let file = match Config::load(&config::config_path()) {
+        Ok(file) => file,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+    file.merge(Config::given(&args, &matches)).apply(&mut args);
+    trace::install_panic_hook();
+    if let Some(path) = &args.log_file {
+        if let Err(err) = trace::init(path, args.log_level) {
+            eprintln!("Couldn't open the log: {}", err);
+            std::process::exit(1);
+        }
+    }
+    lang::init(args.lang.as_deref());
+    if args.print_config {
+        print!("{}", Config::of(&args).to_toml());
+        return Ok(());
+    }
+    // Flags of any kind mean a script or a configured run, no questions
+    if std::env::args_os().len() == 1 {
+        match setup::open(&Theme::new(args.theme))? {
+            Some(setup) => {
+                args.dim = setup.size;
+                args.difficulty = setup.difficulty;
+                args.seed = setup.seed;
+                args.mutator = setup.mutators;
+            }
+            None => return Ok(()),
+        }
+    }
+    // The board size is fixed for the whole process, the run after the
+    // tutorial keeps it
+    if args.tutorial {
+        args.dim = tutorial::DIM;
+    }
+    if args.scores {
+        let table = ScoreTable::load(&scores::scores_path())?;
+        scores::print_scores(&table);
+        return Ok(());
+    }
+    if args.records {
+        let profile = Profile::load(&args.profile)?;
+        records::print_records(&profile.name, &profile.records);
+        return Ok(());
+    }
+    if let Some(Command::Stats { format }) = args.command {
+        let stats = history::stats(&History::load(&history::history_path())?);
+        match format {
+            Format::Table => history::print_stats(&stats),
+            Format::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+        }
+        return Ok(());
+    }
+    let mut pack = None;
+    let mut shared = None;
+    if !matches!(args.command, Some(Command::Spectate { .. })) {
+        let mut settings = GlobalSetting::load(&args).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        });
+        if let Some(Command::Simulate { rounds, .. }) = args.command {
+            // A run without a limit may never end
+            let limit = rounds.or(settings.round_limit).unwrap_or(20);
+            settings.round_limit = Some(limit);
+            settings.difficulty.round_limit = Some(limit);
+        }
+        let hand = settings
+            .difficulty
+            .hand
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+        let loaded = match &args.pack {
+            Some(selection) => match packs::load(Path::new(packs::PACK_DIR), selection, &hand) {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            },
+            None => Pack::builtin(hand),
+        };
+        if let Some(name) = &args.preset {
+            // The packs' own presets go before the shared ones
+            let file = format!("{}.json", name);
+            let dir = loaded
+                .preset_dirs
+                .iter()
+                .find(|dir| dir.join(&file).is_file())
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from(terrain::PRESET_DIR));
+            match terrain::load_preset(&dir, name) {
+                Ok(preset) => settings.preset = Some(preset),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        match (args.mode, &args.puzzle) {
+            (GameMode::Puzzle, Some(name)) => {
+                match loaded.plant_set().and_then(|set| puzzle::load(name, &set)) {
+                    Ok((puzzle, layout)) => {
+                        settings.width = puzzle.size;
+                        settings.height = puzzle.size;
+                        settings.seed = puzzle.seed;
+                        settings.round_limit = Some(puzzle.rounds);
+                        settings.difficulty.round_limit = Some(puzzle.rounds);
+                        if !puzzle.drops {
+                            settings.difficulty.drop_chance = 0.0;
+                        }
+                        settings.board = Some(layout);
+                        settings.puzzle = Some(puzzle);
+                    }
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            (GameMode::Puzzle, None) => {
+                eprintln!("--mode puzzle needs a --puzzle to play");
+                std::process::exit(1);
+            }
+            (_, Some(_)) => {
+                eprintln!("--puzzle only goes with --mode puzzle");
+                std::process::exit(1);
+            }
+            _ => {}
+        }
+        if let Some(path) = &args.board {
+            let laid = loaded
+                .plant_set()
+                .and_then(|set| layout::load(path, &set, settings.width, settings.height));
+            match laid {
+                Ok(layout) => settings.board = Some(layout),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        pack = Some(loaded);
+        shared = Some(Arc::new(settings));
+    }
+
+    let script = match &args.script {
+        Some(path) => {
+            let contents = fs::read_to_string(path)?;
+            match action::parse_script(&contents) {
+                Ok(script) => script,
+                Err(err) => {
+                    eprintln!("{}: {}", path.display(), err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+
+    if let Some(Command::Validate { path }) = &args.command {
+        let hand = shared
+            .as_ref()
+            .unwrap_or_else(|| unreachable!("validation always loads its settings"))
+            .difficulty
+            .hand
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+        // A pack directory is checked with its own starting hand
+        let read = match path.is_dir() {
+            true => packs::read(path).map(|(file, own)| (file, own.unwrap_or(hand))),
+            false => read_plants(path).map(|file| (file, hand)),
+        };
+        let report = match read {
+            Ok((file, hand)) => validate::check(&file.plants, &file.hybrids, &items::all(), &hand),
+            Err(err) => validate::Report {
+                errors: vec![err],
+                warnings: Vec::new(),
+            },
+        };
+        validate::print_report(path, &report);
+        if !report.errors.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Simulate {
+        games,
+        policy,
+        json,
+        ..
+    }) = args.command
+    {
+        let pack = pack.unwrap_or_else(|| unreachable!("simulations always load their plants"));
+        let settings = shared.unwrap_or_else(|| unreachable!("and their settings"));
+        let report = simulate::run(&pack, settings, games, policy);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            simulate::print_report(&report);
+        }
+        return Ok(());
+    }
+
+    let loaded = match (&args.command, &args.load, &pack, &shared) {
+        (None, Some(name), Some(pack), Some(settings)) => {
+            let name = slots::sanitize(name);
+            match slots::load_slot(&slots::slots_dir(), &name, &pack.names, settings) {
+                Ok(game) => Some(game),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => None,
+    };
+
+    if let Some(Command::Serve { .. }) = args.command {
+        let pack = pack.unwrap_or_else(|| unreachable!("bots always play on loaded plants"));
+        let settings = shared.unwrap_or_else(|| unreachable!("and loaded settings"));
+        serve::run(Game::empty(&pack, settings))?;
+        return Ok(());
+    }
+
+    if args.headless && args.command.is_none() {
+        let pack = pack.unwrap_or_else(|| unreachable!("runs always load their plants"));
+        let settings = shared.unwrap_or_else(|| unreachable!("and their settings"));
+        let mut app = App::new(Theme::new(args.theme), pack, settings);
+        app.export = args.export;
+        if let Some(game) = loaded {
+            app.replace_game(game);
+            app.game.keep_selection();
+        }
+        run_headless(app, script)?;
+        return Ok(());
+    }
+
+    // Read before the terminal is taken over, so errors end up readable
+    let profile = match (&args.command, args.no_profile) {
+        (None, false) => match Profile::load(&args.profile) {
+            Ok(profile) => Some(profile),
+            Err(err) => {
+                eprintln!("Couldn't read profile {}: {}", args.profile, err);
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+
+    trace::take_terminal()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = match args.command {
+        Some(Command::Spectate { path }) => {
+            spectate::run_spectator(&mut terminal, &path, Theme::new(args.theme))
+        }
+        Some(
+            Command::Simulate { .. }
+            | Command::Validate { .. }
+            | Command::Stats { .. }
+            | Command::Serve { .. },
+        ) => {
+            unreachable!("simulations, validation, stats and bots never open the terminal")
+        }
+        None => {
+            // create app and run it
+            let pack = pack.unwrap_or_else(|| unreachable!("runs always load their plants"));
+            let settings = shared.unwrap_or_else(|| unreachable!("and their settings"));
+            let mut app = App::new(Theme::new(args.theme), pack, settings);
+            app.earn_achievements = earns_achievements(&args);
+            app.export = args.export;
+            app.plain = args.plain;
+            app.timer = !args.no_timer;
+            app.auto_advance = args.auto_advance;
+            app.game.choosing.grouped = args.group_hand;
+            app.game.keep_selection_visible();
+            app.fps = args.debug_fps.then(FpsMeter::default);
+            app.board_split = args.board_split;
+            app.renderer = args.renderer;
+            app.config_path = Some(config::config_path());
+            app.animate = !args.plain;
+            app.summaries = !args.plain;
+            if args.dev {
+                let path = args.dev_plants.clone().or_else(|| app.pack.path());
+                let (plants, hybrids) = (app.pack.plants.clone(), app.pack.hybrids.clone());
+                app.editor = Some(Editor::new(plants, hybrids, path));
+            }
+            app.profile = profile;
+            if args.tutorial {
+                app.start_tutorial();
+            }
+            let resumed = loaded.is_some();
+            if let Some(game) = loaded {
+                app.replace_game(game);
+                // Saved before the selection was kept in line with the hand
+                app.game.keep_selection();
+            }
+            if !app.owns_theme(app.theme.name) {
+                app.theme = Theme::new(ThemeName::Default);
+                app.notify(format!("The {:?} theme has to be bought first", args.theme));
+            }
+            let mut publisher = args.publish.map(Publisher::new);
+            let res = if args.no_autosave || args.tutorial {
+                Ok(None)
+            } else if resumed {
+                // The slot was asked for, no other run is offered
+                let path = autosave::autosave_path();
+                Ok(Some(Autosave::new(path, app.game.round)))
+            } else {
+                autosave::start(&mut terminal, &mut app).map(Some)
+            };
+            // Resumed runs have their hand already, puzzles play the one
+            // they were given
+            let fixed_hand = resumed || app.game.settings.puzzle.is_some();
+            let res = match res {
+                Ok(autosave) if app.game.round == 0 && app.tutorial.is_none() && !fixed_hand => {
+                    profile::loadout(&mut terminal, &mut app).map(|_| autosave)
+                }
+                res => res,
+            };
+            res.and_then(|mut autosave| {
+                let res = run_app(
+                    &mut terminal,
+                    app,
+                    publisher.as_mut(),
+                    autosave.as_mut(),
+                    script,
+                    args.tick_ms,
+                );
+                match (
+                    publisher.map(Publisher::finish),
+                    autosave.map(Autosave::finish),
+                ) {
+                    (Some(Err(err)), _) | (_, Some(Err(err))) if res.is_ok() => Err(err),
+                    _ => res,
+                }
+            })
+        }
+    };
+
+    trace::restore_terminal()?;
+
+    if let Err(err) = res {
+        tracing::error!("{:?}", err);
+        println!("{:?}", err)
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{self, play};
+
+    /// A fresh run on the built-in plants, set up like `flags` would on the
+    /// command line.
+    pub(crate) fn game(flags: &[&str]) -> Game {
+        let config = engine::config_from_flags(flags).unwrap();
+        let plants = engine::builtin_plants(&config).unwrap();
+        Game::with_plants(plants, Arc::new(config), 1)
+    }
+
+    /// The first player plants at the cursor and hands over to the second,
+    /// who picks a card and has the cursor on that plant.
+    fn second_player_on_first_plant() -> Game {
+        let mut game = game(&["--players", "2"]);
+        for action in [
+            Action::Place,
+            Action::Place,
+            Action::NextRound,
+            Action::Place,
+        ] {
+            play(&mut game, action);
+        }
+        assert_eq!((game.turn, game.state), (1, State::Placing));
+        game
+    }
+
+    pub(crate) fn tall_grass() -> Plant {
+        game(&[])
+            .id_to_plant
+            .iter()
+            .find(|p| p.name == "Tall Grass")
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn tall_grass_drops_itself_five_times_as_often() {
+        let plant = tall_grass();
+        let mut rng = XorShiftRng::seed_from_u64(7);
+        let rolls = 10_000;
+        let itself = (0..rolls)
+            .filter_map(|_| get_drop(&plant, &[true, true], &mut rng))
+            .filter(|drop| drop.plants.iter().all(|name| name == "Tall Grass"))
+            .count();
+        let share = itself as f32 / rolls as f32;
+        assert!((share - 5.0 / 6.0).abs() < 0.02, "{share}");
+        assert_eq!(
+            plant.drop_probabilities(&[true, true]),
+            [5.0 / 6.0, 1.0 / 6.0]
+        );
+    }
+
+    #[test]
+    fn a_drop_is_always_picked_while_one_can_happen() {
+        let plant = tall_grass();
+        let mut rng = XorShiftRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let drop = get_drop(&plant, &[false, true], &mut rng).unwrap();
+            assert!(drop.plants.contains(&String::from("Shrub")));
+        }
+        assert!(get_drop(&plant, &[false, false], &mut rng).is_none());
+
+        let mut drops = plant.drops.clone();
+        drops.set_chance(0, 0.0);
+        assert_eq!(drops.roll(0.0).unwrap().chance, 1.0);
+        assert_eq!(drops.roll(0.999_999).unwrap().chance, 1.0);
+        drops.set_chance(1, 0.0);
+        assert!(drops.roll(0.0).is_none());
+    }
+
+    #[test]
+    fn drop_tables_save_as_the_plain_list() {
+        let drops = tall_grass().drops;
+        let json = serde_json::to_string(&drops).unwrap();
+        assert_eq!(json, serde_json::to_string(&drops.drops).unwrap());
+        let loaded: DropTable = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.cumulative, drops.cumulative);
+    }
+
+    #[test]
+    fn board_sizes_outside_the_setup_range_are_refused() {
+        for dim in ["0", "1", "41"] {
+            assert!(engine::config_from_flags(&["--dim", dim]).is_err(), "{dim}");
+        }
+        for dim in [setup::MIN_SIZE, setup::MAX_SIZE] {
+            let config = engine::config_from_flags(&["--dim", &dim.to_string()]).unwrap();
+            assert_eq!((config.width, config.height), (dim, dim));
+        }
+        // Like a config file setting `dim = 0`
+        let mut args = Args::try_parse_from(["rogue_forest"]).unwrap();
+        args.dim = 0;
+        assert!(GlobalSetting::load(&args).is_err());
+    }
+
+    #[test]
+    fn players_cant_take_back_each_others_plants() {
+        let mut game = second_player_on_first_plant();
+        let (x, y) = (game.placing.x, game.placing.y);
+        let hand = game.hand.len();
+        play(&mut game, Action::Delete);
+        assert!(matches!(game.tile.get(x, y), Some(Tile::New(p)) if p.owner == 0));
+        assert_eq!(game.hand.len(), hand);
+        assert_eq!(game.placed, 0);
+        assert!(game.unplace(x, y).is_none());
+    }
+
+    #[test]
+    fn players_only_tend_their_own_plants() {
+        let mut game = second_player_on_first_plant();
+        let (x, y) = (game.placing.x, game.placing.y);
+        game.compost = 1;
+        let water = game.water;
+        play(&mut game, Action::Water);
+        play(&mut game, Action::Compost);
+        assert_eq!((game.water, game.compost), (water, 1));
+        assert_eq!(game.watered_at(x, y), 0);
+        assert_eq!(game.plant_at(x, y).unwrap().size_per_turn, 1);
+
+        let Some(Tile::New(planted)) = game.tile.set(x, y, Tile::Empty) else {
+            unreachable!();
+        };
+        game.tile.set(x, y, Tile::Infested(planted, 1));
+        play(&mut game, Action::ClearPest);
+        assert!(matches!(game.tile.get(x, y), Some(Tile::Infested(..))));
+        assert_eq!(game.pest_actions, 0);
+    }
+
+    #[test]
+    fn against_the_cpu_players_only_tend_their_half() {
+        let mut game = game(&["--vs-ai"]);
+        let (x, y) = (game.width() - 1, 0);
+        let mut plant = game.id_to_plant[0].clone();
+        plant.owner = game.turn;
+        game.tile
+            .set(x, y, Tile::Permanent(PlantedPlant::new(plant)));
+        game.state = State::Placing;
+        (game.placing.x, game.placing.y) = (x, y);
+        game.compost = 1;
+        let water = game.water;
+        play(&mut game, Action::Water);
+        play(&mut game, Action::Compost);
+        assert_eq!((game.water, game.compost), (water, 1));
+        assert_eq!(
+            game.notice.as_deref(),
+            Some("The other player's half of the board")
+        );
+    }
+
+    #[test]
+    fn the_board_splits_down_the_middle_against_the_cpu() {
+        let mut split = game(&["--vs-ai"]);
+        let width = split.width();
+        let yours = (0..width)
+            .filter(|&x| split.on_own_side(x))
+            .collect::<Vec<_>>();
+        split.turn = AI_SEAT;
+        let cpus = (0..width)
+            .filter(|&x| split.on_own_side(x))
+            .collect::<Vec<_>>();
+        assert_eq!(yours, (0..width / 2).collect::<Vec<_>>());
+        assert_eq!(cpus, (width - width / 2..width).collect::<Vec<_>>());
+        let solo = game(&[]);
+        assert!((0..width).all(|x| solo.on_own_side(x)));
+    }
+
+    #[test]
+    fn the_cpu_only_plants_on_its_half() {
+        let mut game = game(&["--vs-ai", "--rounds", "6"]);
+        let policy = game.ai.unwrap().policy();
+        let mut planted = 0;
+        for _ in 0..10 * MAX_AI_STEPS {
+            if game.state == State::GameOver {
+                break;
+            }
+            if game.turn != AI_SEAT {
+                play(&mut game, Action::NextRound);
+                continue;
+            }
+            let action = policy(&game);
+            let empty = game
+                .tile
+                .iter_coords()
+                .filter(|&(x, y)| matches!(game.tile.get(x, y), Some(Tile::Empty)))
+                .collect::<Vec<_>>();
+            play(&mut game, action);
+            if action == Action::NextRound {
+                continue;
+            }
+            for (x, _) in empty
+                .into_iter()
+                .filter(|&(x, y)| game.plant_at(x, y).is_some())
+            {
+                assert!(
+                    x >= game.width() - game.width() / 2,
+                    "the CPU planted at {x}"
+                );
+                planted += 1;
+            }
+        }
+        assert_eq!(game.state, State::GameOver);
+        assert!(planted > 0);
+    }
+
+    #[test]
+    fn a_single_player_takes_back_their_plant() {
+        let mut game = game(&[]);
+        let hand = game.hand.len();
+        play(&mut game, Action::Place);
+        play(&mut game, Action::Place);
+        game.on_delete();
+        assert_eq!(game.hand.len(), hand);
+        assert_eq!(game.placed, 0);
+        assert!(game.plant_at(game.placing.x, game.placing.y).is_none());
+    }
+
+    #[test]
+    fn each_player_keeps_their_own_compost() {
+        let mut game = game(&["--players", "2"]);
+        play(&mut game, Action::Compost);
+        assert_eq!(game.compost, 1);
+        play(&mut game, Action::NextRound);
+        assert_eq!((game.turn, game.compost), (1, 0));
+        play(&mut game, Action::Place);
+        play(&mut game, Action::Compost);
+        assert_eq!(game.compost, 0);
+        assert_eq!(
+            game.notice.as_deref(),
+            Some("No compost left, compost a card with c first")
+        );
+        play(&mut game, Action::NextRound);
+        assert_eq!((game.turn, game.compost), (0, 1));
+    }
+
+    #[test]
+    fn saves_keep_both_players() {
+        let mut game = second_player_on_first_plant();
+        play(&mut game, Action::Right);
+        play(&mut game, Action::Place);
+        let json = save::to_json(&game).unwrap();
+        let mut loaded = save::from_json(&json).unwrap().game;
+        loaded.settings = game.settings.clone();
+
+        assert_eq!(save::to_json(&loaded).unwrap(), json);
+        assert_eq!((loaded.turn, loaded.seats.len()), (1, 2));
+        assert_eq!(loaded.seats[0].hand.len(), game.seats[0].hand.len());
+        assert_eq!(loaded.ledger_of(0), game.ledger_of(0));
+        let owners = loaded
+            .tile
+            .iter()
+            .filter_map(Tile::plant)
+            .map(|p| p.owner)
+            .collect::<Vec<usize>>();
+        assert_eq!(owners.len(), 2);
+        assert!(owners.contains(&0) && owners.contains(&1));
+    }
+
+    #[test]
+    fn taking_the_selected_last_card_selects_the_one_before() {
+        let mut game = game(&[]);
+        let last = game.hand.len() - 1;
+        game.choosing.index = Some(last);
+        assert!(game.remove_from_hand(last).is_some());
+        assert_eq!(game.choosing.index, Some(last - 1));
+        assert!(game.selected_card().is_some());
+        assert!(game.remove_from_hand(last).is_none());
+    }
+
+    #[test]
+    fn drops_into_the_hand_keep_the_selected_card() {
+        let mut game = game(&[]);
+        let last = game.hand.len() - 1;
+        game.choosing.index = Some(last);
+        let card = Card::Plant(Box::new(tall_grass()));
+        game.add_to_hand([card.clone(), card]);
+        assert_eq!(game.choosing.index, Some(last));
+        assert_eq!(game.selected_card().unwrap().name(), "Grass");
+    }
+
+    #[test]
+    fn an_empty_hand_selects_nothing_until_cards_come() {
+        let mut game = game(&[]);
+        while game.remove_from_hand(0).is_some() {}
+        assert_eq!(game.choosing.index, None);
+        assert!(game.selected_card().is_none());
+        game.add_to_hand([Card::Plant(Box::new(tall_grass()))]);
+        assert_eq!(game.choosing.index, Some(0));
+        assert_eq!(game.selected_card().unwrap().name(), "Tall Grass");
+    }
+
+    #[test]
+    fn the_plants_list_highlights_the_selected_card() {
+        let mut app = app(&[]);
+        let last = app.game.hand.len() - 1;
+        app.game.choosing.index = Some(last);
+        app.select(Some(last));
+        app.apply(Action::Place);
+        app.apply(Action::Place);
+        assert_eq!(app.game.hand.len(), last);
+        assert_eq!(app.game.choosing.index, Some(last - 1));
+        assert_eq!(app.list_state.selected(), Some(last - 1));
+    }
+
+    #[test]
+    fn plants_are_promoted_after_they_would_have_grown() {
+        let at = |phase| ROUND_PHASES.iter().position(|&p| p == phase).unwrap();
+        assert!(at(RoundPhase::Grow) < at(RoundPhase::Mature));
+        assert!(at(RoundPhase::Mature) < at(RoundPhase::Promote));
+        assert!(at(RoundPhase::Spread) < at(RoundPhase::Promote));
+        assert_eq!(ROUND_PHASES.last(), Some(&RoundPhase::Weather));
+    }
+
+    #[test]
+    fn a_plant_placed_this_round_starts_aging_next_round() {
+        let mut game = game(&["--pest-chance", "0"]);
+        game.weather = Weather::Sunny;
+        let mut plant = game.id_to_plant[0].clone();
+        plant.max_age = 1;
+        game.tile.set(0, 0, Tile::New(PlantedPlant::new(plant)));
+        game.update_game();
+        let p = game
+            .plant_at(0, 0)
+            .expect("placed plants survive their round");
+        assert_eq!((p.age, p.size), (0, 0));
+        assert!(matches!(game.tile.get(0, 0), Some(Tile::Permanent(_))));
+
+        let report = game.update_game();
+        assert_eq!(report.matured.len(), 1);
+        assert_eq!(report.matured[0].tile, "A1");
+        assert!(game.plant_at(0, 0).is_none());
+    }
+
+    #[test]
+    fn harvests_go_in_row_major_order() {
+        let mut game = game(&["--pest-chance", "0", "--drop-chance", "1"]);
+        let mut grass = game.id_to_plant[0].clone();
+        // Only the drop that needs Tall Grass next to it is left
+        grass.drops = grass
+            .drops
+            .iter()
+            .filter(|drop| drop.requires_adjacent.is_some())
+            .cloned()
+            .collect();
+        let ripe = |plant: &Plant| {
+            let mut planted = PlantedPlant::new(plant.clone());
+            planted.age = planted.max_age;
+            Tile::Permanent(planted)
+        };
+        let tiles = [
+            (0, 0, ripe(&grass)),
+            (1, 0, ripe(&tall_grass())),
+            (2, 1, ripe(&tall_grass())),
+            (3, 1, ripe(&grass)),
+        ];
+        for (x, y, tile) in tiles {
+            game.tile.set(x, y, tile);
+        }
+
+        let mut report = RoundReport::default();
+        game.mature_plants(&mut report);
+        let tiles = report
+            .matured
+            .iter()
+            .map(|m| m.tile.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(tiles, ["A1", "B1", "C2", "D2"]);
+        // A1 still had its neighbor, D2 lost it to the harvest before
+        assert_eq!(report.matured[0].cards, ["Tall Grass", "Tall Grass"]);
+        assert!(report.matured[3].cards.is_empty());
+    }
+
+    #[test]
+    fn points_show_with_one_decimal_at_most() {
+        assert_eq!(format_points(23.7 + 1e-6), "23.7");
+        assert_eq!(format_points(2.0), "2");
+        assert_eq!(format_points(0.25), "0.2");
+        assert_eq!(format_points(-0.01), "0");
+        assert_eq!(format_points(-1.5), "-1.5");
+    }
+
+    #[test]
+    fn many_small_harvests_dont_drift_on_screen() {
+        let mut game = game(&[]);
+        for _ in 0..1000 {
+            game.credit(0, ScoreCategory::Harvest, 0.1);
+        }
+        assert_ne!(game.points(), 100.0);
+        assert_eq!(game.score_display(), "100");
+        // Saves keep the exact sum
+        let json = save::to_json(&game).unwrap();
+        let loaded = save::from_json(&json).unwrap().game;
+        assert_eq!(loaded.points().to_bits(), game.points().to_bits());
+    }
+
+    /// The states `actions` pass through one by one.
+    fn states(game: &mut Game, actions: &[Action]) -> Vec<State> {
+        actions
+            .iter()
+            .map(|&action| {
+                play(game, action);
+                game.state
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tab_goes_around_the_panels_in_screen_order() {
+        let mut game = game(&[]);
+        let tabs = states(&mut game, &[Action::Tab; 3]);
+        assert_eq!(tabs, [State::NextRound, State::Inspect, State::Choosing]);
+        let back = states(&mut game, &[Action::BackTab; 3]);
+        assert_eq!(back, [State::Inspect, State::NextRound, State::Choosing]);
+    }
+
+    #[test]
+    fn tabbing_off_the_board_drops_the_chosen_card() {
+        let mut game = game(&[]);
+        play(&mut game, Action::Place);
+        assert_eq!(game.state, State::Placing);
+        play(&mut game, Action::Tab);
+        assert_eq!(game.state, State::Choosing);
+        assert!(game.choosing.choice.is_none());
+
+        play(&mut game, Action::Place);
+        let back = states(&mut game, &[Action::BackTab, Action::Tab]);
+        assert_eq!(back, [State::NextRound, State::Inspect]);
+        // Without a card the board is only looked at
+        play(&mut game, Action::Place);
+        assert_eq!(game.state, State::Inspect);
+        assert!(game.tile.iter().all(|tile| matches!(tile, Tile::Empty)));
+    }
+
+    #[test]
+    fn tab_leaves_drafts_and_finished_runs_alone() {
+        let mut game = game(&[]);
+        for state in [State::Drafting, State::GameOver] {
+            game.state = state;
+            game.on_tab();
+            game.on_back_tab();
+            assert_eq!(game.state, state);
+        }
+    }
+
+    #[test]
+    fn each_weather_grows_the_board_its_own_way() {
+        // Size and age of a plant from last round, size of one placed this
+        // round or None once it is gone
+        let round = |weather| {
+            let mut game = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+            game.weather = weather;
+            let plant = tall_grass();
+            game.tile
+                .set(0, 0, Tile::Permanent(PlantedPlant::new(plant.clone())));
+            game.tile.set(1, 0, Tile::New(PlantedPlant::new(plant)));
+            let report = game.update_game();
+            let old = game.plant_at(0, 0).unwrap();
+            let new = game.plant_at(1, 0).map(|p| p.size);
+            ((old.size, old.age), new, report.events)
+        };
+        assert_eq!(round(Weather::Sunny).0, (1, 1));
+        assert_eq!(round(Weather::Sunny).1, Some(0));
+        assert_eq!(round(Weather::Rain).0, (2, 1));
+        assert_eq!(round(Weather::Rain).1, Some(1));
+        assert_eq!(round(Weather::Drought).0, (0, 1));
+        assert_eq!(round(Weather::Drought).1, Some(0));
+        let (old, new, events) = round(Weather::Storm);
+        assert_eq!((old, new), ((1, 1), None));
+        assert!(events.contains(&"The storm uprooted Tall Grass at B1".to_string()));
+    }
+
+    #[test]
+    fn the_forecast_becomes_the_next_weather() {
+        let mut game = game(&[]);
+        for _ in 0..10 {
+            let forecast = game.forecast;
+            game.update_game();
+            assert_eq!(game.weather, forecast);
+        }
+    }
+
+    #[test]
+    fn tiny_hands_put_drops_past_three_cards_in_the_reserve() {
+        let mut game = game(&["--mutator", "tiny-hands"]);
+        assert_eq!(game.hand.len(), 2);
+        let card = Card::Plant(Box::new(tall_grass()));
+        let names = game.give(0, vec![card; 3]);
+        assert_eq!(names.len(), 3);
+        assert_eq!(
+            (game.hand.len(), game.reserve.len()),
+            (mutators::TINY_HAND_LIMIT, 2)
+        );
+    }
+
+    #[test]
+    fn fast_forward_takes_a_round_off_every_plant() {
+        let (plain, fast) = (game(&[]), game(&["--mutator", "fast-forward"]));
+        for (plant, faster) in plain.id_to_plant.iter().zip(&fast.id_to_plant) {
+            assert_eq!(faster.max_age, plant.max_age.saturating_sub(1).max(1));
+        }
+        assert!(fast.id_to_plant.iter().all(|p| p.max_age >= 1));
+    }
+
+    #[test]
+    fn barren_boards_start_with_a_quarter_of_rocks() {
+        let rocks = |flags: &[&str]| {
+            let game = game(flags);
+            game.terrain.iter().filter(|&&t| t == Terrain::Rock).count()
+        };
+        assert_eq!(rocks(&[]), 0);
+        assert_eq!(rocks(&["--mutator", "barren"]), 9);
+        assert_eq!(rocks(&["--mutator", "barren,barren"]), 9);
+    }
+
+    #[test]
+    fn the_title_names_the_mutators() {
+        let mut app = app(&["--mutator", "barren,generous"]);
+        let rows = screen(&mut app, 130, 36);
+        assert!(
+            rows[1].contains(" Forest (Normal, barren, generous) //"),
+            "{}",
+            rows[1]
+        );
+    }
+
+    #[test]
+    fn generous_runs_drop_twice_as_often() {
+        let plain = game(&["--drop-chance", "0.3"]);
+        let generous = game(&["--drop-chance", "0.3", "--mutator", "generous"]);
+        assert_eq!(generous.settings.difficulty.drop_chance, 0.6);
+        for (hybrid, doubled) in plain.hybrids.iter().zip(&generous.hybrids) {
+            assert_eq!(doubled.chance, (hybrid.chance * 2.0).min(1.0));
+        }
+        let capped = game(&["--drop-chance", "0.8", "--mutator", "generous"]);
+        assert_eq!(capped.settings.difficulty.drop_chance, 1.0);
+    }
+
+    #[test]
+    fn unknown_mutators_list_the_known_ones() {
+        let err = engine::config_from_flags(&["--mutator", "barren,nope"]).unwrap_err();
+        assert!(
+            err.contains("tiny-hands, fast-forward, barren, generous"),
+            "{err}"
+        );
+    }
+
+    /// A manual-harvest run with a Grass at A1 that just got ripe.
+    fn ripe_grass() -> Game {
+        let mut game = game(&[
+            "--harvest-mode",
+            "manual",
+            "--overripe-rounds",
+            "2",
+            "--pest-chance",
+            "0",
+            "--cloud-chance",
+            "0",
+        ]);
+        game.weather = Weather::Sunny;
+        game.forecast = Weather::Sunny;
+        let mut grass = PlantedPlant::new(game.id_to_plant[0].clone());
+        (grass.age, grass.size) = (grass.max_age, 2);
+        game.tile.set(0, 0, Tile::Permanent(grass));
+        game.state = State::Inspect;
+        (game.placing.x, game.placing.y) = (0, 0);
+        game
+    }
+
+    #[test]
+    fn overripe_plants_can_be_picked_on_their_last_round() {
+        let mut game = ripe_grass();
+        game.update_game();
+        let overripe = game.plant_at(0, 0).expect("ripe plants wait to be picked");
+        assert_eq!(game.overripe_left(overripe), Some(1));
+        assert_eq!(overripe.size, 2);
+        play(&mut game, Action::Harvest);
+        assert_eq!(game.notice.as_deref(), Some("Picked Grass for 2 points"));
+        assert_eq!(game.ledger.get(ScoreCategory::Harvest), 2.0);
+        assert!(game.plant_at(0, 0).is_none());
+    }
+
+    #[test]
+    fn overripe_plants_wither_once_the_window_is_missed() {
+        let mut game = ripe_grass();
+        game.update_game();
+        let report = game.update_game();
+        assert!(report.matured.is_empty());
+        assert!(report
+            .events
+            .contains(&"Grass at A1 withered unpicked".to_string()));
+        assert!(game.plant_at(0, 0).is_none());
+        assert_eq!(game.ledger.get(ScoreCategory::Harvest), 0.0);
+    }
+
+    #[test]
+    fn only_ripe_plants_can_be_picked() {
+        let mut manual = ripe_grass();
+        if let Some(Tile::Permanent(p)) = manual.tile.get_mut(0, 0) {
+            p.age = 1;
+        }
+        play(&mut manual, Action::Harvest);
+        assert_eq!(manual.notice.as_deref(), Some("Grass is ripe in 1 rounds"));
+        assert!(manual.plant_at(0, 0).is_some());
+
+        let mut auto = game(&[]);
+        auto.state = State::Inspect;
+        play(&mut auto, Action::Harvest);
+        assert_eq!(
+            auto.notice.as_deref(),
+            Some("Plants are harvested on their own in this run")
+        );
+    }
+
+    #[test]
+    fn quick_place_plants_nearest_to_the_center() {
+        let mut game = game(&["--actions", "0"]);
+        play(&mut game, Action::QuickPlace);
+        assert!(matches!(game.tile.get(3, 3), Some(Tile::New(_))));
+        assert_eq!(game.log.last().unwrap(), "[0] Placed Grass at D4");
+        assert_eq!(game.plant_stats["Grass"].placed, 1);
+        assert_eq!(game.state, State::Choosing);
+
+        // Rocks are passed over like taken tiles
+        game.terrain.set(4, 3, Terrain::Rock);
+        play(&mut game, Action::QuickPlace);
+        assert!(matches!(game.tile.get(4, 4), Some(Tile::New(_))));
+    }
+
+    #[test]
+    fn quick_place_without_a_free_tile_says_so() {
+        let mut game = game(&["--dim", "2"]);
+        for (x, y) in game.tile.iter_coords() {
+            game.terrain.set(x, y, Terrain::Rock);
+        }
+        play(&mut game, Action::QuickPlace);
+        assert_eq!(game.notice.as_deref(), Some("No free tile to plant on"));
+        assert_eq!((game.state, game.hand.len()), (State::Choosing, 2));
+    }
+
+    #[test]
+    fn the_selection_is_empty_exactly_when_the_hand_is() {
+        let mut rng = XorShiftRng::seed_from_u64(11);
+        let card = Card::Plant(Box::new(tall_grass()));
+        for run in 0..20 {
+            let mut game = game(&["--actions", "0", "--dim", "4"]);
+            for step in 0..60 {
+                match rng.gen_range(0, 10) {
+                    0 => game.add_to_hand(vec![card.clone(); rng.gen_range(1, 4)]),
+                    1 => {
+                        let idx = rng.gen_range(0, game.hand.len() + 1);
+                        game.remove_from_hand(idx);
+                    }
+                    2 => game.choosing.grouped = !game.choosing.grouped,
+                    roll => {
+                        let action = match roll {
+                            3 => Action::QuickPlace,
+                            4 => Action::Compost,
+                            5 => Action::Up,
+                            6 => Action::Down,
+                            7 => Action::Select(rng.gen_range(0, 4)),
+                            8 => Action::NextRound,
+                            _ => Action::Place,
+                        };
+                        play(&mut game, action);
+                    }
+                }
+                let index = game.choosing.index;
+                assert_eq!(
+                    index.is_none(),
+                    game.hand.is_empty(),
+                    "run {run} step {step}"
+                );
+                assert!(
+                    index.is_none_or(|i| i < game.hand.len()),
+                    "run {run} step {step}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn an_empty_hand_says_to_end_the_round() {
+        let mut app = app(&[]);
+        while app.game.remove_from_hand(0).is_some() {}
+        app.select(app.game.choosing.index);
+        let rows = screen(&mut app, 130, 36);
+        assert!(
+            rows[2].contains("║Hand empty — end the round"),
+            "{}",
+            rows[2]
+        );
+    }
+
+    #[test]
+    fn boosts_grow_the_plant_until_they_run_out() {
+        let mut forest = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.max_age = 10;
+        effects::add(
+            &mut grass.effects,
+            StatusEffect {
+                kind: effects::EffectKind::GrowthBoost,
+                magnitude: 2.0,
+                remaining_rounds: 2,
+            },
+        );
+        forest.tile.set(0, 0, Tile::Permanent(grass));
+        let size = |forest: &Game| forest.tile.get(0, 0).and_then(Tile::planted).unwrap().size;
+        let mut sizes = Vec::new();
+        for _ in 0..3 {
+            (forest.weather, forest.forecast) = (Weather::Sunny, Weather::Sunny);
+            forest.update_game();
+            sizes.push(size(&forest));
+        }
+        assert_eq!(sizes, [3, 6, 7]);
+        assert!(forest
+            .tile
+            .get(0, 0)
+            .and_then(Tile::planted)
+            .unwrap()
+            .effects
+            .is_empty());
+    }
+
+    #[test]
+    fn the_info_panel_lists_effects_with_their_rounds() {
+        let mut forest = game(&[]);
+        let mut grass = PlantedPlant::new(tall_grass());
+        for (kind, remaining_rounds) in [
+            (effects::EffectKind::PointMultiplier, 3),
+            (effects::EffectKind::GrowthHalt, 1),
+        ] {
+            let effect = StatusEffect {
+                kind,
+                magnitude: 2.0,
+                remaining_rounds,
+            };
+            effects::add(&mut grass.effects, effect);
+        }
+        forest.tile.set(0, 0, Tile::Permanent(grass.clone()));
+        let theme = Theme::new(ThemeName::Default);
+        let lines = plant_info(&grass, 0.0, Some((0, 0)), &forest, &theme)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect::<Vec<String>>();
+        assert!(
+            lines.contains(&"Effect: x2 points, 3 rounds left".to_string()),
+            "{lines:?}"
+        );
+        assert!(
+            lines.contains(&"Effect: No growth, last round".to_string()),
+            "{lines:?}"
+        );
+        assert!(
+            lines.contains(&"Size per Turn: 0 (base 1)".to_string()),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn tile_text_and_frame_count_down_together() {
+        let mut forest = game(&["--cloud-chance", "0"]);
+        forest.clouds.clear();
+        let theme = Theme::new(ThemeName::Default);
+        let mut grass = PlantedPlant::new(forest.id_to_plant[0].clone());
+        grass.max_age = 8;
+        for age in 0..8 {
+            grass.age = age;
+            forest.tile.set(1, 1, Tile::Permanent(grass.clone()));
+            let view = BoardViewModel::new(&forest, &theme, true);
+            let tile = view.tiles.iter().find(|t| (t.x, t.y) == (1, 1)).unwrap();
+            assert_eq!(tile.style.fg, Some(tile.frame), "age {age}");
+            let color = theme.lifespan_color(age, 8, forest.settings.maturing_at, grass.color());
+            assert_eq!(tile.frame, color, "age {age}");
+            assert_eq!(
+                tile.text(TileDetail::Full),
+                format!("{} {}", grass.short_display, 8 - age)
+            );
+            assert_eq!(
+                tile.text(TileDetail::Compact),
+                grass.short_display.to_string()
+            );
+        }
+    }
+
+    /// Where the cursor ends up after `action` from each corner of a 4x3
+    /// board.
+    fn from_corners(
+        wrap_cursor: bool,
+        action: fn(&mut PlacingState, &GlobalSetting),
+    ) -> Vec<(usize, usize)> {
+        let settings = GlobalSetting {
+            wrap_cursor,
+            ..GlobalSetting::for_board(4, 3)
+        };
+        [(0, 0), (3, 0), (0, 2), (3, 2)]
+            .into_iter()
+            .map(|(x, y)| {
+                let mut placing = PlacingState { x, y };
+                action(&mut placing, &settings);
+                (placing.x, placing.y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_cursor_stops_at_every_edge_by_default() {
+        assert_eq!(
+            from_corners(false, PlacingState::on_left),
+            [(0, 0), (2, 0), (0, 2), (2, 2)]
+        );
+        assert_eq!(
+            from_corners(false, PlacingState::on_right),
+            [(1, 0), (3, 0), (1, 2), (3, 2)]
+        );
+        assert_eq!(
+            from_corners(false, PlacingState::on_up),
+            [(0, 1), (3, 1), (0, 2), (3, 2)]
+        );
+        assert_eq!(
+            from_corners(false, PlacingState::on_down),
+            [(0, 0), (3, 0), (0, 1), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn the_cursor_wraps_around_every_edge() {
+        assert_eq!(
+            from_corners(true, PlacingState::on_left),
+            [(3, 0), (2, 0), (3, 2), (2, 2)]
+        );
+        assert_eq!(
+            from_corners(true, PlacingState::on_right),
+            [(1, 0), (0, 0), (1, 2), (0, 2)]
+        );
+        assert_eq!(
+            from_corners(true, PlacingState::on_up),
+            [(0, 1), (3, 1), (0, 0), (3, 0)]
+        );
+        assert_eq!(
+            from_corners(true, PlacingState::on_down),
+            [(0, 2), (3, 2), (0, 1), (3, 1)]
+        );
+    }
+
+    /// The terminal cell in the middle of tile `x`, `y`.
+    fn cell_of(geometry: &BoardGeometry, x: usize, y: usize) -> (u16, u16) {
+        let column = (x as f64 + 0.5 - geometry.x_bounds[0]) * geometry.cols();
+        let row = (geometry.y_bounds[1] - y as f64 - 0.5) * geometry.rows();
+        (
+            geometry.inner.x + column as u16,
+            geometry.inner.y + row as u16,
+        )
+    }
+
+    #[test]
+    fn tiles_stay_square_and_the_board_is_centered() {
+        let view = Viewport::whole(6, 6);
+        for (width, height) in [(80, 20), (200, 30), (40, 60), (61, 31)] {
+            let geometry = BoardGeometry::new(&view, Rect::new(3, 2, width, height));
+            assert!((geometry.cols() - geometry.rows() * CELL_ASPECT).abs() < 1e-9);
+            let [left, right] = geometry.x_bounds;
+            let [bottom, top] = geometry.y_bounds;
+            assert!((-left - (right - 6.0)).abs() < 1e-9);
+            assert!((-bottom - (top - 6.0)).abs() < 1e-9);
+            // The board fills one of the directions
+            assert!(
+                left.abs() < 1e-9 || bottom.abs() < 1e-9,
+                "{}x{}",
+                width,
+                height
+            );
+        }
+    }
+
+    #[test]
+    fn the_cell_in_a_tile_maps_back_to_it() {
+        let zoomed = Viewport {
+            x: 1.0,
+            y: 2.0,
+            zoom: 2.0,
+            ..Viewport::whole(6, 6)
+        };
+        for view in [Viewport::whole(6, 6), Viewport::whole(9, 4), zoomed] {
+            for (width, height) in [(80, 20), (200, 30), (40, 60)] {
+                let geometry = BoardGeometry::new(&view, Rect::new(3, 2, width, height));
+                let [left, right] = view.x_bounds();
+                let [bottom, top] = view.y_bounds();
+                for x in left as usize..right as usize {
+                    for y in bottom as usize..top as usize {
+                        let (column, row) = cell_of(&geometry, x, y);
+                        assert_eq!(geometry.tile_at(column, row), Some((x, y)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clicks_on_the_margin_or_the_border_miss() {
+        // Far wider than the board needs, so both sides are margin
+        let geometry = BoardGeometry::new(&Viewport::whole(4, 4), Rect::new(0, 0, 34, 10));
+        assert_eq!(geometry.tile_at(0, 5), None);
+        assert_eq!(geometry.tile_at(5, 0), None);
+        assert_eq!(geometry.tile_at(3, 5), None);
+        assert_eq!(geometry.tile_at(30, 5), None);
+        assert_eq!(geometry.tile_at(17, 5).map(|(x, _)| x), Some(2));
+        assert_eq!(geometry.tile_at(40, 5), None);
+    }
+
+    #[test]
+    fn a_click_moves_the_cursor_to_the_tile_under_it() {
+        let mut app = app(&[]);
+        app.game.state = State::Placing;
+        screen(&mut app, 130, 36);
+        let geometry = app.board_geometry.unwrap();
+        let (column, row) = cell_of(&geometry, 1, 2);
+        let (x, y) = geometry.tile_at(column, row).unwrap();
+        app.apply(Action::MoveTo(x, y));
+        assert_eq!((app.game.placing.x, app.game.placing.y), (1, 2));
+        // Off the board, from a script
+        app.apply(Action::MoveTo(99, 0));
+        assert_eq!((app.game.placing.x, app.game.placing.y), (1, 2));
+        assert_eq!("MoveTo B3".parse::<Action>(), Ok(Action::MoveTo(1, 2)));
+        assert!("MoveTo 3B".parse::<Action>().is_err());
+    }
+
+    #[test]
+    fn a_board_one_tile_wide_keeps_the_cursor_in_place() {
+        for wrap in [false, true] {
+            assert_eq!(step(0, true, 1, wrap), 0);
+            assert_eq!(step(0, false, 1, wrap), 0);
+        }
+    }
+
+    #[test]
+    fn edge_jumps_ignore_the_wrap_setting() {
+        for wrap_cursor in [false, true] {
+            let settings = GlobalSetting {
+                wrap_cursor,
+                ..GlobalSetting::for_board(4, 3)
+            };
+            let mut placing = PlacingState { x: 1, y: 1 };
+            placing.on_edge(Action::TopEdge, &settings);
+            placing.on_edge(Action::RightEdge, &settings);
+            assert_eq!((placing.x, placing.y), (3, 2));
+            placing.on_edge(Action::BottomEdge, &settings);
+            placing.on_edge(Action::LeftEdge, &settings);
+            assert_eq!((placing.x, placing.y), (0, 0));
+        }
+    }
+
+    #[test]
+    fn wrap_cursor_comes_from_the_command_line() {
+        let mut forest = game(&["--wrap-cursor", "--dim", "4"]);
+        forest.state = State::Placing;
+        forest.placing = PlacingState { x: 3, y: 3 };
+        play(&mut forest, Action::Right);
+        play(&mut forest, Action::Up);
+        assert_eq!((forest.placing.x, forest.placing.y), (0, 0));
+    }
+
+    /// Puts a grass that matures in the coming round at (x, y).
+    fn grass_maturing_next(forest: &mut Game, x: usize, y: usize) {
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = grass.max_age - 1;
+        forest.tile.set(x, y, Tile::Permanent(grass));
+    }
+
+    #[test]
+    fn the_streak_multiplier_tops_out_at_twice_the_points() {
+        assert_eq!(streak_multiplier(0), 1.0);
+        assert_eq!(streak_multiplier(4), 1.4);
+        assert_eq!(streak_multiplier(10), 2.0);
+        assert_eq!(streak_multiplier(25), 2.0);
+    }
+
+    #[test]
+    fn the_streak_grows_with_harvests_and_breaks_on_an_empty_round() {
+        let mut forest = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+        let mut streaks = Vec::new();
+        for plant in [true, true, true, false, true] {
+            if plant {
+                grass_maturing_next(&mut forest, 0, 0);
+            }
+            let report = forest.update_game();
+            streaks.push((report.streak, report.streak_grew(), report.streak_broke()));
+        }
+        assert_eq!(
+            streaks,
+            [
+                (1, true, false),
+                (2, true, false),
+                (3, true, false),
+                (0, false, true),
+                (1, true, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn harvests_on_a_long_streak_score_twice_at_most() {
+        let mut forest = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+        forest.streak = 30;
+        grass_maturing_next(&mut forest, 0, 0);
+        forest.update_game();
+        let base = forest.ledger.get(ScoreCategory::Harvest);
+        assert!(base > 0.0);
+        assert_eq!(forest.ledger.get(ScoreCategory::Streak), base);
+        assert_eq!(forest.streak, 31);
+    }
+
+    #[test]
+    fn the_title_shows_the_streak_and_its_multiplier() {
+        let mut app = app(&[]);
+        assert!(!screen(&mut app, 130, 36)[1].contains("Streak"));
+        app.game.streak = 3;
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[1].contains("// Streak: 3 x1.3 //"), "{}", rows[1]);
+    }
+
+    /// Every tile as text, row by row, to see the layout is kept.
+    fn layout(forest: &Game) -> Vec<String> {
+        forest.tile.iter().map(|tile| tile.to_string()).collect()
+    }
+
+    #[test]
+    fn taking_a_plant_back_leaves_the_rest_of_the_board_in_place() {
+        let mut forest = game(&["--actions", "0"]);
+        forest.add_to_hand(vec![Card::Plant(Box::new(tall_grass())); 2]);
+        for _ in 0..3 {
+            play(&mut forest, Action::QuickPlace);
+        }
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = 1;
+        forest.tile.set(0, 5, Tile::Permanent(grass));
+        let planted = forest
+            .tile
+            .iter_coords()
+            .filter(|&(x, y)| matches!(forest.tile.get(x, y), Some(Tile::New(_))));
+        let (x, y) = planted.last().unwrap();
+        let mut expected = layout(&forest);
+        expected[y * forest.settings.width + x] = Tile::Empty.to_string();
+        let (hand, placed) = (forest.hand.len(), forest.placed);
+
+        forest.state = State::Placing;
+        forest.placing = PlacingState { x, y };
+        play(&mut forest, Action::Delete);
+        assert_eq!(layout(&forest), expected);
+        assert_eq!((forest.hand.len(), forest.placed), (hand + 1, placed - 1));
+        assert_eq!(forest.choosing.index, Some(forest.hand.len() - 1));
+        let msg = format!(
+            "Took {} at {} back into the hand",
+            forest.hand[hand].name(),
+            tile_name(x, y)
+        );
+        assert_eq!(forest.notice.as_deref(), Some(msg.as_str()));
+    }
+
+    #[test]
+    fn older_plants_and_free_tiles_cant_be_taken_back() {
+        let mut forest = game(&[]);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = 1;
+        forest.tile.set(0, 0, Tile::Permanent(grass));
+        let (board, hand) = (layout(&forest), forest.hand.len());
+        forest.state = State::Placing;
+        for x in [0, 1] {
+            forest.placing = PlacingState { x, y: 0 };
+            play(&mut forest, Action::Delete);
+            assert_eq!(layout(&forest), board);
+            assert_eq!(forest.hand.len(), hand);
+            let msg = "Only plants placed this round can be taken back";
+            assert_eq!(forest.notice.as_deref(), Some(msg));
+        }
+        assert!(forest.unplace(0, 0).is_none());
+        assert!(matches!(forest.tile.get(0, 0), Some(Tile::Permanent(_))));
+    }
+
+    #[test]
+    fn invalid_plant_colors_are_refused_by_name() {
+        let mut plants = builtin_plants();
+        plants[1].color = Some("#12345".into());
+        let err = PlantSet::new(plants, Vec::new(), Vec::new(), &[])
+            .err()
+            .unwrap();
+        let name = &builtin_plants()[1].name;
+        assert!(
+            err.contains(&format!("Plant <{name}> has an invalid color `#12345`")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn placing_maturing_and_overripe_go_over_a_plants_own_color() {
+        let own = Color::Rgb(1, 2, 3);
+        let mut forest = game(&["--harvest-mode", "manual", "--overripe-rounds", "2"]);
+        forest.clouds.clear();
+        let theme = Theme::new(ThemeName::Default);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.color = Some("#010203".into());
+        grass.max_age = 8;
+        let aged = |age: u32| {
+            let mut grass = grass.clone();
+            grass.age = age;
+            Tile::Permanent(grass)
+        };
+        let tiles = [
+            (Tile::New(grass.clone()), theme.new_plant),
+            (aged(0), own),
+            (aged(5), own),
+            (aged(6), theme.maturing),
+            (aged(8), theme.overripe),
+        ];
+        for (tile, expected) in tiles {
+            let text = tile.to_string();
+            forest.tile.set(2, 2, tile);
+            let view = BoardViewModel::new(&forest, &theme, true);
+            let tile = view.tiles.iter().find(|t| (t.x, t.y) == (2, 2)).unwrap();
+            assert_eq!(tile.style.fg, Some(expected), "{text}");
+        }
+    }
+
+    #[test]
+    fn a_sprint_goes_ten_rounds_with_nothing_to_play() {
+        for (mode, rounds) in [("standard", 1), ("sprint", mode::SPRINT_ROUNDS)] {
+            let mut forest = game(&["--mode", mode, "--pest-chance", "0"]);
+            forest.hand.clear();
+            forest.keep_selection();
+            let mut played = 0;
+            while forest.state != State::GameOver {
+                forest.update_game();
+                played += 1;
+                assert!(played <= 20, "{mode}");
+            }
+            assert_eq!(played, rounds, "{mode}");
+        }
+    }
+
+    #[test]
+    fn endless_runs_have_no_limit_and_drop_less() {
+        let mut forest = game(&["--mode", "endless", "--rounds", "5"]);
+        assert_eq!(forest.rounds_left(), None);
+        let base = forest.settings.difficulty.drop_chance;
+        forest.round = 25;
+        assert_eq!(forest.drop_chance(), base * 0.95 * 0.95);
+    }
+
+    #[test]
+    fn modes_keep_their_own_high_scores() {
+        let keys = ["standard", "sprint", "endless"]
+            .map(|mode| scores::board_key(&game(&["--mode", mode, "--seed", "7"]).settings));
+        assert_eq!(keys[0], "6x6");
+        assert_eq!(keys[1], "6x6, 10 rounds, sprint, seed 7");
+        assert_eq!(keys[2], "6x6, endless");
+    }
+
+    #[test]
+    fn the_title_names_the_mode() {
+        let mut app = app(&["--mode", "endless"]);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[1].contains("Forest (Normal, Endless)"), "{}", rows[1]);
+    }
+
+    #[test]
+    fn saves_keep_the_mode() {
+        let forest = game(&["--mode", "sprint"]);
+        let json = serde_json::to_string(&forest).unwrap();
+        let loaded = serde_json::from_str::<Game>(&json).unwrap();
+        assert_eq!(loaded.mode, GameMode::Sprint);
+    }
+
+    #[test]
+    fn dev_and_scripted_runs_only_earn_achievements_when_allowed() {
+        let earns = |flags: &[&str]| {
+            let args =
+                Args::try_parse_from(std::iter::once("rogue_forest").chain(flags.iter().copied()));
+            earns_achievements(&args.unwrap())
+        };
+        assert!(earns(&[]));
+        assert!(!earns(&["--dev"]));
+        assert!(!earns(&["--script", "moves.txt"]));
+        assert!(earns(&["--dev", "--allow-achievements"]));
+        assert!(earns(&["--script", "moves.txt", "--allow-achievements"]));
+    }
+
+    #[test]
+    fn hands_without_a_tile_to_go_on_have_no_legal_move() {
+        let mut forest = game(&["--dim", "2", "--actions", "0"]);
+        assert!(forest.has_legal_move());
+        for (x, y) in forest.tile.iter_coords() {
+            forest.terrain.set(x, y, Terrain::Rock);
+        }
+        assert!(!forest.has_legal_move());
+        assert!(forest.out_of_moves());
+        // Items need a plant to go on, a pest is always worth an action
+        forest.terrain.set(0, 0, Terrain::Plain);
+        forest
+            .tile
+            .set(0, 0, Tile::Permanent(PlantedPlant::new(tall_grass())));
+        assert!(!forest.has_legal_move());
+        forest.add_to_hand(items::all().into_iter().map(Card::Item));
+        assert!(forest.has_legal_move());
+        forest.hand.retain(|card| matches!(card, Card::Plant(_)));
+        forest
+            .tile
+            .set(0, 0, Tile::Infested(PlantedPlant::new(tall_grass()), 0));
+        assert!(forest.has_legal_move());
+    }
+
+    #[test]
+    fn full_boards_and_spent_actions_leave_no_legal_move() {
+        let mut forest = game(&["--dim", "2", "--actions", "0"]);
+        for (x, y) in forest.tile.iter_coords() {
+            forest
+                .tile
+                .set(x, y, Tile::New(PlantedPlant::new(tall_grass())));
+        }
+        assert!(!forest.has_legal_move());
+
+        let mut forest = game(&["--actions", "1"]);
+        assert!(forest.has_legal_move());
+        play(&mut forest, Action::QuickPlace);
+        assert!(!forest.hand.is_empty());
+        assert!(!forest.has_legal_move());
+    }
+
+    #[test]
+    fn ripe_plants_to_pick_are_a_legal_move() {
+        let mut forest = ripe_grass();
+        forest.hand.clear();
+        forest.keep_selection();
+        forest.state = State::Choosing;
+        assert!(forest.has_legal_move());
+        forest.tile.set(0, 0, Tile::Empty);
+        assert!(!forest.has_legal_move());
+    }
+
+    #[test]
+    fn space_ends_the_round_once_nothing_is_left_to_do() {
+        let mut forest = game(&["--dim", "2", "--actions", "0"]);
+        rocks_but_one(&mut forest);
+        play(&mut forest, Action::QuickPlace);
+        assert!(forest.out_of_moves() && !forest.hand.is_empty());
+        assert_eq!(forest.round, 0);
+        play(&mut forest, Action::Place);
+        assert_eq!(forest.round, 1);
+    }
+
+    /// Rocks on every tile but the first.
+    fn rocks_but_one(forest: &mut Game) {
+        for (x, y) in forest.tile.iter_coords().skip(1) {
+            forest.terrain.set(x, y, Terrain::Rock);
+        }
+    }
+
+    /// Young grass on `tiles`, none of it ripe for a while.
+    fn young_grass(forest: &mut Game, tiles: &[(usize, usize)]) {
+        for &(x, y) in tiles {
+            let mut grass = PlantedPlant::new(tall_grass());
+            grass.max_age = 10;
+            forest.tile.set(x, y, Tile::Permanent(grass));
+        }
+    }
+
+    fn passives(report: &RoundReport) -> Vec<(String, f32)> {
+        report
+            .passives
+            .iter()
+            .map(|p| (p.name.clone(), p.points))
+            .collect()
+    }
+
+    #[test]
+    fn every_plant_on_the_board_scores_at_the_end_of_a_round() {
+        let mut forest = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+        young_grass(&mut forest, &[(0, 0), (1, 0), (2, 0)]);
+        let report = forest.update_game();
+        assert_eq!(
+            passives(&report),
+            [("3 plants on the board".to_string(), 0.3)]
+        );
+        assert!((forest.ledger.get(ScoreCategory::Passives) - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn plants_harvested_in_the_round_dont_count_for_the_board() {
+        let mut forest = game(&["--dim", "2", "--pest-chance", "0", "--cloud-chance", "0"]);
+        young_grass(&mut forest, &[(0, 0), (1, 0), (0, 1)]);
+        grass_maturing_next(&mut forest, 1, 1);
+        let report = forest.update_game();
+        assert_eq!(report.matured.len(), 1);
+        assert!(!forest.board_full());
+        assert_eq!(passives(&report)[0].0, "3 plants on the board");
+        assert_eq!(passives(&report).len(), 1);
+    }
+
+    #[test]
+    fn a_full_board_earns_a_bonus_on_top() {
+        let mut forest = game(&["--dim", "2", "--pest-chance", "0", "--cloud-chance", "0"]);
+        young_grass(&mut forest, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        let report = forest.update_game();
+        let full = ("Full board".to_string(), FULL_BOARD_BONUS);
+        assert_eq!(
+            passives(&report),
+            [("4 plants on the board".to_string(), 0.4), full]
+        );
+    }
+
+    #[test]
+    fn no_passives_leaves_only_the_harvests() {
+        let flags = [
+            "--dim",
+            "2",
+            "--no-passives",
+            "--pest-chance",
+            "0",
+            "--cloud-chance",
+            "0",
+        ];
+        let mut forest = game(&flags);
+        young_grass(&mut forest, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        let report = forest.update_game();
+        assert!(report.passives.is_empty());
+        assert_eq!(forest.ledger.get(ScoreCategory::Passives), 0.0);
+    }
+
+    fn sown_tiles(forest: &Game) -> Vec<(usize, usize)> {
+        let coords = forest.tile.iter_coords();
+        coords
+            .filter(|&(x, y)| matches!(forest.tile.get(x, y), Some(Tile::New(_))))
+            .collect()
+    }
+
+    #[test]
+    fn drops_take_root_on_free_neighbors() {
+        let mut forest = game(&["--dim", "3"]);
+        young_grass(&mut forest, &[(1, 0), (0, 1)]);
+        let grass = || Card::Plant(Box::new(tall_grass()));
+        let (left, sown) = forest.sow(1, 1, 0, vec![grass(), grass(), grass()]);
+        let tiles = sown_tiles(&forest);
+        assert_eq!(tiles.len(), 2);
+        assert!(tiles.iter().all(|tile| [(2, 1), (1, 2)].contains(tile)));
+        assert_eq!(sown.len(), 2);
+        assert!(sown.iter().all(|s| s.starts_with("Tall Grass at ")));
+        // The third finds no room and goes to the hand
+        assert_eq!(left.len(), 1);
+        assert_eq!(
+            forest.log.last().unwrap(),
+            "[0] No free tile next to B2, Tall Grass went to the hand"
+        );
+    }
+
+    #[test]
+    fn surrounded_harvests_drop_into_the_hand() {
+        let mut forest = game(&["--dim", "3", "--pest-chance", "0", "--cloud-chance", "0"]);
+        young_grass(&mut forest, &[(1, 0), (0, 1), (2, 1), (1, 2)]);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = grass.max_age - 1;
+        grass.fate = Some(vec![CardId::Plant(grass.id)]);
+        grass.fate_target = DropTarget::AdjacentTile;
+        forest.tile.set(1, 1, Tile::Permanent(grass));
+        let hand = forest.hand.len();
+        let report = forest.update_game();
+        assert!(report.matured[0].sown.is_empty());
+        assert_eq!(report.matured[0].cards, ["Tall Grass"]);
+        assert_eq!(forest.hand.len(), hand + 1);
+        assert!(sown_tiles(&forest).is_empty());
+    }
+
+    #[test]
+    fn the_seed_decides_where_drops_take_root() {
+        let tiles = (0..2)
+            .map(|_| {
+                let mut forest = game(&["--dim", "5"]);
+                let drops = vec![Card::Plant(Box::new(tall_grass())); 2];
+                forest.sow(2, 2, 0, drops);
+                sown_tiles(&forest)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(tiles[0], tiles[1]);
+        assert_eq!(tiles[0].len(), 2);
+    }
+
+    #[test]
+    fn items_and_hand_drops_never_take_root() {
+        let mut forest = game(&["--dim", "3"]);
+        let item = items::all().into_iter().next().unwrap();
+        let (left, sown) = forest.sow(1, 1, 0, vec![Card::Item(item)]);
+        assert_eq!((left.len(), sown.len()), (1, 0));
+        assert!(sown_tiles(&forest).is_empty());
+        let drop = serde_json::from_str::<Drop>(r#"{"chance": 1.0, "plants": ["Grass"]}"#).unwrap();
+        assert_eq!(drop.target, DropTarget::Hand);
+        let json = r#"{"chance": 1.0, "plants": ["Grass"], "target": "adjacent_tile"}"#;
+        assert_eq!(
+            serde_json::from_str::<Drop>(json).unwrap().target,
+            DropTarget::AdjacentTile
+        );
+    }
+
+    #[test]
+    fn previews_follow_the_growth_curve_to_the_harvest() {
+        for curve in [
+            GrowthCurve::Linear,
+            GrowthCurve::Accelerating { step: 1 },
+            GrowthCurve::Burst { rounds: 2 },
+        ] {
+            let mut forest = game(&["--pest-chance", "0", "--cloud-chance", "0"]);
+            forest.clouds.clear();
+            let mut plant = tall_grass();
+            (plant.growth_curve, plant.max_age) = (curve, 4);
+            let projected = forest.projected_points(0, 0, &plant);
+            forest.tile.set(0, 0, Tile::New(PlantedPlant::new(plant)));
+            while forest.ledger.get(ScoreCategory::Harvest) == 0.0 {
+                (forest.weather, forest.forecast) = (Weather::Sunny, Weather::Sunny);
+                forest.update_game();
+                assert!(forest.round <= 5, "{curve}");
+            }
+            let harvested = forest.ledger.get(ScoreCategory::Harvest);
+            assert_eq!(harvested, projected, "{curve}");
+            let size = curve::size_at(curve, 1, 4, 4) as f32;
+            assert_eq!(harvested, size * tall_grass().points_per_size, "{curve}");
+        }
+    }
+
+    /// Grass, Grass, Tall Grass, Grass, with the three Grass in one row.
+    fn grouped_hand() -> Game {
+        let mut forest = game(&["--actions", "0"]);
+        let grass = forest.hand[0].clone();
+        forest.add_to_hand([Card::Plant(Box::new(tall_grass())), grass]);
+        play(&mut forest, Action::GroupHand);
+        assert!(forest.choosing.grouped);
+        forest
+    }
+
+    #[test]
+    fn grouped_rows_stand_for_every_identical_card() {
+        let forest = grouped_hand();
+        assert_eq!(forest.hand.len(), 4);
+        assert_eq!(forest.visible_hand(), [0, 2]);
+        assert_eq!((forest.stack_size(0), forest.stack_size(2)), (3, 1));
+        assert_eq!(forest.stack_of(3), 0);
+        // A different base stat is a different card
+        let mut taller = tall_grass();
+        taller.max_age += 1;
+        assert!(!Card::Plant(Box::new(taller)).stacks_with(&forest.hand[2]));
+    }
+
+    #[test]
+    fn placing_from_a_group_takes_one_copy() {
+        let mut forest = grouped_hand();
+        forest.choosing.index = Some(0);
+        play(&mut forest, Action::QuickPlace);
+        assert_eq!(forest.hand.len(), 3);
+        assert_eq!(forest.visible_hand(), [0, 1]);
+        assert_eq!(forest.stack_size(0), 2);
+        assert_eq!(forest.choosing.index, Some(0));
+        assert_eq!(forest.hand[0].name(), "Grass");
+    }
+
+    #[test]
+    fn taking_a_plant_back_puts_it_into_its_group() {
+        let mut forest = grouped_hand();
+        forest.choosing.index = Some(0);
+        play(&mut forest, Action::QuickPlace);
+        forest.state = State::Placing;
+        forest.placing = PlacingState { x: 3, y: 3 };
+        play(&mut forest, Action::Delete);
+        assert_eq!(forest.hand.len(), 4);
+        assert_eq!(forest.visible_hand(), [0, 1]);
+        assert_eq!(forest.stack_size(0), 3);
+        // The group's row is selected, by its first card
+        assert_eq!(forest.choosing.index, Some(0));
+    }
+
+    #[test]
+    fn switching_modes_keeps_the_selected_card() {
+        let mut forest = grouped_hand();
+        play(&mut forest, Action::GroupHand);
+        forest.choosing.index = Some(3);
+        play(&mut forest, Action::GroupHand);
+        assert_eq!(forest.choosing.index, Some(0));
+        play(&mut forest, Action::Down);
+        assert_eq!(forest.choosing.index, Some(2));
+        play(&mut forest, Action::GroupHand);
+        assert_eq!(forest.choosing.index, Some(2));
+        assert_eq!(forest.visible_hand(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn the_score_is_what_the_ledger_adds_up_to() {
+        let flags = [
+            "--overplant",
+            "--actions",
+            "0",
+            "--pest-chance",
+            "0",
+            "--cloud-chance",
+            "0",
+        ];
+        let mut forest = game(&flags);
+        grass_maturing_next(&mut forest, 0, 0);
+        young_grass(&mut forest, &[(1, 0)]);
+        forest.streak = 3;
+        forest.update_game();
+        // Overplanting the young grass costs points
+        forest.add_to_hand([Card::Plant(Box::new(tall_grass()))]);
+        forest.choosing.index = Some(forest.hand.len() - 1);
+        play(&mut forest, Action::Place);
+        forest.placing = PlacingState { x: 1, y: 0 };
+        play(&mut forest, Action::Place);
+        play(&mut forest, Action::Confirm);
+        let categories = forest
+            .ledger
+            .lines()
+            .map(|(category, _)| category)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            categories,
+            [
+                ScoreCategory::Harvest,
+                ScoreCategory::Streak,
+                ScoreCategory::Passives,
+                ScoreCategory::Penalties
+            ]
+        );
+        assert_eq!(forest.total_points(), forest.ledger.total());
+        assert!(forest.ledger.get(ScoreCategory::Penalties) < 0.0);
+    }
+
+    /// A plant file in a fresh folder of its own.
+    fn plant_file(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-plants-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("plants.json");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn the_forest_pack_holds_the_built_in_plants_and_hybrids() {
+        let file = read_plants(Path::new(PLANTS_PATH)).unwrap();
+        assert_eq!(file.hybrids, builtin_hybrids());
+        let names = |plants: &[Plant]| {
+            plants
+                .iter()
+                .map(|p| p.name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&file.plants), names(&builtin_plants()));
+    }
+
+    #[test]
+    fn plant_files_may_leave_out_the_hybrids() {
+        let plants = editor::to_json(&builtin_plants(), &[]);
+        let path = plant_file("list", &plants);
+        let file = read_plants(&path).unwrap();
+        assert_eq!(file.plants.len(), builtin_plants().len());
+        assert!(file.hybrids.is_empty());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        let path = plant_file(
+            "bad-hybrid",
+            r#"{"plants": [], "hybrids": [{"parents": ["Grass"], "child": "Fern"}]}"#,
+        );
+        let err = read_plants(&path).err().unwrap();
+        assert!(err.contains("invalid hybrids"), "{err}");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn packs_refuse_hybrids_of_plants_they_lack() {
+        let hybrids = [HybridRule {
+            parents: ["Grass".into(), "Oak".into()],
+            child: "Fern".into(),
+            chance: 2.0,
+        }];
+        let path = plant_file("pack-hybrid", &editor::to_json(&builtin_plants(), &hybrids));
+        let dir = path.parent().unwrap().parent().unwrap();
+        let name = path
+            .parent()
+            .unwrap()
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap();
+        let err = packs::load(dir, name, &["Grass".into()]).err().unwrap();
+        assert!(
+            err.contains("Hybrid of <Grass> and <Oak> names unknown Plant <Oak>"),
+            "{err}"
+        );
+        assert!(err.contains("has a chance of 2"), "{err}");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn the_game_crosses_the_hybrids_of_its_plant_set() {
+        let flags = ["--pest-chance", "0", "--cloud-chance", "0"];
+        let config = engine::config_from_flags(&flags).unwrap();
+        let mut hybrids = builtin_hybrids();
+        hybrids.push(HybridRule {
+            parents: ["Tall Grass".into(), "Tall Grass".into()],
+            child: "Shrub".into(),
+            chance: 1.0,
+        });
+        let hand = config.difficulty.hand.iter().map(|name| name.to_string());
+        let set = PlantSet::new(
+            builtin_plants(),
+            hybrids,
+            items::all(),
+            &hand.collect::<Vec<_>>(),
+        );
+        let mut forest = Game::with_plants(set.unwrap(), Arc::new(config), 1);
+        assert_eq!(forest.hybrids.len(), 2);
+        grass_maturing_next(&mut forest, 0, 0);
+        young_grass(&mut forest, &[(1, 0)]);
+        (forest.weather, forest.forecast) = (Weather::Sunny, Weather::Sunny);
+        forest.update_game();
+        assert!(forest
+            .log
+            .iter()
+            .any(|line| line.ends_with("New hybrid discovered! Shrub")));
+    }
+
+    #[test]
+    fn hot_seat_players_only_dig_up_their_own_plants() {
+        let mut forest = game(&["--players", "2", "--overplant"]);
+        for (x, owner) in [(0, 0), (1, 1)] {
+            let mut plant = tall_grass();
+            plant.owner = owner;
+            forest
+                .tile
+                .set(x, 0, Tile::Permanent(PlantedPlant::new(plant)));
+        }
+        forest.turn = 1;
+        play(&mut forest, Action::Place);
+        forest.placing = PlacingState { x: 0, y: 0 };
+        assert!(!forest.can_overplant(0, 0));
+        assert!(forest.overplant_victim(0, 0).is_none());
+        assert!(forest.can_overplant(1, 0));
+
+        let hand = forest.hand.len();
+        play(&mut forest, Action::Place);
+        assert_eq!(forest.notice.as_deref(), Some("Tile occupied"));
+        assert_eq!(forest.plant_at(0, 0).map(|p| p.owner), Some(0));
+        assert_eq!(forest.hand.len(), hand);
+        assert_eq!(forest.ledger.get(ScoreCategory::Penalties), 0.0);
+    }
+
+    #[test]
+    fn items_only_work_on_the_players_own_plants() {
+        let mut forest = game(&["--players", "2"]);
+        let mut plant = tall_grass();
+        plant.size = 3;
+        forest
+            .tile
+            .set(0, 0, Tile::Permanent(PlantedPlant::new(plant)));
+        forest.turn = 1;
+        for name in ["Scythe", "Fertilizer", "Tonic"] {
+            let item = items::all()
+                .into_iter()
+                .find(|item| item.name == name)
+                .unwrap();
+            assert!(!forest.can_use_item(0, 0, &item), "{}", name);
+            forest.add_to_hand([Card::Item(item)]);
+            forest.state = State::Choosing;
+            forest.choosing.index = Some(forest.hand.len() - 1);
+            play(&mut forest, Action::Place);
+            forest.placing = PlacingState { x: 0, y: 0 };
+            let hand = forest.hand.len();
+            play(&mut forest, Action::Place);
+            let refusal = format!("{} only works on your own plants", name);
+            assert_eq!(forest.notice.as_deref(), Some(refusal.as_str()));
+            assert_eq!(forest.hand.len(), hand);
+        }
+        let Some(Tile::Permanent(plant)) = forest.tile.get(0, 0) else {
+            panic!("the plant is gone");
+        };
+        assert_eq!((plant.size, plant.effects.len()), (3, 0));
+        assert_eq!(forest.ledger_of(0).total(), 0.0);
+    }
+
+    fn rain_cloud(x: i64, y: usize) -> Cloud {
+        Cloud {
+            kind: CloudKind::Rain,
+            entry: weather::Edge::West,
+            x,
+            y,
+        }
+    }
+
+    #[test]
+    fn only_plants_under_a_cloud_grow_faster() {
+        let mut game = game(&[]);
+        game.weather = Weather::Sunny;
+        let plant = tall_grass();
+        for x in 0..4 {
+            game.tile
+                .set(x, 0, Tile::Permanent(PlantedPlant::new(plant.clone())));
+        }
+        game.clouds = vec![rain_cloud(0, 0)];
+        game.grow_plants();
+        let sizes = (0..4)
+            .map(|x| game.tile.get(x, 0).and_then(Tile::plant).unwrap().size)
+            .collect::<Vec<u32>>();
+        let (size, more) = (plant.size + plant.size_per_turn, plant.size_per_turn + 1);
+        assert_eq!(sizes, [plant.size + more, plant.size + more, size, size]);
+
+        game.clouds[0].kind = CloudKind::Drought;
+        game.grow_plants();
+        let sizes = (0..4)
+            .map(|x| game.tile.get(x, 0).and_then(Tile::plant).unwrap().size)
+            .collect::<Vec<u32>>();
+        assert_eq!(sizes[..2], [plant.size + more; 2]);
+    }
+
+    #[test]
+    fn clouds_leave_the_board_and_new_ones_come_from_the_seed() {
+        let mut calm = game(&["--cloud-chance", "0"]);
+        calm.clouds = vec![rain_cloud(calm.width() as i64 - 1, 0)];
+        calm.advance_clouds(&mut RoundReport::default());
+        assert!(calm.clouds.is_empty());
+
+        let rolled = |seed| {
+            let mut game = game(&["--cloud-chance", "1"]);
+            game.rng = XorShiftRng::seed_from_u64(seed);
+            game.advance_clouds(&mut RoundReport::default());
+            game.clouds
+        };
+        assert_eq!(rolled(7).len(), 1);
+        assert_eq!(rolled(7), rolled(7));
+    }
+
+    #[test]
+    fn projections_only_count_the_rounds_left() {
+        let mut game = game(&["--rounds", "3"]);
+        let grass = game.id_to_plant[0].clone();
+        assert_eq!(
+            (grass.name.as_ref(), grass.max_age, grass.size),
+            ("Grass", 2, 0)
+        );
+        assert_eq!(game.projected_points(0, 0, &grass), 2.0);
+        // A card sits out its first round, one is left to grow in
+        game.round = 1;
+        assert_eq!(game.projected_points(0, 0, &grass), 1.0);
+        game.round = 2;
+        assert_eq!(game.projected_points(0, 0, &grass), 0.0);
+
+        // A plant on the board already grows this round
+        let mut planted = PlantedPlant::new(grass.clone());
+        planted.age = 1;
+        planted.size = 1;
+        game.tile.set(0, 0, Tile::Permanent(planted));
+        let on_board = game.plant_at(0, 0).unwrap().clone();
+        assert_eq!(game.projected_points(0, 0, &on_board), 2.0);
+        assert_eq!(game.projected_points(1, 0, &grass), 0.0);
+    }
+
+    #[test]
+    fn projections_follow_the_clouds_along_their_path() {
+        let mut game = game(&[]);
+        let plant = tall_grass();
+        let (width, height) = (game.width(), game.height());
+        let clear = game.projected_points(width - 1, height - 1, &plant);
+        // A card only grows from next round on, by then the cloud is a
+        // column further
+        game.clouds = vec![rain_cloud(0, 0)];
+        assert_eq!(game.projected_points(0, 0, &plant), clear);
+        assert!(game.projected_points(2, 0, &plant) > clear);
+        assert_eq!(game.projected_points(2, 2, &plant), clear);
+    }
+
+    #[test]
+    #[ignore = "a timing, run it with --release -- --ignored"]
+    fn a_thousand_greedy_games_on_a_small_board_take_under_a_second() {
+        let config =
+            engine::config_from_flags(&["--dim", "6", "--rounds", "20", "--seed", "1"]).unwrap();
+        let hand = config.difficulty.hand.iter().map(|name| name.to_string());
+        let pack = Pack::builtin(hand.collect());
+        let start = std::time::Instant::now();
+        let report = simulate::run(&pack, Arc::new(config), 1000, PolicyName::Greedy);
+        let took = start.elapsed();
+        assert_eq!(report.stuck, 0);
+        assert!(took.as_secs_f32() < 1.0, "took {:?}", took);
+    }
+
+    /// The TUI around a fresh `game(flags)`.
+    fn app(flags: &[&str]) -> App {
+        let config = engine::config_from_flags(flags).unwrap();
+        let hand = config.difficulty.hand.iter().map(|name| name.to_string());
+        let mut app = App::new(
+            Theme::new(ThemeName::Default),
+            Pack::builtin(hand.collect()),
+            Arc::new(config),
+        );
+        app.replace_game(game(flags));
+        app
+    }
+
+    /// What `ui` draws on a terminal `width` by `height` cells, row by row.
+    fn screen(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| ui(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn the_board_and_the_side_panel_share_the_screen() {
+        let mut app = app(&[]);
+        let rows = screen(&mut app, 130, 36);
+        assert_eq!(rows[0].trim(), "");
+        assert!(rows[1].starts_with(" ┌ Forest (Normal) // Score: 0 // Round: 0 //"));
+        // 70% of the 128 columns inside the margin go to the board
+        assert_eq!(rows[1].chars().nth(90), Some('┐'));
+        assert!(rows[1][rows[1].find('┐').unwrap()..].contains(" Plants "));
+        assert!(rows[2].contains(">>  1 Grass"));
+        assert!(rows[32].starts_with(" └──"));
+        assert!(rows[33].starts_with(" No plants // 0 mature next round // 36 free"));
+        assert!(rows.iter().any(|row| row.contains("Next Round")));
+    }
+
+    #[test]
+    fn plants_placed_show_in_the_log_and_the_summary() {
+        let mut app = app(&[]);
+        app.apply(Action::Place);
+        app.apply(Action::Place);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("[0] Placed Grass at D4")));
+        assert!(rows[33].starts_with(" w×1 // 0 mature next round // 35 free"));
+        assert_eq!(rows[35].trim(), "Placed Grass at D4");
+    }
+
+    #[test]
+    fn the_plain_board_is_a_grid_of_letters() {
+        let mut app = app(&[]);
+        app.plain = true;
+        let rows = screen(&mut app, 130, 36);
+        // Inside the board's borders
+        let grid = rows
+            .iter()
+            .map(|row| row.split('│').nth(1).unwrap_or_default().trim_end())
+            .collect::<Vec<_>>();
+        assert_eq!(grid[3], "     A  B  C  D  E  F");
+        assert_eq!(grid[4], "  6  .  .  .  .  .  .");
+        assert_eq!(grid[9], "  1  .  .  .  .  .  .");
+        assert_eq!(grid[10], "");
+    }
+
+    #[test]
+    fn ticks_only_move_the_clock_of_the_game() {
+        let mut app = app(&[]);
+        app.apply(Action::Place);
+        let before = save::to_json(&app.game).unwrap();
+        let tick = Duration::from_millis(100);
+        for _ in 0..50 {
+            app.on_tick(tick);
+        }
+        assert_eq!(app.game.played_ms, 5000);
+        app.game.played_ms = 0;
+        assert_eq!(save::to_json(&app.game).unwrap(), before);
+
+        app.apply(Action::Pause);
+        app.on_tick(tick);
+        assert_eq!(app.game.played_ms, 0);
+    }
+
+    #[test]
+    fn the_clock_stops_behind_overlays_and_at_the_end() {
+        let tick = Duration::from_millis(250);
+        let mut app = app(&[]);
+        app.on_tick(tick);
+        app.apply(Action::Pause);
+        app.on_tick(tick);
+        app.apply(Action::Pause);
+        app.on_tick(tick);
+        assert_eq!(app.game.played_ms, 500);
+
+        app.apply(Action::Codex);
+        assert!(app.codex.is_some());
+        app.on_tick(tick);
+        app.apply(Action::Codex);
+        app.on_tick(tick);
+        assert_eq!(app.game.played_ms, 750);
+
+        app.game.state = State::GameOver;
+        app.on_tick(tick);
+        assert_eq!(app.game.played_ms, 750);
+    }
+
+    #[test]
+    fn the_time_played_carries_over_a_save() {
+        let tick = Duration::from_millis(400);
+        let mut app = app(&[]);
+        for _ in 0..5 {
+            app.on_tick(tick);
+        }
+        let saved = save::from_json(&save::to_json(&app.game).unwrap()).unwrap();
+        let mut resumed = self::app(&[]);
+        resumed.replace_game(saved.game);
+        assert_eq!(resumed.game.played_ms, 2000);
+        resumed.on_tick(tick);
+        assert_eq!(resumed.game.played_ms, 2400);
+        assert_eq!(scores::ScoreEntry::from_game(&resumed.game).played_ms, 2400);
+    }
+
+    #[test]
+    fn the_clock_shows_unless_it_is_turned_off() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(61_999), "1:01");
+        assert_eq!(format_duration(3_725_000), "1:02:05");
+        let mut app = app(&[]);
+        (app.timer, app.game.played_ms) = (true, 65_000);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[35].ends_with(" 1:05 "), "{}", rows[35]);
+        app.timer = false;
+        assert!(!screen(&mut app, 130, 36)[35].contains("1:05"));
+        // Hidden, it still counts
+        app.on_tick(Duration::from_secs(1));
+        assert_eq!(app.game.played_ms, 66_000);
+    }
+
+    #[test]
+    fn ticks_blink_the_cursor_and_expire_the_status() {
+        let mut app = app(&[]);
+        app.notify("Hello");
+        app.needs_redraw = false;
+        let tick = Duration::from_millis(100);
+        let mut redraws = Vec::new();
+        for _ in 0..STATUS_TICKS {
+            app.on_tick(tick);
+            redraws.push(std::mem::take(&mut app.needs_redraw));
+        }
+        assert!(app.status.is_none());
+        // The cursor flips every BLINK_TICKS, the status goes on the last tick
+        let flips = (1..=STATUS_TICKS)
+            .map(|tick| tick % BLINK_TICKS == 0 || tick == STATUS_TICKS)
+            .collect::<Vec<bool>>();
+        assert_eq!(redraws, flips);
+    }
+
+    #[test]
+    fn resizing_between_frames_keeps_the_run() {
+        let mut app = app(&[]);
+        app.apply(Action::Place);
+        app.apply(Action::Place);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(130, 36)).unwrap();
+        for (width, height) in [
+            (130, 36),
+            (1, 1),
+            (30, 10),
+            (59, 40),
+            (200, 19),
+            (60, 20),
+            (200, 60),
+        ] {
+            terminal.backend_mut().resize(width, height);
+            terminal.autoresize().unwrap();
+            let frame = terminal.draw(|f| ui(f, &mut app)).unwrap();
+            assert_eq!(frame.area, Rect::new(0, 0, width, height));
+            let text = frame
+                .buffer
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>();
+            let small = width < MIN_WIDTH || height < MIN_HEIGHT;
+            // A single cell only fits the first letter
+            assert_eq!(text.contains("Terminal too small"), small && width > 1);
+            assert_eq!(
+                text.contains("Placed Grass at D4"),
+                !small,
+                "{width}x{height}"
+            );
+        }
+        assert_eq!(app.game.placed, 1);
+    }
+
+    #[test]
+    fn every_screen_fits_the_smallest_terminal() {
+        for action in [
+            None,
+            Some(Action::Pause),
+            Some(Action::Codex),
+            Some(Action::Details),
+            Some(Action::Reserve),
+            Some(Action::ScoreBreakdown),
+        ] {
+            let mut app = app(&["--tutorial"]);
+            app.start_tutorial();
+            if let Some(action) = action {
+                app.apply(action);
+            }
+            let rows = screen(&mut app, MIN_WIDTH, MIN_HEIGHT);
+            assert!(!rows.join("").contains("Terminal too small"), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn tile_text_shrinks_with_the_tile() {
+        assert_eq!(TileDetail::fitting(8.0, 3), TileDetail::Full);
+        assert_eq!(TileDetail::fitting(4.0, 3), TileDetail::Compact);
+        assert_eq!(TileDetail::fitting(2.0, 3), TileDetail::Compact);
+        assert_eq!(TileDetail::fitting(1.5, 3), TileDetail::Block);
+
+        let tile = Tile::Permanent(PlantedPlant::new(tall_grass()));
+        assert_eq!(tile.text(TileDetail::Full), "W 4");
+        assert_eq!(tile.text(TileDetail::Compact), "W");
+        assert_eq!(tile.text(TileDetail::Block), "█");
+        assert_eq!(Tile::Empty.text(TileDetail::Full), " ");
+    }
+
+    #[test]
+    fn large_boards_on_small_terminals_keep_tile_text_apart() {
+        for (dim, text) in [("6", "w 2"), ("16", "w")] {
+            let mut app = app(&["--dim", dim]);
+            app.apply(Action::Place);
+            app.apply(Action::Place);
+            app.apply(Action::NextRound);
+            let rows = screen(&mut app, 80, 24);
+            // Inside the borders of the board
+            let board = rows[2..22]
+                .iter()
+                .map(|row| row.chars().skip(2).take(42).collect::<String>())
+                .collect::<Vec<String>>();
+            let shown = board.iter().filter(|row| row.contains(text)).count();
+            assert_eq!(shown, 1, "{}", board.join("\n"));
+            assert_eq!(board.concat().matches('w').count(), 1);
+        }
+    }
+
+    #[test]
+    fn the_side_panels_make_room_for_the_hand() {
+        assert_eq!(side_heights(33, 2, 4), [5, 8, 5, 11, 4]);
+        assert_eq!(side_heights(33, 15, 4), [17, 7, 5, 0, 4]);
+        assert_eq!(side_heights(33, 40, 4), [19, 6, 5, 0, 3]);
+        assert_eq!(side_heights(20, 40, 4), [6, 6, 5, 0, 3]);
+    }
+
+    #[test]
+    fn a_long_hand_scrolls_with_the_selection() {
+        let mut app = app(&[]);
+        app.game
+            .add_to_hand(vec![Card::Plant(Box::new(tall_grass())); 18]);
+        app.select(app.game.choosing.index);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[1].contains(" Plants (+3 more) "));
+        assert!(rows[2].contains(">>  1 Grass"));
+
+        let last = app.game.hand.len() - 1;
+        app.game.choosing.index = Some(last);
+        app.select(Some(last));
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[18].contains(">>    Tall Grass"));
+        assert!(!rows[2].contains("1 Grass"));
+    }
+
+    /// The tile view under the cursor.
+    fn hovered(app: &App) -> TileView {
+        let view = BoardViewModel::new(&app.game, &app.theme, true);
+        let at = (app.game.placing.x, app.game.placing.y);
+        view.tiles.into_iter().find(|t| (t.x, t.y) == at).unwrap()
+    }
+
+    #[test]
+    fn the_chosen_plant_shows_faintly_where_it_would_go() {
+        let mut app = app(&[]);
+        app.renderer = Renderer::Ascii;
+        play(&mut app.game, Action::Place);
+        let rows = screen(&mut app, 80, 24);
+        assert!(
+            rows[9].starts_with(" ║│     ││     ││     │║ w 2 ║│     │"),
+            "{}",
+            rows[9]
+        );
+        let tile = hovered(&app);
+        assert!(tile.style.add_modifier.contains(Modifier::DIM));
+        assert_eq!(tile.text(TileDetail::Compact), "w");
+        assert_eq!(tile.points.as_deref(), Some("2"));
+
+        // Only the hovered tile, and nothing is placed yet
+        play(&mut app.game, Action::Left);
+        let rows = screen(&mut app, 80, 24);
+        assert!(
+            rows[9].starts_with(" ║│     ││     │║ w 2 ║│     ││     │"),
+            "{}",
+            rows[9]
+        );
+        assert!(app.game.tile.iter().all(|tile| matches!(tile, Tile::Empty)));
+    }
+
+    #[test]
+    fn taken_tiles_show_their_plant_and_illegal_ones_an_x() {
+        let mut app = app(&[]);
+        play(&mut app.game, Action::Place);
+        let (x, y) = (app.game.placing.x, app.game.placing.y);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = 1;
+        let occupant = grass.countdown();
+        app.game.tile.set(x, y, Tile::Permanent(grass));
+        let tile = hovered(&app);
+        assert_eq!(tile.text(TileDetail::Full), occupant);
+        assert!(!tile.style.add_modifier.contains(Modifier::DIM));
+        assert_eq!(tile.points, None);
+
+        app.game.tile.set(x, y, Tile::Empty);
+        app.game.terrain.set(x, y, Terrain::Rock);
+        let tile = hovered(&app);
+        assert_eq!(tile.text(TileDetail::Full), "X");
+        assert_eq!(tile.text(TileDetail::Compact), "X");
+        assert_eq!(tile.style.fg, Some(app.theme.infested));
+        app.renderer = Renderer::Ascii;
+        assert!(screen(&mut app, 80, 24)[9].contains("║  X  ║"));
+    }
+
+    #[test]
+    fn narrow_terminals_tighten_the_board_split() {
+        assert_eq!(board_percent(70, 200), 70);
+        assert_eq!(board_percent(85, 200), 83);
+        assert_eq!(board_percent(70, 120), 70);
+        assert_eq!(board_percent(70, 80), 57);
+        assert_eq!(board_percent(85, 60), MIN_BOARD_SPLIT);
+        assert_eq!(board_percent(50, 0), MIN_BOARD_SPLIT);
+    }
+
+    #[test]
+    fn the_split_moves_in_steps_and_stops_at_its_ends() {
+        let mut app = app(&[]);
+        let path = std::env::temp_dir().join(format!(
+            "rogue_forest-split-{}/config.toml",
+            std::process::id()
+        ));
+        app.config_path = Some(path.clone());
+        app.resize_board(true);
+        assert_eq!(app.board_split, 75);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "board_split = 75\n");
+        for _ in 0..10 {
+            app.resize_board(false);
+        }
+        assert_eq!(app.board_split, MIN_BOARD_SPLIT);
+        assert_eq!(
+            app.status.as_ref().unwrap().0,
+            "The board is as narrow as it gets"
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn every_split_leaves_room_for_board_and_panels() {
+        let mut app = app(&[]);
+        for width in [80, 120, 200] {
+            for split in (MIN_BOARD_SPLIT..=MAX_BOARD_SPLIT).step_by(SPLIT_STEP as usize) {
+                app.board_split = split;
+                let rows = screen(&mut app, width, 36);
+                assert!(
+                    rows[1].contains(" Forest ("),
+                    "{width} {split}: {}",
+                    rows[1]
+                );
+                assert!(rows[1].contains(" Plants "), "{width} {split}: {}", rows[1]);
+                assert!(
+                    rows[33].contains("No plants"),
+                    "{width} {split}: {}",
+                    rows[33]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn f3_switches_renderers_that_show_the_same_tile() {
+        let mut app = app(&[]);
+        play(&mut app.game, Action::Place);
+        for renderer in [Renderer::Ascii, Renderer::Canvas] {
+            assert_eq!(
+                key_action(&app, KeyCode::F(3)),
+                Some(Action::ToggleRenderer)
+            );
+            app.apply(Action::ToggleRenderer);
+            assert_eq!(app.renderer, renderer);
+            // The ghost and what it would harvest, on either
+            let rows = screen(&mut app, 130, 36);
+            assert!(rows.iter().any(|row| row.contains("w 2")), "{renderer:?}");
+        }
+    }
+
+    #[test]
+    fn the_status_bar_suggests_ending_a_stuck_round() {
+        let mut app = app(&["--dim", "2", "--actions", "0"]);
+        rocks_but_one(&mut app.game);
+        app.apply(Action::QuickPlace);
+        let rows = screen(&mut app, 130, 36);
+        // The placement notice goes first
+        app.status = None;
+        let rows_after = screen(&mut app, 130, 36);
+        assert!(!rows[35].contains("No moves left"), "{}", rows[35]);
+        assert!(rows_after[35].contains("No moves left — press space to end the round"));
+    }
+
+    #[test]
+    fn auto_advance_ends_the_round_after_the_last_move() {
+        for auto_advance in [false, true] {
+            let mut app = app(&["--dim", "2", "--actions", "0"]);
+            app.auto_advance = auto_advance;
+            rocks_but_one(&mut app.game);
+            app.apply(Action::QuickPlace);
+            assert_eq!(app.game.round, auto_advance as u32);
+        }
+    }
+
+    #[test]
+    fn the_round_summary_itemizes_the_passives() {
+        let mut app = app(&["--pest-chance", "0", "--cloud-chance", "0"]);
+        young_grass(&mut app.game, &[(0, 0), (1, 0)]);
+        app.summaries = true;
+        app.apply(Action::NextRound);
+        assert!(app.summary);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("2 plants on the board: 0.2 points")));
+    }
+
+    #[test]
+    fn the_round_summary_says_where_drops_took_root() {
+        let mut app = app(&["--dim", "3", "--pest-chance", "0", "--cloud-chance", "0"]);
+        young_grass(&mut app.game, &[(1, 0), (0, 1), (2, 1)]);
+        let mut grass = PlantedPlant::new(tall_grass());
+        grass.age = grass.max_age - 1;
+        grass.fate = Some(vec![CardId::Plant(grass.id)]);
+        grass.fate_target = DropTarget::AdjacentTile;
+        app.game.tile.set(1, 1, Tile::Permanent(grass));
+        app.summaries = true;
+        app.apply(Action::NextRound);
+        let matured = &app.game.round_reports.last().unwrap().matured[0];
+        assert_eq!(matured.sown, ["Tall Grass at B3"]);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows
+            .iter()
+            .any(|row| row.contains(", sowed Tall Grass at B3")));
+        assert!(app
+            .game
+            .log
+            .iter()
+            .any(|line| line.ends_with("Tall Grass from B2 took root at B3")));
+    }
+
+    #[test]
+    fn the_info_panel_names_curves_other_than_linear() {
+        let forest = game(&[]);
+        let theme = Theme::new(ThemeName::Default);
+        let mut plant = tall_grass();
+        let text = |plant: &Plant| {
+            let lines = plant_info(plant, 0.0, None, &forest, &theme);
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert!(!text(&plant).iter().any(|line| line.starts_with("Growth")));
+        plant.growth_curve = GrowthCurve::Burst { rounds: 2 };
+        let lines = text(&plant);
+        assert!(
+            lines.contains(&"Growth: burst in the last 2 rounds".to_string()),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn grouped_cards_show_their_count() {
+        let mut app = app(&[]);
+        app.apply(Action::GroupHand);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[2].contains("║>>  1 Grass ×2"), "{}", rows[2]);
+        assert!(!rows[3].contains("Grass"), "{}", rows[3]);
+    }
+
+    #[test]
+    fn b_shows_the_score_breakdown() {
+        let mut app = app(&[]);
+        assert_eq!(
+            key_action(&app, KeyCode::Char('b')),
+            Some(Action::ScoreBreakdown)
+        );
+        app.apply(Action::ScoreBreakdown);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows.iter().any(|row| row.contains("Nothing scored yet")));
+        app.game.ledger.add(ScoreCategory::Harvest, 12.0);
+        app.game.ledger.add(ScoreCategory::Penalties, -2.0);
+        let rows = screen(&mut app, 130, 36);
+        let shown = |text: &str| rows.iter().any(|row| row.contains(text));
+        assert!(shown(" Score breakdown "));
+        assert!(shown("║Harvests                  12"));
+        assert!(shown("║Penalties                 -2"));
+        assert!(shown("║Total                     10"));
+        assert_eq!(key_action(&app, KeyCode::Esc), Some(Action::ScoreBreakdown));
+        app.apply(Action::ScoreBreakdown);
+        assert!(!app.breakdown);
+    }
+
+    /// Plays a `--vs-ai` run to its end: the player passes every round and
+    /// the CPU plays its turns out.
+    fn against_the_cpu(flags: &[&str]) -> App {
+        let mut app = app(flags);
+        for _ in 0..100 {
+            match app.game.state {
+                State::GameOver => break,
+                _ if app.ai_turn() => app.finish_ai_turn(),
+                _ => {
+                    app.apply(Action::NextRound);
+                }
+            }
+        }
+        assert_eq!(app.game.state, State::GameOver);
+        app
+    }
+
+    #[test]
+    fn the_cpu_plays_the_same_game_with_the_same_seed() {
+        let flags = ["--vs-ai", "--seed", "7", "--rounds", "4"];
+        let first = against_the_cpu(&flags);
+        let second = against_the_cpu(&flags);
+        assert_eq!(layout(&first.game), layout(&second.game));
+        assert_eq!(first.game.log, second.game.log);
+        assert_eq!(
+            first.game.points_of(AI_SEAT),
+            second.game.points_of(AI_SEAT)
+        );
+        assert!(first.game.points_of(AI_SEAT) > first.game.points_of(0));
+    }
+
+    #[test]
+    fn the_end_screen_shows_both_scores_and_the_winner() {
+        let mut app = against_the_cpu(&["--vs-ai", "--seed", "7", "--rounds", "4"]);
+        let cpu = format!("CPU: {}", format_points(app.game.points_of(AI_SEAT)));
+        let rows = screen(&mut app, 130, 36);
+        let shown = |text: &str| rows.iter().any(|row| row.contains(text));
+        assert!(shown(&cpu));
+        assert!(shown("You: 0"));
+        assert!(shown("The CPU wins!"));
+        assert!(!shown("You win!"));
+    }
+
+    #[test]
+    fn the_player_waits_for_the_cpu_and_the_cpu_for_popups() {
+        let mut app = app(&["--vs-ai", "--seed", "7"]);
+        app.apply(Action::NextRound);
+        assert!(app.ai_turn());
+        let before = (layout(&app.game), app.game.hand.len(), app.game.state);
+        app.apply(Action::Place);
+        let status = app.status.as_ref().map(|(msg, _)| msg.as_str());
+        assert_eq!(status, Some("Wait for the CPU to end its turn"));
+        assert_eq!(
+            (layout(&app.game), app.game.hand.len(), app.game.state),
+            before
+        );
+
+        app.apply(Action::ScoreBreakdown);
+        assert_eq!(app.step_ai(), Flow::Unchanged);
+        assert_eq!(
+            (layout(&app.game), app.game.hand.len(), app.game.state),
+            before
+        );
+        app.apply(Action::ScoreBreakdown);
+        app.finish_ai_turn();
+        assert!(!app.ai_turn());
+        assert_eq!(app.game.turn, 0);
+    }
+
+    #[test]
+    fn the_codex_shows_drop_weights_as_shares() {
+        let mut app = app(&[]);
+        app.apply(Action::Codex);
+        app.apply(Action::Down);
+        let rows = screen(&mut app, 130, 36);
+        assert!(rows[1].contains("Tall Grass"));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("│83% Tall Grass + Tall Grass ")));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("│17% Tall Grass + Shrub ")));
+    }
+
+    #[test]
+    fn a_small_terminal_asks_for_more_room() {
+        let mut app = app(&[]);
+        let rows = screen(&mut app, MIN_WIDTH - 1, MIN_HEIGHT);
+        assert!(rows[..10].iter().all(|row| row.trim().is_empty()));
+        assert_eq!(rows[10].trim(), "Terminal too small, need at least 60x20");
+        let rows = screen(&mut app, MIN_WIDTH, MIN_HEIGHT);
+        assert!(rows[1].contains("Forest"));
+    }
+}
@@ -0,0 +1,140 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+/// Rounds a sprint lasts, no more and no fewer.
+pub const SPRINT_ROUNDS: u32 = 10;
+
+/// Endless runs lose this share of their drop chance every
+/// `DECAY_ROUNDS` rounds, so they run dry eventually.
+const DECAY: f32 = 0.05;
+const DECAY_ROUNDS: u32 = 10;
+
+/// How long a run goes and how it ends. The difficulty still sets
+/// everything else.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum GameMode {
+    /// The difficulty's round limit, over early once nothing is left
+    #[default]
+    Standard,
+    /// Exactly 10 rounds, ranked per seed
+    Sprint,
+    /// No round limit, but harvests drop less and less
+    Endless,
+    /// The board, hand, seed and round limit of `--puzzle`, played for its
+    /// target score
+    Puzzle,
+}
+
+/// Why a run is over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    /// The last round was played, what is left gets harvested
+    RoundLimit,
+    /// No card in any hand and no plant on the board
+    NothingLeft,
+}
+
+impl GameMode {
+    /// The round limit of a run in this mode, `limit` being the one of the
+    /// difficulty and the flags.
+    pub fn round_limit(self, limit: Option<u32>) -> Option<u32> {
+        match self {
+            GameMode::Standard | GameMode::Puzzle => limit,
+            GameMode::Sprint => Some(SPRINT_ROUNDS),
+            GameMode::Endless => None,
+        }
+    }
+
+    /// What the drop chance is multiplied with in `round`: 0.95 from round
+    /// 10 on, 0.95² from round 20 on, and so on in endless runs.
+    pub fn drop_decay(self, round: u32) -> f32 {
+        match self {
+            GameMode::Endless => (1.0 - DECAY).powi((round / DECAY_ROUNDS) as i32),
+            GameMode::Standard | GameMode::Sprint | GameMode::Puzzle => 1.0,
+        }
+    }
+
+    /// Whether the run ends after a round, with `rounds_left` to its limit
+    /// and `nothing_left` to play. A sprint always goes the full length.
+    pub fn ending(self, rounds_left: Option<u32>, nothing_left: bool) -> Option<Ending> {
+        match self {
+            _ if rounds_left == Some(0) => Some(Ending::RoundLimit),
+            GameMode::Standard | GameMode::Endless | GameMode::Puzzle if nothing_left => {
+                Some(Ending::NothingLeft)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for GameMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameMode::Standard => f.pad("Standard"),
+            GameMode::Sprint => f.pad("Sprint"),
+            GameMode::Endless => f.pad("Endless"),
+            GameMode::Puzzle => f.pad("Puzzle"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endless_drops_decay_five_percent_every_ten_rounds() {
+        let decay = [0, 9, 10, 19, 20, 35, 100].map(|round| GameMode::Endless.drop_decay(round));
+        let expected = [0, 0, 1, 1, 2, 3, 10].map(|steps| 0.95f32.powi(steps));
+        for (decay, expected) in decay.into_iter().zip(expected) {
+            assert!((decay - expected).abs() < 1e-6, "{decay} {expected}");
+        }
+        assert!(GameMode::Endless.drop_decay(1000) > 0.0);
+    }
+
+    #[test]
+    fn only_endless_runs_decay() {
+        for mode in [GameMode::Standard, GameMode::Sprint, GameMode::Puzzle] {
+            assert_eq!(mode.drop_decay(50), 1.0, "{mode}");
+        }
+    }
+
+    #[test]
+    fn the_mode_sets_the_round_limit() {
+        assert_eq!(GameMode::Standard.round_limit(Some(30)), Some(30));
+        assert_eq!(GameMode::Standard.round_limit(None), None);
+        assert_eq!(GameMode::Sprint.round_limit(Some(30)), Some(SPRINT_ROUNDS));
+        assert_eq!(GameMode::Sprint.round_limit(None), Some(SPRINT_ROUNDS));
+        assert_eq!(GameMode::Endless.round_limit(Some(30)), None);
+        assert_eq!(GameMode::Puzzle.round_limit(Some(5)), Some(5));
+    }
+
+    #[test]
+    fn the_sprint_help_names_its_rounds() {
+        let value = GameMode::Sprint.to_possible_value().unwrap();
+        let help = value.get_help().unwrap().to_string();
+        assert_eq!(
+            help,
+            format!("Exactly {} rounds, ranked per seed", SPRINT_ROUNDS)
+        );
+    }
+
+    #[test]
+    fn sprints_only_end_at_the_round_limit() {
+        assert_eq!(GameMode::Sprint.ending(Some(3), true), None);
+        assert_eq!(
+            GameMode::Sprint.ending(Some(0), false),
+            Some(Ending::RoundLimit)
+        );
+        for mode in [GameMode::Standard, GameMode::Endless] {
+            assert_eq!(mode.ending(None, true), Some(Ending::NothingLeft), "{mode}");
+            assert_eq!(mode.ending(Some(2), false), None, "{mode}");
+        }
+        assert_eq!(
+            GameMode::Standard.ending(Some(0), true),
+            Some(Ending::RoundLimit)
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use clap::ValueEnum;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+use crate::{board::Board, difficulty::Difficulty, terrain::Terrain, Hybrid, Plant};
+
+/// Cards a `TinyHands` hand holds at most.
+pub const TINY_HAND_LIMIT: usize = 3;
+
+/// Share of the plantable tiles `Barren` turns into rocks.
+pub const BARREN_SHARE: f32 = 0.25;
+
+/// Rule changes picked for a whole run with `--mutator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mutator {
+    /// At most 3 cards in hand, drops beyond that wait in a reserve
+    TinyHands,
+    /// Every plant matures a round earlier, but never in less than one
+    FastForward,
+    /// A quarter of the board starts as rocks
+    Barren,
+    /// Harvests and hybrids drop twice as often
+    Generous,
+}
+
+impl Display for Mutator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mutator::TinyHands => f.pad("tiny-hands"),
+            Mutator::FastForward => f.pad("fast-forward"),
+            Mutator::Barren => f.pad("barren"),
+            Mutator::Generous => f.pad("generous"),
+        }
+    }
+}
+
+/// "tiny-hands, barren", for titles and score rows.
+pub fn list(mutators: &[Mutator]) -> String {
+    mutators
+        .iter()
+        .map(Mutator::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub fn hand_limit(mutators: &[Mutator]) -> Option<usize> {
+    mutators
+        .contains(&Mutator::TinyHands)
+        .then_some(TINY_HAND_LIMIT)
+}
+
+/// Laid over the difficulty after the explicit flags.
+pub fn adjust_difficulty(mutators: &[Mutator], difficulty: &mut Difficulty) {
+    if mutators.contains(&Mutator::Generous) {
+        difficulty.drop_chance = (difficulty.drop_chance * 2.0).min(1.0);
+    }
+}
+
+/// Applied to every plant set, the one a run starts with and reloaded ones.
+pub fn adjust_plants(mutators: &[Mutator], plants: &mut [Plant]) {
+    if mutators.contains(&Mutator::FastForward) {
+        for plant in plants {
+            plant.max_age = plant.max_age.saturating_sub(1).max(1);
+        }
+    }
+}
+
+pub fn adjust_hybrids(mutators: &[Mutator], hybrids: &mut [Hybrid]) {
+    if mutators.contains(&Mutator::Generous) {
+        for hybrid in hybrids {
+            hybrid.chance = (hybrid.chance * 2.0).min(1.0);
+        }
+    }
+}
+
+/// Turns `BARREN_SHARE` of the plantable tiles into rocks, picked at random.
+pub fn adjust_terrain<R: Rng>(mutators: &[Mutator], terrain: &mut Board<Terrain>, rng: &mut R) {
+    if !mutators.contains(&Mutator::Barren) {
+        return;
+    }
+    let mut tiles = terrain
+        .iter_coords()
+        .filter(|&(x, y)| terrain.get(x, y).is_some_and(Terrain::can_plant))
+        .collect::<Vec<(usize, usize)>>();
+    let rocks = (tiles.len() as f32 * BARREN_SHARE).round() as usize;
+    rng.shuffle(&mut tiles);
+    for &(x, y) in tiles.iter().take(rocks) {
+        terrain.set(x, y, Terrain::Rock);
+    }
+}
@@ -0,0 +1,93 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+/// Every objective a run can roll, see `assets/objectives.json`.
+const POOL: &str = include_str!("../assets/objectives.json");
+
+/// Objectives rolled at the start of a run.
+pub const OBJECTIVES_PER_RUN: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Goal {
+    /// Harvest `count` plants called `plant`
+    Harvest { plant: String, count: u32 },
+    /// Have scored `points` by the end of round `round`
+    PointsBy { points: f32, round: u32 },
+    /// Have `count` plants on the board at the same time
+    PlantsOnBoard { count: u32 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    #[default]
+    Open,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    pub goal: Goal,
+    /// Points added as soon as the goal is reached
+    pub bonus: f32,
+    #[serde(default)]
+    pub progress: u32,
+    #[serde(default)]
+    pub status: Status,
+}
+
+impl Objective {
+    pub fn target(&self) -> u32 {
+        match &self.goal {
+            Goal::Harvest { count, .. } | Goal::PlantsOnBoard { count } => *count,
+            Goal::PointsBy { points, .. } => *points as u32,
+        }
+    }
+
+    /// Records new progress, returns true the moment the goal is reached.
+    pub fn advance(&mut self, progress: u32) -> bool {
+        if self.status != Status::Open {
+            return false;
+        }
+        self.progress = progress.max(self.progress);
+        if self.progress >= self.target() {
+            self.status = Status::Done;
+            return true;
+        }
+        false
+    }
+}
+
+impl Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.goal {
+            Goal::Harvest { plant, count } => write!(f, "Harvest {} {}", count, plant),
+            Goal::PointsBy { points, round } => {
+                write!(f, "Reach {} points by round {}", points, round)
+            }
+            Goal::PlantsOnBoard { count } => write!(f, "Have {} plants on the board", count),
+        }
+    }
+}
+
+pub fn pool() -> Vec<Objective> {
+    serde_json::from_str(POOL).expect("assets/objectives.json is invalid")
+}
+
+/// Picks `count` different objectives from the pool, leaving out harvests
+/// of plants that aren't `known` to the run.
+pub fn roll<R: Rng>(rng: &mut R, count: usize, known: impl Fn(&str) -> bool) -> Vec<Objective> {
+    let mut pool = pool();
+    pool.retain(|objective| match &objective.goal {
+        Goal::Harvest { plant, .. } => known(plant),
+        _ => true,
+    });
+    let mut picked = Vec::new();
+    while picked.len() < count && !pool.is_empty() {
+        let idx = rng.gen_range(0, pool.len());
+        picked.push(pool.remove(idx));
+    }
+    picked
+}
@@ -0,0 +1,249 @@
+use serde::Deserialize;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    builtin_hybrids, builtin_plants, items, read_plants, validate, HybridRule, Plant, PlantFile,
+    PlantSet, PLANTS_PATH,
+};
+
+/// Every pack is a directory in here: `plants.json` with the plants and
+/// the hybrids they cross into, an optional `pack.json` and optional board
+/// presets in `presets/`.
+pub const PACK_DIR: &str = "assets/packs";
+
+/// `--pack` value that merges every pack there is.
+pub const ALL: &str = "all";
+
+/// The optional `pack.json` of a pack.
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    /// Starting hand instead of the difficulty's
+    #[serde(default)]
+    hand: Option<Vec<String>>,
+}
+
+/// The plants and the starting hand a run is played with.
+#[derive(Debug, Clone)]
+pub struct Pack {
+    /// Packs merged into it, empty for the built-in plants
+    pub names: Vec<String>,
+    pub plants: Vec<Plant>,
+    pub hybrids: Vec<HybridRule>,
+    pub hand: Vec<String>,
+    /// Where the packs keep their board presets
+    pub preset_dirs: Vec<PathBuf>,
+    /// `--pack` as given, to read the same packs again
+    selection: Option<String>,
+    /// The difficulty's hand, for packs without one
+    default_hand: Vec<String>,
+}
+
+impl Pack {
+    /// The plants the game comes with, dealt `hand`.
+    pub fn builtin(hand: Vec<String>) -> Pack {
+        Pack {
+            names: Vec::new(),
+            plants: builtin_plants(),
+            hybrids: builtin_hybrids(),
+            hand: hand.clone(),
+            preset_dirs: Vec::new(),
+            selection: None,
+            default_hand: hand,
+        }
+    }
+
+    /// The file the plants were read from, `None` for several packs.
+    pub fn path(&self) -> Option<PathBuf> {
+        match self.names.as_slice() {
+            [] => Some(PathBuf::from(PLANTS_PATH)),
+            [name] => Some(Path::new(PACK_DIR).join(name).join("plants.json")),
+            _ => None,
+        }
+    }
+
+    pub fn plant_set(&self) -> Result<PlantSet, String> {
+        let mut set = PlantSet::new(
+            self.plants.clone(),
+            self.hybrids.clone(),
+            items::all(),
+            &self.hand,
+        )?;
+        set.packs = self.names.clone();
+        Ok(set)
+    }
+
+    /// The same packs read from disk once more. The built-in plants are
+    /// read from their copy in the forest pack.
+    pub fn reload(&self) -> Result<Pack, String> {
+        match &self.selection {
+            Some(selection) => load(Path::new(PACK_DIR), selection, &self.default_hand),
+            None => {
+                let file = read_plants(Path::new(PLANTS_PATH))?;
+                Ok(Pack {
+                    plants: file.plants,
+                    hybrids: file.hybrids,
+                    ..self.clone()
+                })
+            }
+        }
+    }
+}
+
+/// The plants of `names` in words, for messages.
+pub fn describe(names: &[String]) -> String {
+    match names {
+        [] => String::from("the built-in plants"),
+        [name] => format!("the {} pack", name),
+        names => format!("the packs {}", names.join(" + ")),
+    }
+}
+
+/// Names of the packs in `dir`, sorted.
+pub fn pack_names(dir: &Path) -> Vec<String> {
+    let mut names = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().join("plants.json").is_file())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Reads the pack in `dir`: its plants, tagged with the pack's name, its
+/// hybrids and its starting hand if it brings one.
+pub fn read(dir: &Path) -> Result<(PlantFile, Option<Vec<String>>), String> {
+    let name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut file = read_plants(&dir.join("plants.json"))?;
+    for plant in &mut file.plants {
+        plant.pack = name.clone();
+    }
+    let path = dir.join("pack.json");
+    let manifest = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str::<Manifest>(&contents)
+            .map_err(|err| format!("{}: {}", path.display(), err))?,
+        Err(_) => Manifest::default(),
+    };
+    Ok((file, manifest.hand))
+}
+
+/// Reads the packs `selection` names, one or `all`. Packs without a hand
+/// of their own are dealt `hand`. Every pack is checked on its own, then
+/// all of them together, and no two may have a plant of the same name.
+pub fn load(dir: &Path, selection: &str, hand: &[String]) -> Result<Pack, String> {
+    let available = pack_names(dir);
+    let names = match selection {
+        ALL if available.is_empty() => {
+            return Err(format!("there are no packs in {}", dir.display()))
+        }
+        ALL => available,
+        name if available.iter().any(|n| n == name) => vec![name.to_string()],
+        name => {
+            return Err(format!(
+                "unknown pack `{}`, available: {}, {}",
+                name,
+                available.join(", "),
+                ALL
+            ))
+        }
+    };
+
+    let items = items::all();
+    let mut plants = Vec::new();
+    let mut hybrids = Vec::new();
+    let mut hands = Vec::new();
+    let mut owners = HashMap::<String, String>::new();
+    for name in &names {
+        let (file, pack_hand) = read(&dir.join(name))?;
+        let pack_hand = pack_hand.unwrap_or_else(|| hand.to_vec());
+        let report = validate::check(&file.plants, &file.hybrids, &items, &pack_hand);
+        if !report.errors.is_empty() {
+            return Err(format!("pack {}: {}", name, report.errors.join("; ")));
+        }
+        for plant in &file.plants {
+            if let Some(other) = owners.insert(plant.name.to_string(), name.clone()) {
+                return Err(format!(
+                    "Plant <{}> is in both the {} and the {} pack",
+                    plant.name, other, name
+                ));
+            }
+        }
+        plants.extend(file.plants);
+        hybrids.extend(file.hybrids);
+        hands.extend(pack_hand);
+    }
+    let report = validate::check(&plants, &hybrids, &items, &hands);
+    if !report.errors.is_empty() {
+        return Err(format!("packs together: {}", report.errors.join("; ")));
+    }
+    Ok(Pack {
+        preset_dirs: names
+            .iter()
+            .map(|name| dir.join(name).join("presets"))
+            .filter(|dir| dir.is_dir())
+            .collect(),
+        names,
+        plants,
+        hybrids,
+        hand: hands,
+        selection: Some(selection.to_string()),
+        default_hand: hand.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor;
+
+    /// A pack directory holding each of `packs` with the built-in plants.
+    fn scratch(name: &str, packs: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-packs-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let plants = editor::to_json(&builtin_plants(), &builtin_hybrids());
+        for pack in packs {
+            fs::create_dir_all(dir.join(pack)).unwrap();
+            fs::write(dir.join(pack).join("plants.json"), &plants).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn all_packs_together_refuse_plants_of_the_same_name() {
+        let dir = scratch("collision", &["meadow", "marsh"]);
+        let hand = [String::from("Grass")];
+        for name in ["meadow", "marsh"] {
+            let pack = load(&dir, name, &hand).unwrap();
+            assert_eq!(pack.names, [name]);
+            assert!(pack.plants.iter().all(|p| p.pack == name));
+        }
+        assert_eq!(
+            load(&dir, ALL, &hand).err().unwrap(),
+            "Plant <Grass> is in both the marsh and the meadow pack"
+        );
+        let _ = fs::remove_dir_all(&dir);
+
+        let empty = scratch("none", &[]);
+        fs::create_dir_all(&empty).unwrap();
+        assert_eq!(
+            load(&empty, ALL, &hand).err().unwrap(),
+            format!("there are no packs in {}", empty.display())
+        );
+        let _ = fs::remove_dir_all(&empty);
+    }
+}
@@ -0,0 +1,288 @@
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, Wrap},
+    Terminal,
+};
+use serde::{Deserialize, Serialize};
+
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use crate::{
+    achievements::Unlocked,
+    centered_rect, persist,
+    records::Records,
+    theme::{Theme, ThemeName},
+    App, Card,
+};
+
+/// Bumped whenever a field of `Profile` changes meaning or goes away.
+/// Profiles of older versions are read with defaults for what they lack,
+/// newer ones are refused rather than overwritten.
+pub const PROFILE_VERSION: u32 = 1;
+
+/// Seeds banked per point of the final score, rounded down.
+pub const SEEDS_PER_POINT: f32 = 0.1;
+
+/// What carries over from one run to the next.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Profile {
+    pub version: u32,
+    /// Seeds banked and not spent yet
+    #[serde(default)]
+    pub seeds: u32,
+    /// Seeds banked over all runs
+    #[serde(default)]
+    pub earned: u32,
+    /// Cosmetic themes bought, the others are always available
+    #[serde(default)]
+    pub themes: Vec<ThemeName>,
+    /// Bests over all runs
+    #[serde(default)]
+    pub records: Records,
+    #[serde(default)]
+    pub achievements: Unlocked,
+    #[serde(skip)]
+    pub name: String,
+}
+
+pub fn profile_path(name: &str) -> PathBuf {
+    persist::data_dir()
+        .join("profiles")
+        .join(format!("{}.json", name))
+}
+
+/// Names end up in a file name, so only letters, digits, `-` and `_`.
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl Profile {
+    fn new(name: &str) -> Profile {
+        Profile {
+            version: PROFILE_VERSION,
+            seeds: 0,
+            earned: 0,
+            themes: Vec::new(),
+            records: Records::default(),
+            achievements: Unlocked::new(),
+            name: name.to_string(),
+        }
+    }
+
+    /// Reads the profile, starting a fresh one if there is none yet. A
+    /// corrupt file is backed up and replaced like the score table.
+    pub fn load(name: &str) -> io::Result<Profile> {
+        if !valid_name(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid profile name `{}`, use letters, digits, - and _",
+                    name
+                ),
+            ));
+        }
+        let path = profile_path(name);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Profile::new(name)),
+            Err(err) => return Err(err),
+        };
+        let mut profile = match serde_json::from_str::<Profile>(&contents) {
+            Ok(profile) => profile,
+            Err(_) => {
+                persist::back_up_corrupt(&path)?;
+                return Ok(Profile::new(name));
+            }
+        };
+        if profile.version > PROFILE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "profile `{}` is version {}, this game only knows up to {}",
+                    name, profile.version, PROFILE_VERSION
+                ),
+            ));
+        }
+        profile.version = PROFILE_VERSION;
+        profile.name = name.to_string();
+        Ok(profile)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = profile_path(&self.name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        persist::write_atomic(&path, json.as_bytes())
+    }
+
+    /// Adds the seeds a final score is worth and returns how many.
+    pub fn bank(&mut self, score: f32) -> u32 {
+        let seeds = (score.max(0.0) * SEEDS_PER_POINT).floor() as u32;
+        self.seeds += seeds;
+        self.earned += seeds;
+        seeds
+    }
+
+    pub fn owns(&self, theme: ThemeName) -> bool {
+        !theme.cosmetic() || self.themes.contains(&theme)
+    }
+}
+
+/// Something the loadout screen sells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Offer {
+    /// An extra card in this run's starting hand
+    Card(&'static str),
+    /// A cosmetic theme, kept for every later run
+    Theme(ThemeName),
+    Start,
+}
+
+const OFFERS: [(Offer, u32); 5] = [
+    (Offer::Card("Grass"), 5),
+    (Offer::Card("Shrub"), 15),
+    (Offer::Card("Fertilizer"), 20),
+    (Offer::Theme(ThemeName::Autumn), 50),
+    (Offer::Start, 0),
+];
+
+impl Display for Offer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Offer::Card(name) => write!(f, "Extra {} card", name),
+            Offer::Theme(theme) => write!(f, "{:?} theme", theme),
+            Offer::Start => f.write_str("Start the run"),
+        }
+    }
+}
+
+/// Lets the player spend seeds on the run about to start, before the board
+/// is shown. Purchases are saved to the profile right away.
+pub fn loadout<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
+    let mut list = ListState::default();
+    list.select(Some(0));
+    let mut msg = String::from("Enter buys, Esc starts the run");
+    loop {
+        let Some(profile) = app.profile.as_ref() else {
+            return Ok(());
+        };
+        draw(terminal, app, profile, &mut list, &msg)?;
+
+        let selected = list.selected().unwrap_or(0);
+        match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Up => list.select(Some((selected + OFFERS.len() - 1) % OFFERS.len())),
+                KeyCode::Down => list.select(Some((selected + 1) % OFFERS.len())),
+                KeyCode::Enter | KeyCode::Char(' ') => match buy(app, OFFERS[selected])? {
+                    Some(outcome) => msg = outcome,
+                    None => return Ok(()),
+                },
+                KeyCode::Esc | KeyCode::Char('s') => return Ok(()),
+                _ => {}
+            },
+            Event::Resize(..) => terminal.autoresize()?,
+            _ => {}
+        }
+    }
+}
+
+/// Spends the seeds on an offer and says how it went, `None` to start.
+fn buy(app: &mut App, (offer, price): (Offer, u32)) -> io::Result<Option<String>> {
+    let Some(profile) = app.profile.as_mut() else {
+        return Ok(None);
+    };
+    let outcome = match offer {
+        Offer::Start => return Ok(None),
+        Offer::Theme(theme) if profile.owns(theme) => {
+            app.theme = Theme::new(theme);
+            return Ok(Some(format!("Switched to the {}", offer)));
+        }
+        _ if profile.seeds < price => {
+            return Ok(Some(format!(
+                "{} costs {} seeds, you have {}",
+                offer, price, profile.seeds
+            )))
+        }
+        Offer::Card(_) if app.game.hand_full() => {
+            return Ok(Some(String::from("Your hand is full")))
+        }
+        Offer::Card(name) => match app.game.card_named(name) {
+            Some(card) => {
+                app.game.add_to_hand([card]);
+                format!("Bought an extra {} card", name)
+            }
+            None => return Ok(Some(format!("There is no {} in this game", name))),
+        },
+        Offer::Theme(theme) => {
+            profile.themes.push(theme);
+            app.theme = Theme::new(theme);
+            format!("Bought the {}", offer)
+        }
+    };
+    profile.seeds -= price;
+    profile.save()?;
+    Ok(Some(outcome))
+}
+
+fn draw<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &App,
+    profile: &Profile,
+    list: &mut ListState,
+    msg: &str,
+) -> io::Result<()> {
+    let theme = &app.theme;
+    let items = OFFERS
+        .iter()
+        .map(|&(offer, price)| match offer {
+            Offer::Start => ListItem::new(offer.to_string()),
+            Offer::Theme(theme) if profile.owns(theme) => {
+                ListItem::new(format!("{} (owned)", offer))
+            }
+            _ => ListItem::new(format!("{:<24} {:>3} seeds", offer.to_string(), price)),
+        })
+        .collect::<Vec<ListItem>>();
+    let hand = app
+        .game
+        .hand
+        .iter()
+        .map(Card::name)
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let label = Style::default().fg(theme.label);
+    let info = vec![
+        Line::from(Span::styled(format!("Starting hand: {}", hand), label)),
+        Line::from(""),
+        Line::from(msg.to_string()),
+    ];
+    terminal.draw(|f| {
+        let area = centered_rect(60, 60, f.area());
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(5)].as_ref())
+            .split(area);
+        let title = format!(
+            " Loadout // Profile {} // Seeds: {} ",
+            profile.name, profile.seeds
+        );
+        let list_widget = List::new(items)
+            .block(theme.panel(title, true))
+            .highlight_style(theme.highlight())
+            .highlight_symbol(theme.highlight_symbol);
+        f.render_stateful_widget(list_widget, chunks[0], list);
+        let paragraph = Paragraph::new(info)
+            .block(theme.panel("", false))
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, chunks[1]);
+    })?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use serde::Deserialize;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    layout::{self, BoardLayout},
+    PlantSet,
+};
+
+/// Folder `--puzzle` looks in for names that aren't a file.
+pub const PUZZLE_DIR: &str = "assets/puzzles";
+
+/// A puzzle as written in its file. The board is square, as many rows as
+/// each has tiles, in the format of `--board` files.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PuzzleFile {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    board: Vec<String>,
+    hand: Vec<String>,
+    seed: u64,
+    rounds: u32,
+    target: f32,
+    /// Highest score anyone is known to have reached
+    #[serde(default)]
+    best: Option<f32>,
+    /// Whether harvests and hybrids hand out cards, off so the hand is all
+    /// there is
+    #[serde(default)]
+    drops: bool,
+}
+
+/// What a puzzle run is measured against.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub name: String,
+    pub description: Option<String>,
+    pub seed: u64,
+    pub rounds: u32,
+    pub target: f32,
+    pub best: Option<f32>,
+    pub drops: bool,
+    /// Width and height of the board
+    pub size: usize,
+}
+
+impl Puzzle {
+    pub fn solved(&self, score: f32) -> bool {
+        score >= self.target
+    }
+}
+
+/// Names of the puzzles in `dir`, sorted.
+pub fn puzzle_names(dir: &Path) -> Vec<String> {
+    let mut names = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+        .collect::<Vec<String>>();
+    names.sort();
+    names
+}
+
+/// Reads the puzzle `name`: a file, or one of `PUZZLE_DIR` by its name.
+/// The board and hand are checked against the plants in `set`.
+pub fn load(name: &Path, set: &PlantSet) -> Result<(Puzzle, BoardLayout), String> {
+    let path = match name.is_file() {
+        true => name.to_path_buf(),
+        false => PathBuf::from(PUZZLE_DIR).join(name).with_extension("json"),
+    };
+    let contents = fs::read_to_string(&path).map_err(|_| {
+        format!(
+            "unknown puzzle `{}`, available: {}",
+            name.display(),
+            puzzle_names(Path::new(PUZZLE_DIR)).join(", ")
+        )
+    })?;
+    let file = serde_json::from_str::<PuzzleFile>(&contents)
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+    check(file, set).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+fn check(file: PuzzleFile, set: &PlantSet) -> Result<(Puzzle, BoardLayout), String> {
+    let size = file.board.len();
+    if size == 0 {
+        return Err(String::from("the board has no rows"));
+    }
+    let mut layout =
+        layout::from_rows(&file.board, set, size, size).map_err(|err| format!("board {}", err))?;
+    if let Some(name) = file.hand.iter().find(|name| set.card_id(name).is_none()) {
+        return Err(format!("no plant or item is called <{}>", name));
+    }
+    if file.rounds == 0 {
+        return Err(String::from("a puzzle needs at least one round"));
+    }
+    if !file.target.is_finite() || file.target <= 0.0 {
+        return Err(format!("a target of {} is no puzzle", file.target));
+    }
+    layout.hand = Some(file.hand);
+    let puzzle = Puzzle {
+        name: file.name,
+        description: file.description,
+        seed: file.seed,
+        rounds: file.rounds,
+        target: file.target,
+        best: file.best,
+        drops: file.drops,
+        size,
+    };
+    Ok((puzzle, layout))
+}
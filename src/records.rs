@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use crate::format_points;
+
+/// Bests of a single run, or of every run a profile played. Fields that
+/// are missing in an older file start at zero.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Records {
+    pub biggest_size: u32,
+    pub biggest_plant: String,
+    /// Rounds a plant stayed on the board
+    pub oldest_age: u32,
+    pub oldest_plant: String,
+    /// Points of one harvest
+    pub best_harvest: f32,
+    pub best_harvest_plant: String,
+    /// Plants on the board at the same time
+    pub most_plants: usize,
+    /// Rounds of one run
+    pub longest_run: u32,
+}
+
+impl Records {
+    pub fn note_size(&mut self, plant: &str, size: u32) {
+        if size > self.biggest_size {
+            self.biggest_size = size;
+            self.biggest_plant = plant.to_string();
+        }
+    }
+
+    pub fn note_age(&mut self, plant: &str, age: u32) {
+        if age > self.oldest_age {
+            self.oldest_age = age;
+            self.oldest_plant = plant.to_string();
+        }
+    }
+
+    pub fn note_harvest(&mut self, plant: &str, points: f32) {
+        if points > self.best_harvest {
+            self.best_harvest = points;
+            self.best_harvest_plant = plant.to_string();
+        }
+    }
+
+    /// Takes over what `run` did better and returns the labels of the
+    /// records it broke.
+    pub fn merge(&mut self, run: &Records) -> Vec<&'static str> {
+        let mut broken = Vec::new();
+        if run.biggest_size > self.biggest_size {
+            self.note_size(&run.biggest_plant, run.biggest_size);
+            broken.push(BIGGEST);
+        }
+        if run.oldest_age > self.oldest_age {
+            self.note_age(&run.oldest_plant, run.oldest_age);
+            broken.push(OLDEST);
+        }
+        if run.best_harvest > self.best_harvest {
+            self.note_harvest(&run.best_harvest_plant, run.best_harvest);
+            broken.push(BEST_HARVEST);
+        }
+        if run.most_plants > self.most_plants {
+            self.most_plants = run.most_plants;
+            broken.push(MOST_PLANTS);
+        }
+        if run.longest_run > self.longest_run {
+            self.longest_run = run.longest_run;
+            broken.push(LONGEST_RUN);
+        }
+        broken
+    }
+
+    /// Label and value of every record, `-` for those never set.
+    pub fn lines(&self) -> [(&'static str, String); 5] {
+        let feat = |value: String, plant: &str| match plant {
+            "" => String::from("-"),
+            _ => format!("{}, {}", plant, value),
+        };
+        let count = |value: usize, what: &str| match value {
+            0 => String::from("-"),
+            _ => format!("{} {}", value, what),
+        };
+        [
+            (
+                BIGGEST,
+                feat(format!("size {}", self.biggest_size), &self.biggest_plant),
+            ),
+            (
+                OLDEST,
+                feat(format!("{} rounds", self.oldest_age), &self.oldest_plant),
+            ),
+            (
+                BEST_HARVEST,
+                feat(
+                    format!("{} points", format_points(self.best_harvest)),
+                    &self.best_harvest_plant,
+                ),
+            ),
+            (MOST_PLANTS, count(self.most_plants, "at once")),
+            (LONGEST_RUN, count(self.longest_run as usize, "rounds")),
+        ]
+    }
+
+    pub fn value_of(&self, label: &str) -> String {
+        self.lines()
+            .into_iter()
+            .find(|(l, _)| *l == label)
+            .map_or_else(String::new, |(_, value)| value)
+    }
+}
+
+pub const BIGGEST: &str = "Biggest plant";
+pub const OLDEST: &str = "Oldest plant";
+pub const BEST_HARVEST: &str = "Best harvest";
+pub const MOST_PLANTS: &str = "Most plants";
+pub const LONGEST_RUN: &str = "Longest run";
+
+/// Prints the records of a profile for `--records`.
+pub fn print_records(profile: &str, records: &Records) {
+    println!("Records of profile {}", profile);
+    for (label, value) in records.lines() {
+        println!("  {:<14} {}", label, value);
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// A plant that left the board for points in a round update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Matured {
+    pub plant: String,
+    /// Name of the tile it stood on, like `B3`
+    pub tile: String,
+    pub points: f32,
+    /// Cards it dropped and hybrids it bred, as they went into the hand
+    pub cards: Vec<String>,
+    /// Drops planted next to it instead, like `Grass at B2`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sown: Vec<String>,
+}
+
+/// Points a round gave for the board itself rather than for a harvest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Passive {
+    /// What scored, like `5 plants on the board`
+    pub name: String,
+    pub points: f32,
+}
+
+/// What one round update did, for the summary after Next Round.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundReport {
+    /// The round that just ended
+    pub round: u32,
+    pub matured: Vec<Matured>,
+    /// Pests, storms, spreads, clouds and weather changes, as logged
+    pub events: Vec<String>,
+    /// Harvest streak going into the round and coming out of it
+    #[serde(default)]
+    pub streak_before: u32,
+    #[serde(default)]
+    pub streak: u32,
+    /// Scored for the board after the harvest
+    #[serde(default)]
+    pub passives: Vec<Passive>,
+}
+
+impl RoundReport {
+    /// Nothing happened that would be worth a summary. The passives come
+    /// every round and don't bring one up on their own.
+    pub fn is_empty(&self) -> bool {
+        self.matured.is_empty() && self.events.is_empty() && !self.streak_broke()
+    }
+
+    pub fn streak_grew(&self) -> bool {
+        self.streak > self.streak_before
+    }
+
+    pub fn streak_broke(&self) -> bool {
+        self.streak == 0 && self.streak_before > 0
+    }
+
+    /// Harvests and passives together.
+    pub fn points(&self) -> f32 {
+        let harvested = self.matured.iter().map(|m| m.points).sum::<f32>();
+        harvested + self.passives.iter().map(|p| p.points).sum::<f32>()
+    }
+
+    /// Every card the round handed out.
+    pub fn cards(&self) -> impl Iterator<Item = &str> {
+        self.matured
+            .iter()
+            .flat_map(|m| m.cards.iter().map(String::as_str))
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Game;
+
+/// Bumped whenever the serialized layout of `Game` changes incompatibly.
+pub const SAVE_VERSION: u32 = 5;
+
+#[derive(Serialize)]
+struct SaveRef<'a> {
+    version: u32,
+    width: usize,
+    height: usize,
+    game: &'a Game,
+}
+
+#[derive(Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub game: Game,
+}
+
+pub fn to_json(game: &Game) -> serde_json::Result<String> {
+    serde_json::to_string(&SaveRef {
+        version: SAVE_VERSION,
+        width: game.width(),
+        height: game.height(),
+        game,
+    })
+}
+
+pub fn from_json(contents: &str) -> Result<SaveFile, String> {
+    let save: SaveFile = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    if save.version != SAVE_VERSION {
+        return Err(format!(
+            "unsupported save version {}, expected {}",
+            save.version, SAVE_VERSION
+        ));
+    }
+    Ok(save)
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    difficulty::Level, format_points, mode::GameMode, mutators, mutators::Mutator, persist, Game,
+    GlobalSetting, HarvestMode,
+};
+
+/// Entries kept per board configuration.
+pub const MAX_ENTRIES: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub score: f32,
+    pub rounds: u32,
+    pub width: usize,
+    pub height: usize,
+    pub seed: u64,
+    #[serde(default)]
+    pub difficulty: Level,
+    #[serde(default)]
+    pub mutators: Vec<Mutator>,
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Unix timestamp of the end of the run
+    pub timestamp: u64,
+    /// Time played, without the pauses
+    #[serde(default)]
+    pub played_ms: u64,
+}
+
+/// The best runs, grouped by board configuration so different setups never
+/// compete with each other.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScoreTable {
+    boards: BTreeMap<String, Vec<ScoreEntry>>,
+}
+
+/// Identifies the board configuration a score was reached on. Normal runs
+/// keep the keys from before difficulties existed.
+pub fn board_key(settings: &GlobalSetting) -> String {
+    let mut key = match settings.round_limit {
+        Some(limit) => format!("{}x{}, {} rounds", settings.width, settings.height, limit),
+        None => format!("{}x{}", settings.width, settings.height),
+    };
+    if settings.difficulty.level != Level::Normal {
+        key.push_str(&format!(", {}", settings.difficulty.level));
+    }
+    match settings.mode {
+        GameMode::Standard => {}
+        // Sprints are races on the same board, so every seed ranks apart
+        GameMode::Sprint => key.push_str(&format!(", sprint, seed {}", settings.seed)),
+        GameMode::Endless => key.push_str(", endless"),
+        GameMode::Puzzle => {
+            if let Some(puzzle) = &settings.puzzle {
+                key.push_str(&format!(", puzzle {}", puzzle.name));
+            }
+        }
+    }
+    if settings.harvest_mode == HarvestMode::Manual {
+        key.push_str(", manual harvests");
+    }
+    if let Some(rounds) = settings.freshness {
+        key.push_str(&format!(", cards stale after {} rounds", rounds));
+    }
+    if !settings.mutators.is_empty() {
+        key.push_str(&format!(", {}", mutators::list(&settings.mutators)));
+    }
+    key
+}
+
+pub fn scores_path() -> PathBuf {
+    persist::data_dir().join("scores.json")
+}
+
+impl ScoreTable {
+    /// Reads the table, starting a fresh one if the file is missing. A
+    /// corrupt file is backed up and replaced instead of aborting the game.
+    pub fn load(path: &Path) -> io::Result<ScoreTable> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(ScoreTable::default()),
+            Err(err) => return Err(err),
+        };
+        match serde_json::from_str(&contents) {
+            Ok(table) => Ok(table),
+            Err(_) => {
+                persist::back_up_corrupt(path)?;
+                Ok(ScoreTable::default())
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        persist::write_atomic(path, json.as_bytes())
+    }
+
+    /// Inserts a run and returns its rank, `None` if it didn't make the top.
+    pub fn insert(&mut self, key: String, entry: ScoreEntry) -> Option<usize> {
+        let entries = self.boards.entry(key).or_default();
+        let rank = entries
+            .iter()
+            .position(|e| entry.score > e.score)
+            .unwrap_or(entries.len());
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+        entries.insert(rank, entry);
+        entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+
+    pub fn entries(&self, key: &str) -> &[ScoreEntry] {
+        self.boards.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn boards(&self) -> impl Iterator<Item = (&String, &Vec<ScoreEntry>)> {
+        self.boards.iter()
+    }
+}
+
+impl ScoreEntry {
+    pub fn from_game(game: &Game) -> ScoreEntry {
+        ScoreEntry {
+            score: game.points(),
+            rounds: game.round,
+            width: game.width(),
+            height: game.height(),
+            seed: game.seed,
+            difficulty: game.difficulty,
+            mutators: game.mutators.clone(),
+            mode: game.mode,
+            timestamp: persist::unix_now(),
+            played_ms: game.played_ms,
+        }
+    }
+
+    pub fn row(&self, rank: usize) -> String {
+        let mut row = format!(
+            "{:>2}. {:>8} {:>4} rounds  {}x{} {:<6}  seed {:<20} {}",
+            rank + 1,
+            format_points(self.score),
+            self.rounds,
+            self.width,
+            self.height,
+            self.difficulty,
+            self.seed,
+            persist::format_date(self.timestamp)
+        );
+        if !self.mutators.is_empty() {
+            row.push_str(&format!("  {}", mutators::list(&self.mutators)));
+        }
+        row
+    }
+}
+
+/// Prints every board's table for `--scores`.
+pub fn print_scores(table: &ScoreTable) {
+    let mut any = false;
+    for (key, entries) in table.boards() {
+        any = true;
+        println!("{}", key);
+        for (rank, entry) in entries.iter().enumerate() {
+            println!("  {}", entry.row(rank));
+        }
+        println!();
+    }
+    if !any {
+        println!("No high scores yet.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::game;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-scores-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("scores.json")
+    }
+
+    #[test]
+    fn a_corrupt_table_is_backed_up_and_started_over() {
+        let path = scratch("corrupt");
+        fs::write(&path, "{\"boards\": {\"6x6\": [").unwrap();
+        let table = ScoreTable::load(&path).unwrap();
+        assert_eq!(table.boards().count(), 0);
+        assert!(!path.exists());
+        let backup = path.with_file_name("scores.json.corrupt");
+        assert_eq!(
+            fs::read_to_string(&backup).unwrap(),
+            "{\"boards\": {\"6x6\": ["
+        );
+
+        // The fresh table saves over the old name, the backup stays
+        let mut table = table;
+        let game = game(&[]);
+        let key = board_key(&game.settings);
+        assert_eq!(
+            table.insert(key.clone(), ScoreEntry::from_game(&game)),
+            Some(0)
+        );
+        table.save(&path).unwrap();
+        assert_eq!(ScoreTable::load(&path).unwrap().entries(&key).len(), 1);
+        assert!(backup.exists());
+    }
+
+    #[test]
+    fn a_missing_table_is_empty_and_nothing_is_backed_up() {
+        let path = scratch("missing");
+        let _ = fs::remove_file(&path);
+        assert_eq!(ScoreTable::load(&path).unwrap().boards().count(), 0);
+        assert!(!path.with_file_name("scores.json.corrupt").exists());
+    }
+}
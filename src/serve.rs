@@ -0,0 +1,459 @@
+//! `rogue_forest serve --stdio`: one run for a bot on the other end of a
+//! pipe, in newline-delimited JSON. Every line read from stdin is a
+//! command, every line written to stdout a response. The first response
+//! comes before any command and holds the starting state.
+//!
+//! ```text
+//! {"action":"place","x":2,"y":3,"hand_index":0}
+//! {"action":"clear_pest","x":1,"y":0}     also "water" and "harvest"
+//! {"action":"next_round"}
+//! {"action":"draft","index":1}           without an index, takes nothing
+//! {"action":"state"}                      only answers with the state
+//! {"action":"quit"}                       answers one last time
+//!
+//! {"version":1,"events":[..],"state":{..}}
+//! {"version":1,"error":{"code":"illegal","message":"Tile occupied"},"events":[],"state":{..}}
+//! ```
+//!
+//! `x` counts columns from the left and `y` rows from the bottom, both from
+//! 0 like the board of an export. `hand_index` is the card's place in
+//! `state.hand`. The events are `engine::GameEvent`s, the state is a
+//! `StateView`. A command that fails says why in `error` and the run goes
+//! on: `parse` for lines that aren't a command, `out_of_range` for tiles
+//! off the board and cards past the hand, `game_over` once the run is over
+//! and `illegal` when the rules refuse it. While `state.draft` lists
+//! rewards, `draft` is the only command the rules take.
+//!
+//! Commands are played like the keys would play them, so they follow the
+//! same rules: clearing pests and watering need a card in hand and a move
+//! left, the way the cursor only gets onto the board with both.
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    io::{self, BufRead, Write},
+    iter,
+};
+
+use crate::{
+    action::Action,
+    engine::{Engine, GameEvent},
+    export::{self, ExportTile},
+    trace,
+    weather::Weather,
+    Card, Game, State, Tile,
+};
+
+/// Bumped whenever a field of a command or a response changes meaning or
+/// goes away. New fields can be added without a bump.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum Command {
+    Place {
+        x: usize,
+        y: usize,
+        hand_index: usize,
+    },
+    ClearPest {
+        x: usize,
+        y: usize,
+    },
+    Water {
+        x: usize,
+        y: usize,
+    },
+    /// Pick a ripe plant, with `--harvest manual`
+    Harvest {
+        x: usize,
+        y: usize,
+    },
+    NextRound,
+    /// Take the reward at `index` of `state.draft`, or none
+    Draft {
+        #[serde(default)]
+        index: Option<usize>,
+    },
+    State,
+    Quit,
+}
+
+#[derive(Debug, Serialize)]
+struct ProtocolError {
+    code: &'static str,
+    message: String,
+}
+
+impl ProtocolError {
+    fn new(code: &'static str, message: impl Into<String>) -> ProtocolError {
+        ProtocolError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Response<'a> {
+    version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ProtocolError>,
+    events: &'a [GameEvent],
+    state: StateView,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HandCard {
+    pub name: String,
+    /// `plant` or `item`
+    pub kind: &'static str,
+    /// `None` for items
+    pub max_age: Option<u32>,
+    /// Rounds it keeps in hand, `None` if cards never go stale
+    pub freshness: Option<u32>,
+}
+
+/// What a bot sees of the run.
+#[derive(Debug, Serialize)]
+pub struct StateView {
+    pub width: usize,
+    pub height: usize,
+    pub round: u32,
+    /// `None` without a round limit
+    pub rounds_left: Option<u32>,
+    pub score: f32,
+    pub game_over: bool,
+    pub weather: Weather,
+    pub forecast: Weather,
+    pub water: u32,
+    /// Cards that can still be played this round, `None` without a limit
+    pub actions_left: Option<u32>,
+    /// In the order `hand_index` counts
+    pub hand: Vec<HandCard>,
+    /// Every occupied tile
+    pub board: Vec<ExportTile>,
+    /// The rewards of a running draft, empty without one
+    pub draft: Vec<String>,
+}
+
+impl StateView {
+    fn of(game: &Game) -> StateView {
+        let hand = game
+            .hand
+            .iter()
+            .map(|card| match card {
+                Card::Plant(plant) => HandCard {
+                    name: plant.name.to_string(),
+                    kind: "plant",
+                    max_age: Some(plant.max_age),
+                    freshness: game.freshness_left(card),
+                },
+                Card::Item(item) => HandCard {
+                    name: item.name.to_string(),
+                    kind: "item",
+                    max_age: None,
+                    freshness: game.freshness_left(card),
+                },
+            })
+            .collect();
+        StateView {
+            width: game.width(),
+            height: game.height(),
+            round: game.round,
+            rounds_left: game.rounds_left(),
+            score: game.total_points(),
+            game_over: game.state == State::GameOver,
+            weather: game.weather,
+            forecast: game.forecast,
+            water: game.water,
+            actions_left: game.actions_left(),
+            hand,
+            board: export::board(game),
+            draft: game
+                .draft
+                .iter()
+                .flat_map(|draft| &draft.rewards)
+                .map(|reward| reward.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Plays `game` for the commands on stdin until `quit` or the end of the
+/// input.
+pub fn run(game: Game) -> io::Result<()> {
+    let mut engine = Engine::from(game);
+    let mut out = io::stdout().lock();
+    respond(&mut out, &engine, None, &[])?;
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut events = Vec::new();
+        let command = serde_json::from_str::<Command>(&line)
+            .map_err(|err| ProtocolError::new("parse", err.to_string()));
+        let quit = matches!(command, Ok(Command::Quit));
+        let error = command
+            .and_then(|command| execute(&mut engine, command, &mut events))
+            .err();
+        trace::events(&events);
+        respond(&mut out, &engine, error, &events)?;
+        if quit {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn respond(
+    out: &mut impl Write,
+    engine: &Engine,
+    error: Option<ProtocolError>,
+    events: &[GameEvent],
+) -> io::Result<()> {
+    let response = Response {
+        version: PROTOCOL_VERSION,
+        error,
+        events,
+        state: StateView::of(engine.view()),
+    };
+    serde_json::to_writer(&mut *out, &response)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+fn execute(
+    engine: &mut Engine,
+    command: Command,
+    events: &mut Vec<GameEvent>,
+) -> Result<(), ProtocolError> {
+    if matches!(command, Command::State | Command::Quit) {
+        return Ok(());
+    }
+    let game = engine.view();
+    if game.state == State::GameOver {
+        return Err(ProtocolError::new("game_over", "The run is over"));
+    }
+    if let Command::Place { x, y, .. }
+    | Command::ClearPest { x, y }
+    | Command::Water { x, y }
+    | Command::Harvest { x, y } = command
+    {
+        if x >= game.width() || y >= game.height() {
+            return Err(ProtocolError::new(
+                "out_of_range",
+                format!(
+                    "({}, {}) is off the {}x{} board",
+                    x,
+                    y,
+                    game.width(),
+                    game.height()
+                ),
+            ));
+        }
+    }
+    let offered = game.draft.as_ref().map_or(0, |draft| draft.rewards.len());
+    match (&command, game.state == State::Drafting) {
+        (Command::Draft { .. }, false) => {
+            return Err(ProtocolError::new("illegal", "No draft is running"));
+        }
+        (Command::Draft { index: Some(index) }, true) if *index >= offered => {
+            return Err(ProtocolError::new(
+                "out_of_range",
+                format!("The draft offers {} rewards", offered),
+            ));
+        }
+        (Command::Draft { .. }, true) | (_, false) => {}
+        (_, true) => {
+            return Err(ProtocolError::new(
+                "illegal",
+                "A draft is running, only draft is taken",
+            ));
+        }
+    }
+    let before = Progress::of(game);
+    let result = match command {
+        Command::Place { x, y, hand_index } => place(engine, x, y, hand_index, events),
+        Command::ClearPest { x, y } => with_card(engine, x, y, Action::ClearPest, events),
+        Command::Water { x, y } => with_card(engine, x, y, Action::Water, events),
+        Command::Harvest { x, y } => {
+            let mut play = |action| events.extend(engine.apply(action));
+            play(Action::Inspect);
+            move_to(&mut play, x, y);
+            play(Action::Harvest);
+            Ok(())
+        }
+        Command::NextRound => {
+            events.extend(engine.apply(Action::NextRound));
+            Ok(())
+        }
+        Command::Draft { index } => {
+            let mut play = |action| events.extend(engine.apply(action));
+            match index {
+                Some(index) => {
+                    play(Action::Select(index));
+                    play(Action::Place);
+                }
+                None => play(Action::SkipDraft),
+            }
+            Ok(())
+        }
+        Command::State | Command::Quit => Ok(()),
+    };
+    back_to_hand(engine, events);
+    result?;
+    let after = Progress::of(engine.view());
+    let done = match command {
+        Command::Place { .. } => after.placed > before.placed,
+        Command::ClearPest { x, y } => before.infested(x, y) && !after.infested(x, y),
+        Command::Water { .. } => after.water < before.water,
+        Command::Harvest { .. } => after.picked > before.picked,
+        _ => true,
+    };
+    if done {
+        return Ok(());
+    }
+    // The rules explain themselves in the notice of the refused key
+    let message = last_notice(events).unwrap_or_else(|| String::from("Nothing happened"));
+    Err(ProtocolError::new("illegal", message))
+}
+
+/// What tells whether a command did anything.
+struct Progress {
+    placed: u32,
+    picked: u32,
+    water: u32,
+    infested: Vec<(usize, usize)>,
+}
+
+impl Progress {
+    fn of(game: &Game) -> Progress {
+        Progress {
+            placed: game.placed,
+            picked: game.picked,
+            water: game.water,
+            infested: game
+                .tile
+                .iter_coords()
+                .filter(|&(x, y)| matches!(game.tile.get(x, y), Some(Tile::Infested(..))))
+                .collect(),
+        }
+    }
+
+    fn infested(&self, x: usize, y: usize) -> bool {
+        self.infested.contains(&(x, y))
+    }
+}
+
+/// Puts the cursor on `(x, y)` with the edge jumps and steps of the keys.
+fn move_to(play: &mut impl FnMut(Action), x: usize, y: usize) {
+    let steps = iter::repeat_n(Action::Right, x).chain(iter::repeat_n(Action::Up, y));
+    for action in [Action::LeftEdge, Action::BottomEdge]
+        .into_iter()
+        .chain(steps)
+    {
+        play(action);
+    }
+}
+
+fn place(
+    engine: &mut Engine,
+    x: usize,
+    y: usize,
+    hand_index: usize,
+    events: &mut Vec<GameEvent>,
+) -> Result<(), ProtocolError> {
+    let hand = engine.view().hand.len();
+    if hand_index >= hand {
+        return Err(ProtocolError::new(
+            "out_of_range",
+            format!("The hand holds {} cards", hand),
+        ));
+    }
+    pick_up(engine, hand_index, events)?;
+    let mut play = |action| events.extend(engine.apply(action));
+    move_to(&mut play, x, y);
+    play(Action::Place);
+    Ok(())
+}
+
+/// Picks up the first card to get the cursor onto the board, then plays
+/// `action` on `(x, y)`.
+fn with_card(
+    engine: &mut Engine,
+    x: usize,
+    y: usize,
+    action: Action,
+    events: &mut Vec<GameEvent>,
+) -> Result<(), ProtocolError> {
+    if engine.view().hand.is_empty() {
+        return Err(ProtocolError::new(
+            "illegal",
+            "The cursor only gets onto the board with a card in hand",
+        ));
+    }
+    pick_up(engine, 0, events)?;
+    let mut play = |action| events.extend(engine.apply(action));
+    move_to(&mut play, x, y);
+    play(action);
+    Ok(())
+}
+
+/// Chooses the card at `hand_index` of `game.hand` and takes it onto the
+/// board. The keys count slots of the chooser, which may group and sort
+/// the hand, so the card is looked up there first.
+fn pick_up(
+    engine: &mut Engine,
+    hand_index: usize,
+    events: &mut Vec<GameEvent>,
+) -> Result<(), ProtocolError> {
+    let game = engine.view();
+    // Space would end the round instead
+    if game.out_of_moves() {
+        return Err(ProtocolError::new(
+            "illegal",
+            "No moves left, only next_round",
+        ));
+    }
+    let row = game.stack_of(hand_index);
+    let Some(slot) = game.visible_hand().iter().position(|&idx| idx == row) else {
+        return Err(ProtocolError::new(
+            "illegal",
+            "The card is hidden in the chooser",
+        ));
+    };
+    let (taken, selected) = engine.try_apply(Action::Select(slot));
+    events.extend(selected);
+    if !taken || engine.view().choosing.index != Some(row) {
+        return Err(ProtocolError::new("illegal", "The card can't be chosen"));
+    }
+    events.extend(engine.apply(Action::Place));
+    if engine.view().state != State::Placing {
+        return Err(ProtocolError::new(
+            "illegal",
+            last_notice(events).unwrap_or_else(|| String::from("The card can't be taken out")),
+        ));
+    }
+    Ok(())
+}
+
+/// What the rules said about the last key they refused.
+fn last_notice(events: &[GameEvent]) -> Option<String> {
+    events.iter().rev().find_map(|event| match event {
+        GameEvent::Notice(notice) => Some(notice.clone()),
+        _ => None,
+    })
+}
+
+/// Leaves the board or the round panel again, so every command starts
+/// from the hand.
+fn back_to_hand(engine: &mut Engine, events: &mut Vec<GameEvent>) {
+    let action = match engine.view().state {
+        State::Placing | State::Inspect => Action::Tab,
+        State::NextRound => Action::BackTab,
+        State::Choosing | State::Drafting | State::GameOver => return,
+    };
+    events.extend(engine.apply(action));
+}
@@ -0,0 +1,340 @@
+use clap::ValueEnum;
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, Wrap},
+    Terminal,
+};
+use serde::{Deserialize, Serialize};
+
+use std::io;
+
+use crate::{
+    centered_rect,
+    config::{self, Config},
+    difficulty::Level,
+    mutators::Mutator,
+    theme::Theme,
+    trace,
+};
+
+/// Board sizes the setup screen accepts.
+pub const MIN_SIZE: usize = 2;
+pub const MAX_SIZE: usize = 40;
+
+/// What a run is set up with when the game is started without flags.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Setup {
+    pub size: usize,
+    pub difficulty: Level,
+    /// Random for every run if not given
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub mutators: Vec<Mutator>,
+}
+
+impl Default for Setup {
+    fn default() -> Setup {
+        Setup {
+            size: 6,
+            difficulty: Level::Normal,
+            seed: None,
+            mutators: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Size,
+    Difficulty,
+    Seed,
+    Mutator(Mutator),
+    LastSettings,
+    Start,
+}
+
+/// The setup screen while it is open. Size and seed are kept as typed, so
+/// a half-typed number can be shown and checked.
+struct Screen {
+    fields: Vec<Field>,
+    list: ListState,
+    size: String,
+    difficulty: Level,
+    seed: String,
+    mutators: Vec<Mutator>,
+    last: Option<Setup>,
+    msg: String,
+}
+
+impl Screen {
+    fn new(last: Option<Setup>) -> Screen {
+        let mut fields = vec![Field::Size, Field::Difficulty, Field::Seed];
+        fields.extend(Mutator::value_variants().iter().map(|&m| Field::Mutator(m)));
+        if last.is_some() {
+            fields.push(Field::LastSettings);
+        }
+        fields.push(Field::Start);
+        let mut list = ListState::default();
+        list.select(Some(fields.len() - 1));
+        let mut screen = Screen {
+            fields,
+            list,
+            size: String::new(),
+            difficulty: Level::Normal,
+            seed: String::new(),
+            mutators: Vec::new(),
+            last,
+            msg: String::from("Enter starts the run, Esc quits"),
+        };
+        screen.fill(&Setup::default());
+        screen
+    }
+
+    fn fill(&mut self, setup: &Setup) {
+        self.size = setup.size.to_string();
+        self.difficulty = setup.difficulty;
+        self.seed = setup.seed.map(|s| s.to_string()).unwrap_or_default();
+        self.mutators = setup.mutators.clone();
+    }
+
+    fn selected(&self) -> Field {
+        self.fields[self.list.selected().unwrap_or(0)]
+    }
+
+    fn size_error(&self) -> Option<String> {
+        match self.size.parse::<usize>() {
+            Ok(size) if (MIN_SIZE..=MAX_SIZE).contains(&size) => None,
+            _ => Some(format!("must be {} to {}", MIN_SIZE, MAX_SIZE)),
+        }
+    }
+
+    fn seed_error(&self) -> Option<String> {
+        match self.seed.is_empty() || self.seed.parse::<u64>().is_ok() {
+            true => None,
+            false => Some(String::from("too large")),
+        }
+    }
+
+    /// The setup as entered, or the first field that is wrong.
+    fn setup(&self) -> Result<Setup, Field> {
+        if self.size_error().is_some() {
+            return Err(Field::Size);
+        }
+        if self.seed_error().is_some() {
+            return Err(Field::Seed);
+        }
+        Ok(Setup {
+            size: self.size.parse().unwrap_or_default(),
+            difficulty: self.difficulty,
+            seed: self.seed.parse().ok(),
+            mutators: self.mutators.clone(),
+        })
+    }
+
+    fn step(&mut self, by: isize) {
+        let len = self.fields.len() as isize;
+        let selected = self.list.selected().unwrap_or(0) as isize;
+        self.list
+            .select(Some((selected + by).rem_euclid(len) as usize));
+    }
+
+    fn change(&mut self, by: isize) {
+        match self.selected() {
+            Field::Size => {
+                let size = self.size.parse::<isize>().unwrap_or(6) + by;
+                self.size = size.clamp(MIN_SIZE as isize, MAX_SIZE as isize).to_string();
+            }
+            Field::Difficulty => {
+                let levels = Level::value_variants();
+                let at = levels
+                    .iter()
+                    .position(|&l| l == self.difficulty)
+                    .unwrap_or(0);
+                let next = (at as isize + by).rem_euclid(levels.len() as isize);
+                self.difficulty = levels[next as usize];
+            }
+            Field::Mutator(mutator) => self.toggle(mutator),
+            Field::Seed | Field::LastSettings | Field::Start => {}
+        }
+    }
+
+    fn toggle(&mut self, mutator: Mutator) {
+        match self.mutators.iter().position(|&m| m == mutator) {
+            Some(at) => {
+                self.mutators.remove(at);
+            }
+            None => self.mutators.push(mutator),
+        }
+    }
+
+    fn text(&mut self) -> Option<&mut String> {
+        match self.selected() {
+            Field::Size => Some(&mut self.size),
+            Field::Seed => Some(&mut self.seed),
+            _ => None,
+        }
+    }
+
+    /// Enter: starts the run if everything checks out, `None` to stay.
+    fn confirm(&mut self) -> Option<Setup> {
+        match self.selected() {
+            Field::LastSettings => {
+                if let Some(last) = self.last.clone() {
+                    self.fill(&last);
+                    self.list.select(Some(self.fields.len() - 1));
+                    self.msg = String::from("Loaded the last settings, Enter starts");
+                }
+            }
+            _ => match self.setup() {
+                Ok(setup) => return Some(setup),
+                Err(field) => {
+                    if let Some(at) = self.fields.iter().position(|&f| f == field) {
+                        self.list.select(Some(at));
+                    }
+                    self.msg = String::from("Fix the marked setting first");
+                }
+            },
+        }
+        None
+    }
+
+    fn items(&self, theme: &Theme) -> Vec<ListItem<'static>> {
+        let error = Style::default().fg(theme.infested);
+        let hint = Style::default().fg(theme.inactive);
+        self.fields
+            .iter()
+            .map(|field| {
+                let line = match field {
+                    Field::Size => {
+                        let mut spans = vec![Span::raw(format!(
+                            "Board size   {}x{}",
+                            self.size, self.size
+                        ))];
+                        if let Some(msg) = self.size_error() {
+                            spans.push(Span::styled(format!("  {}", msg), error));
+                        }
+                        Line::from(spans)
+                    }
+                    Field::Difficulty => {
+                        Line::from(format!("Difficulty   < {} >", self.difficulty))
+                    }
+                    Field::Seed => {
+                        let mut spans = vec![Span::raw("Seed         ")];
+                        match self.seed.is_empty() {
+                            true => {
+                                spans.push(Span::styled("random, type digits to fix one", hint))
+                            }
+                            false => spans.push(Span::raw(self.seed.clone())),
+                        }
+                        if let Some(msg) = self.seed_error() {
+                            spans.push(Span::styled(format!("  {}", msg), error));
+                        }
+                        Line::from(spans)
+                    }
+                    Field::Mutator(mutator) => {
+                        let mark = match self.mutators.contains(mutator) {
+                            true => "[x]",
+                            false => "[ ]",
+                        };
+                        Line::from(format!("Mutator      {} {}", mark, mutator))
+                    }
+                    Field::LastSettings => Line::from("Use last settings"),
+                    Field::Start => Line::from("Start the run"),
+                };
+                ListItem::new(line)
+            })
+            .collect()
+    }
+}
+
+/// Opens the setup screen in a terminal of its own, before any setting
+/// is fixed. `None` if the player quits instead.
+pub fn open(theme: &Theme) -> io::Result<Option<Setup>> {
+    trace::take_terminal()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let res = run(&mut terminal, theme);
+    trace::restore_terminal()?;
+    let setup = res?;
+    if let Some(setup) = &setup {
+        config::save_last_setup(&config::config_path(), setup)?;
+    }
+    Ok(setup)
+}
+
+fn run<B: Backend>(terminal: &mut Terminal<B>, theme: &Theme) -> io::Result<Option<Setup>> {
+    // An unreadable config only costs the last settings, `run` reports it
+    let last = Config::load(&config::config_path())
+        .ok()
+        .and_then(|config| config.last_setup);
+    let mut screen = Screen::new(last);
+    loop {
+        draw(terminal, &mut screen, theme)?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Up => screen.step(-1),
+            KeyCode::Down | KeyCode::Tab => screen.step(1),
+            KeyCode::Left => screen.change(-1),
+            KeyCode::Right => screen.change(1),
+            KeyCode::Char(' ') => screen.change(1),
+            KeyCode::Enter => {
+                if let Some(setup) = screen.confirm() {
+                    return Ok(Some(setup));
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(text) = screen.text() {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if let Some(text) = screen.text() {
+                    // Long enough for any u64 and for a size to be wrong
+                    if text.len() < 20 {
+                        text.push(c);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw<B: Backend>(
+    terminal: &mut Terminal<B>,
+    screen: &mut Screen,
+    theme: &Theme,
+) -> io::Result<()> {
+    let items = screen.items(theme);
+    let help = vec![
+        Line::from(Span::styled(
+            "Up/Down pick, Left/Right change, digits type, Space toggles",
+            Style::default().fg(theme.label),
+        )),
+        Line::from(""),
+        Line::from(screen.msg.clone()),
+    ];
+    terminal.draw(|f| {
+        let area = centered_rect(60, 60, f.area());
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(5)].as_ref())
+            .split(area);
+        let list = List::new(items)
+            .block(theme.panel(" New Run ", true))
+            .highlight_style(theme.highlight())
+            .highlight_symbol(theme.highlight_symbol);
+        f.render_stateful_widget(list, chunks[0], &mut screen.list);
+        let paragraph = Paragraph::new(help)
+            .block(theme.panel("", false))
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, chunks[1]);
+    })?;
+    Ok(())
+}
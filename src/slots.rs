@@ -0,0 +1,584 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Span,
+    widgets::{Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    centered_rect, format_points,
+    lang::{self, tr},
+    mode::GameMode,
+    packs, persist,
+    save::{self, SAVE_VERSION},
+    theme::Theme,
+    Game, GlobalSetting,
+};
+
+/// Slot names end up in file names, so they keep at most this many
+/// letters, digits, `-` and `_`.
+pub const MAX_NAME: usize = 24;
+
+pub fn slots_dir() -> PathBuf {
+    persist::data_dir().join("saves")
+}
+
+fn slot_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("slot-{}.json", name))
+}
+
+fn index_path(dir: &Path) -> PathBuf {
+    dir.join("index.json")
+}
+
+/// `name` the way it is stored: spaces turn into `-`, anything else that
+/// isn't a letter, digit, `-` or `_` is dropped.
+pub fn sanitize(name: &str) -> String {
+    name.chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .take(MAX_NAME)
+        .collect()
+}
+
+/// What the load menu shows of a slot. The index keeps one for every slot,
+/// so listing them doesn't read every save.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotInfo {
+    pub name: String,
+    pub version: u32,
+    pub score: f32,
+    pub round: u32,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default)]
+    pub mode: GameMode,
+    #[serde(default)]
+    pub packs: Vec<String>,
+    /// Unix seconds
+    pub saved: u64,
+}
+
+impl SlotInfo {
+    fn new(name: &str, version: u32, (width, height): (usize, usize), game: &Game) -> SlotInfo {
+        SlotInfo {
+            name: name.to_string(),
+            version,
+            score: game.total_points(),
+            round: game.round,
+            width,
+            height,
+            mode: game.mode,
+            packs: game.packs.clone(),
+            saved: persist::unix_now(),
+        }
+    }
+
+    /// Why the run can't be played on in this one, `None` if it can. Like
+    /// the autosave, a run only continues on the same board size, mode and
+    /// packs.
+    pub fn conflict(&self, packs: &[String], settings: &GlobalSetting) -> Option<String> {
+        let mode = settings.mode;
+        if self.version != SAVE_VERSION {
+            Some(format!(
+                "it has save version {}, this game reads {}",
+                self.version, SAVE_VERSION
+            ))
+        } else if (self.width, self.height) != (settings.width, settings.height) {
+            Some(format!(
+                "it is on a {}x{} board, start with --dim {}",
+                self.width, self.height, self.width
+            ))
+        } else if self.mode != mode {
+            Some(format!(
+                "it is a {} run, start with --mode {}",
+                self.mode,
+                self.mode.to_string().to_lowercase()
+            ))
+        } else if self.packs != packs {
+            Some(format!(
+                "it was played with {}, this run with {}",
+                packs::describe(&self.packs),
+                packs::describe(packs)
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self) -> String {
+        format!(
+            "{:<width$} {:>8} pts  round {:>3}  {}x{}  {:<8}  {} {:02}:{:02}",
+            self.name,
+            format_points(self.score),
+            self.round,
+            self.width,
+            self.height,
+            self.mode,
+            persist::format_date(self.saved),
+            self.saved / 3600 % 24,
+            self.saved / 60 % 60,
+            width = MAX_NAME
+        )
+    }
+}
+
+type Index = BTreeMap<String, SlotInfo>;
+
+/// Reads the index, rebuilding it from the slots when it is missing. A
+/// corrupt index is backed up first.
+fn read_index(dir: &Path) -> io::Result<Index> {
+    let path = index_path(dir);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return rebuild_index(dir),
+        Err(err) => return Err(err),
+    };
+    match serde_json::from_str(&contents) {
+        Ok(index) => Ok(index),
+        Err(_) => {
+            persist::back_up_corrupt(&path)?;
+            rebuild_index(dir)
+        }
+    }
+}
+
+/// The index as the slot files have it. Slots this version can't read are
+/// left out, the file time stands in for when they were saved.
+fn rebuild_index(dir: &Path) -> io::Result<Index> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Index::new()),
+        Err(err) => return Err(err),
+    };
+    let mut index = Index::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("slot-")?.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        let Some(save) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| save::from_json(&contents).ok())
+        else {
+            continue;
+        };
+        let mut info = SlotInfo::new(name, save.version, (save.width, save.height), &save.game);
+        if let Ok(modified) = fs::metadata(&path).and_then(|meta| meta.modified()) {
+            info.saved = modified
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs());
+        }
+        index.insert(name.to_string(), info);
+    }
+    Ok(index)
+}
+
+fn write_index(dir: &Path, index: &Index) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(index)?;
+    persist::write_atomic(&index_path(dir), json.as_bytes())
+}
+
+/// Every slot, the last one saved first.
+pub fn list(dir: &Path) -> io::Result<Vec<SlotInfo>> {
+    let mut slots = read_index(dir)?.into_values().collect::<Vec<SlotInfo>>();
+    slots.sort_by_key(|slot| std::cmp::Reverse(slot.saved));
+    Ok(slots)
+}
+
+/// Writes `game` to the slot `name`, replacing what was in it.
+pub fn save_slot(dir: &Path, name: &str, game: &Game) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let json = save::to_json(game)?;
+    persist::write_atomic(&slot_path(dir, name), json.as_bytes())?;
+    let mut index = read_index(dir)?;
+    let info = SlotInfo::new(name, SAVE_VERSION, (game.width(), game.height()), game);
+    index.insert(name.to_string(), info);
+    write_index(dir, &index)
+}
+
+/// The run in the slot `name`, if it can be played on here with `packs`
+/// and `settings`. It goes on with those settings.
+pub fn load_slot(
+    dir: &Path,
+    name: &str,
+    packs: &[String],
+    settings: &Arc<GlobalSetting>,
+) -> Result<Game, String> {
+    let path = slot_path(dir, name);
+    let contents = fs::read_to_string(&path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => format!("There is no save slot `{}`", name),
+        _ => format!("{}: {}", path.display(), err),
+    })?;
+    let save = save::from_json(&contents).map_err(|err| format!("Slot {}: {}", name, err))?;
+    let info = SlotInfo::new(name, save.version, (save.width, save.height), &save.game);
+    if let Some(conflict) = info.conflict(packs, settings) {
+        return Err(format!("Can't load slot {}, {}", name, conflict));
+    }
+    let mut game = save.game;
+    game.settings = settings.clone();
+    Ok(game)
+}
+
+pub fn delete_slot(dir: &Path, name: &str) -> io::Result<()> {
+    match fs::remove_file(slot_path(dir, name)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    let mut index = read_index(dir)?;
+    index.remove(name);
+    write_index(dir, &index)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuKind {
+    /// `S`: type a name or pick a slot to overwrite
+    Save,
+    /// `L`: pick a slot to play on
+    Load,
+}
+
+/// A slot that Enter would overwrite or Delete remove, waiting for the
+/// second press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Confirm {
+    Overwrite,
+    Delete,
+}
+
+/// What a key in the menu led to.
+pub enum Outcome {
+    Open,
+    Closed,
+    Saved(String),
+    Loaded(String, Box<Game>),
+}
+
+/// The save dialog or the load menu over the board. It takes every key
+/// while it is open.
+pub struct SlotMenu {
+    kind: MenuKind,
+    dir: PathBuf,
+    slots: Vec<SlotInfo>,
+    list: ListState,
+    /// Typed into the save dialog, or the name of the slot picked there
+    name: String,
+    confirm: Option<Confirm>,
+    /// What the last key did or why it didn't, over the help line
+    msg: Option<String>,
+    /// Those of the run, a slot only loads on the same board and mode
+    settings: Arc<GlobalSetting>,
+}
+
+impl SlotMenu {
+    pub fn open(
+        kind: MenuKind,
+        dir: PathBuf,
+        settings: Arc<GlobalSetting>,
+    ) -> io::Result<SlotMenu> {
+        let slots = list(&dir)?;
+        let mut list = ListState::default();
+        if kind == MenuKind::Load && !slots.is_empty() {
+            list.select(Some(0));
+        }
+        Ok(SlotMenu {
+            kind,
+            dir,
+            slots,
+            list,
+            name: String::new(),
+            confirm: None,
+            msg: None,
+            settings,
+        })
+    }
+
+    fn selected(&self) -> Option<&SlotInfo> {
+        self.list.selected().and_then(|index| self.slots.get(index))
+    }
+
+    /// Highlights the next or previous slot. In the save dialog its name
+    /// replaces the one typed.
+    fn step(&mut self, by: isize) {
+        let len = self.slots.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let next = match self.list.selected() {
+            Some(index) => (index as isize + by).rem_euclid(len) as usize,
+            None if by > 0 => 0,
+            None => len as usize - 1,
+        };
+        self.list.select(Some(next));
+        if self.kind == MenuKind::Save {
+            self.name = self.slots[next].name.clone();
+        }
+    }
+
+    /// Keeps the slot of the typed name highlighted, if there is one.
+    fn retype(&mut self, name: String) {
+        self.name = name;
+        let index = self.slots.iter().position(|slot| slot.name == self.name);
+        self.list.select(index);
+    }
+
+    pub fn on_key(&mut self, key: KeyCode, game: &Game, packs: &[String]) -> Outcome {
+        let confirm = self.confirm.take();
+        self.msg = None;
+        match (self.kind, key) {
+            (_, KeyCode::Esc) => return Outcome::Closed,
+            (_, KeyCode::Up) => self.step(-1),
+            (_, KeyCode::Down) => self.step(1),
+            (_, KeyCode::Delete) | (MenuKind::Load, KeyCode::Char('d')) => self.delete(confirm),
+            (MenuKind::Save, KeyCode::Char(c)) => {
+                self.retype(sanitize(&format!("{}{}", self.name, c)));
+            }
+            (MenuKind::Save, KeyCode::Backspace) => {
+                let mut name = self.name.clone();
+                name.pop();
+                self.retype(name);
+            }
+            (MenuKind::Save, KeyCode::Enter) => return self.save(confirm, game),
+            (MenuKind::Load, KeyCode::Enter) => return self.load(packs),
+            _ => {}
+        }
+        Outcome::Open
+    }
+
+    fn save(&mut self, confirm: Option<Confirm>, game: &Game) -> Outcome {
+        if self.name.is_empty() {
+            self.msg = Some(String::from("Type a name for the slot first"));
+            return Outcome::Open;
+        }
+        let taken = self.slots.iter().any(|slot| slot.name == self.name);
+        if taken && confirm != Some(Confirm::Overwrite) {
+            self.confirm = Some(Confirm::Overwrite);
+            return Outcome::Open;
+        }
+        match save_slot(&self.dir, &self.name, game) {
+            Ok(()) => Outcome::Saved(self.name.clone()),
+            Err(err) => {
+                self.msg = Some(format!("Couldn't save slot {}: {}", self.name, err));
+                Outcome::Open
+            }
+        }
+    }
+
+    fn load(&mut self, packs: &[String]) -> Outcome {
+        let Some(name) = self.selected().map(|slot| slot.name.clone()) else {
+            return Outcome::Open;
+        };
+        match load_slot(&self.dir, &name, packs, &self.settings) {
+            Ok(game) => Outcome::Loaded(name, Box::new(game)),
+            Err(err) => {
+                self.msg = Some(err);
+                Outcome::Open
+            }
+        }
+    }
+
+    fn delete(&mut self, confirm: Option<Confirm>) {
+        let Some(index) = self
+            .list
+            .selected()
+            .filter(|&index| index < self.slots.len())
+        else {
+            return;
+        };
+        if confirm != Some(Confirm::Delete) {
+            self.confirm = Some(Confirm::Delete);
+            return;
+        }
+        let name = self.slots[index].name.clone();
+        match delete_slot(&self.dir, &name) {
+            Ok(()) => {
+                self.slots.remove(index);
+                let next = match self.kind {
+                    MenuKind::Load if !self.slots.is_empty() => {
+                        Some(index.min(self.slots.len() - 1))
+                    }
+                    _ => None,
+                };
+                self.list.select(next);
+                self.msg = Some(format!("Deleted slot {}", name));
+            }
+            Err(err) => self.msg = Some(format!("Couldn't delete slot {}: {}", name, err)),
+        }
+    }
+
+    /// The line under the slots: a pending confirmation, the outcome of
+    /// the last key, why the highlighted slot can't be loaded, or the keys.
+    fn footer(&self, packs: &[String]) -> (String, bool) {
+        let name = self
+            .selected()
+            .map_or(self.name.as_str(), |slot| &slot.name);
+        match self.confirm {
+            Some(Confirm::Overwrite) => {
+                return (format!("Enter again to overwrite slot {}", name), true)
+            }
+            Some(Confirm::Delete) => {
+                let key = match self.kind {
+                    MenuKind::Save => "Delete",
+                    MenuKind::Load => "d",
+                };
+                return (format!("{} again to delete slot {}", key, name), true);
+            }
+            None => {}
+        }
+        if let Some(msg) = &self.msg {
+            return (msg.clone(), true);
+        }
+        if let Some(conflict) = self
+            .selected()
+            .filter(|_| self.kind == MenuKind::Load)
+            .and_then(|slot| slot.conflict(packs, &self.settings))
+        {
+            return (format!("Can't be loaded here, {}", conflict), true);
+        }
+        let help = match self.kind {
+            MenuKind::Save => {
+                "Type a name or pick a slot with Up/Down, Enter saves, Delete deletes, Esc closes"
+            }
+            MenuKind::Load => "Up/Down picks a slot, Enter loads it, d deletes, Esc closes",
+        };
+        (help.to_string(), false)
+    }
+}
+
+pub fn draw(f: &mut Frame, menu: &mut SlotMenu, packs: &[String], theme: &Theme, area: Rect) {
+    let area = centered_rect(80, 60, area);
+    let title = match menu.kind {
+        MenuKind::Save => tr("slots.save"),
+        MenuKind::Load => tr("slots.load"),
+    };
+    let title = format!(" {} ", title);
+    let block = theme.panel(title, true);
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    let name_height = if menu.kind == MenuKind::Save { 2 } else { 0 };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(name_height),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ]
+            .as_ref(),
+        )
+        .split(inner);
+
+    if menu.kind == MenuKind::Save {
+        let name = Paragraph::new(format!("{}{}_", lang::label("slots.name"), menu.name));
+        f.render_widget(name, rows[0]);
+    }
+    let dim = Style::default().fg(theme.label);
+    let items = match menu.slots.as_slice() {
+        [] => vec![ListItem::new(Span::styled(tr("slots.none"), dim))],
+        slots => slots
+            .iter()
+            .map(|slot| match slot.conflict(packs, &menu.settings) {
+                Some(_) => ListItem::new(Span::styled(slot.row(), dim)),
+                None => ListItem::new(slot.row()),
+            })
+            .collect(),
+    };
+    let list = List::new(items)
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol);
+    f.render_stateful_widget(list, rows[1], &mut menu.list);
+
+    let (footer, warning) = menu.footer(packs);
+    let style = Style::default().fg(if warning { theme.maturing } else { theme.label });
+    let footer = Paragraph::new(Span::styled(footer, style)).wrap(Wrap { trim: true });
+    f.render_widget(footer, rows[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::game;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-slots-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn slot_names_are_safe_file_names() {
+        assert_eq!(sanitize("my run"), "my-run");
+        assert_eq!(sanitize("../../etc/passwd"), "etcpasswd");
+        assert_eq!(sanitize("Wald_2 ärger!"), "Wald_2-rger");
+        assert_eq!(sanitize(&"x".repeat(40)).len(), MAX_NAME);
+        assert_eq!(sanitize("/.."), "");
+    }
+
+    #[test]
+    fn loading_checks_the_save_version_and_the_packs() {
+        let dir = scratch("checks");
+        let mut game = game(&[]);
+        let settings = game.settings.clone();
+        save_slot(&dir, "builtin", &game).unwrap();
+        assert!(load_slot(&dir, "builtin", &[], &settings).is_ok());
+        assert_eq!(
+            load_slot(&dir, "builtin", &["meadow".into()], &settings).err().unwrap(),
+            "Can't load slot builtin, it was played with the built-in plants, this run with the meadow pack"
+        );
+
+        game.packs = vec!["meadow".into()];
+        save_slot(&dir, "meadow", &game).unwrap();
+        assert_eq!(
+            load_slot(&dir, "meadow", &[], &settings).err().unwrap(),
+            "Can't load slot meadow, it was played with the meadow pack, this run with the built-in plants"
+        );
+
+        let old = save::to_json(&game).unwrap().replacen(
+            &format!("\"version\":{}", SAVE_VERSION),
+            "\"version\":1",
+            1,
+        );
+        fs::write(slot_path(&dir, "old"), old).unwrap();
+        assert_eq!(
+            load_slot(&dir, "old", &[], &settings).err().unwrap(),
+            format!(
+                "Slot old: unsupported save version 1, expected {}",
+                SAVE_VERSION
+            )
+        );
+        assert_eq!(
+            load_slot(&dir, "gone", &[], &settings).err().unwrap(),
+            "There is no save slot `gone`"
+        );
+        // The index rebuilt from the files leaves out what it can't read
+        fs::remove_file(index_path(&dir)).unwrap();
+        let names = list(&dir).unwrap().into_iter().map(|slot| slot.name);
+        let mut names = names.collect::<Vec<String>>();
+        names.sort();
+        assert_eq!(names, ["builtin", "meadow"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
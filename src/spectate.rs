@@ -0,0 +1,383 @@
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    widgets::Paragraph,
+    Frame, Terminal,
+};
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    draw_event_log, draw_game_board, draw_too_small, lang::tr, persist, save, theme::Theme,
+    too_small, Game, GlobalSetting, Overlay, Viewport,
+};
+
+/// Minimum time between two writes of the published state.
+pub const PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longest the published file goes without a write while the game runs,
+/// an unchanged state is written again so spectators know it's alive.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A published file older than this was left behind by a game that
+/// stopped without cleaning up, like one that crashed.
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Writes the latest game state to a file for spectators, at most once per
+/// `PUBLISH_INTERVAL` and only when the state changed, or every
+/// `REFRESH_INTERVAL` when it didn't.
+pub struct Publisher {
+    path: PathBuf,
+    last_write: Option<Instant>,
+    last_published: Option<String>,
+    pending: Option<String>,
+}
+
+impl Publisher {
+    pub fn new(path: PathBuf) -> Publisher {
+        Publisher {
+            path,
+            last_write: None,
+            last_published: None,
+            pending: None,
+        }
+    }
+
+    /// Queues the current state and writes it if the throttle allows.
+    pub fn publish(&mut self, game: &Game, now: Instant) -> io::Result<()> {
+        let json = save::to_json(game)?;
+        if self.last_published.as_ref() == Some(&json) {
+            self.pending = None;
+        } else {
+            self.pending = Some(json);
+        }
+        self.flush(now)
+    }
+
+    /// Writes the queued state once the throttle interval has passed, and
+    /// the last one again once it is due for a refresh.
+    pub fn flush(&mut self, now: Instant) -> io::Result<()> {
+        let refresh = self.last_write.is_some_and(|t| now >= t + REFRESH_INTERVAL);
+        if self.pending.is_none() && refresh {
+            self.pending = self.last_published.take();
+        }
+        if self.pending.is_none() || self.deadline().is_some_and(|d| now < d) {
+            return Ok(());
+        }
+        if let Some(json) = self.pending.take() {
+            persist::write_atomic(&self.path, json.as_bytes())?;
+            self.last_write = Some(now);
+            self.last_published = Some(json);
+        }
+        Ok(())
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.last_write.map(|t| t + PUBLISH_INTERVAL)
+    }
+
+    /// Removes the published file so spectators go back to waiting.
+    pub fn finish(self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+enum Snapshot {
+    Waiting,
+    /// The file is there but hasn't been written in `STALE_AFTER`.
+    Stopped,
+    /// The file holds a game of a different board size than the first one seen.
+    Mismatch(usize, usize),
+    Game(Box<Game>),
+}
+
+struct Spectator {
+    path: PathBuf,
+    snapshot: Snapshot,
+    /// Those of the first board seen, the spectator sticks to its size
+    settings: Option<Arc<GlobalSetting>>,
+    viewport: Viewport,
+    theme: Theme,
+}
+
+impl Spectator {
+    /// Re-reads the published file. Unreadable or corrupt contents keep the
+    /// last good snapshot, a missing file means the game is gone and one
+    /// that stopped being refreshed that it stopped running.
+    fn poll(&mut self, now: SystemTime) {
+        let modified = match fs::metadata(&self.path).and_then(|meta| meta.modified()) {
+            Ok(modified) => modified,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.snapshot = Snapshot::Waiting;
+                return;
+            }
+            Err(_) => return,
+        };
+        if now
+            .duration_since(modified)
+            .is_ok_and(|age| age > STALE_AFTER)
+        {
+            self.snapshot = Snapshot::Stopped;
+            return;
+        }
+        let Ok(contents) = fs::read_to_string(&self.path) else {
+            return;
+        };
+        let save = match save::from_json(&contents) {
+            Ok(save) => save,
+            Err(_) => return,
+        };
+
+        let settings = match &self.settings {
+            Some(settings) => settings.clone(),
+            None => {
+                self.viewport = Viewport::whole(save.width, save.height);
+                let settings = Arc::new(GlobalSetting::for_board(save.width, save.height));
+                self.settings.insert(settings).clone()
+            }
+        };
+        self.snapshot = if settings.width == save.width && settings.height == save.height {
+            let mut game = save.game;
+            game.settings = settings;
+            Snapshot::Game(Box::new(game))
+        } else {
+            Snapshot::Mismatch(save.width, save.height)
+        };
+    }
+}
+
+pub fn run_spectator<B: Backend>(
+    terminal: &mut Terminal<B>,
+    path: &Path,
+    theme: Theme,
+) -> io::Result<()> {
+    let mut spectator = Spectator {
+        path: path.to_path_buf(),
+        snapshot: Snapshot::Waiting,
+        settings: None,
+        viewport: Viewport::whole(0, 0),
+        theme,
+    };
+    let mut last_poll: Option<Instant> = None;
+
+    loop {
+        if last_poll.is_none_or(|t| t.elapsed() >= POLL_INTERVAL) {
+            spectator.poll(SystemTime::now());
+            last_poll = Some(Instant::now());
+        }
+        terminal.draw(|f| spectator_ui(f, &spectator))?;
+
+        if !event::poll(POLL_INTERVAL)? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) => {
+                let view = &mut spectator.viewport;
+                let whole = Viewport::whole(view.width as usize, view.height as usize);
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+                    KeyCode::F(2) => spectator.theme = spectator.theme.next(),
+                    KeyCode::Up | KeyCode::Char('w') => view.pan(0.0, 1.0),
+                    KeyCode::Down | KeyCode::Char('s') => view.pan(0.0, -1.0),
+                    KeyCode::Right | KeyCode::Char('d') => view.pan(1.0, 0.0),
+                    KeyCode::Left | KeyCode::Char('a') => view.pan(-1.0, 0.0),
+                    KeyCode::Char('+') | KeyCode::Char('=') => view.zoom_in(),
+                    KeyCode::Char('-') => view.zoom_out(),
+                    KeyCode::Char('0') => *view = whole,
+                    _ => {}
+                }
+            }
+            Event::Resize(..) => terminal.autoresize()?,
+            _ => {}
+        }
+    }
+}
+
+fn spectator_ui(f: &mut Frame, spectator: &Spectator) {
+    if too_small(f.area()) {
+        draw_too_small(f, &spectator.theme, f.area());
+        return;
+    }
+    let game = match &spectator.snapshot {
+        Snapshot::Game(game) => game,
+        Snapshot::Waiting => {
+            draw_waiting(f, &spectator.theme, "Waiting for game…", f.area());
+            return;
+        }
+        Snapshot::Stopped => {
+            let msg = "Game not running, waiting for it to start again…";
+            draw_waiting(f, &spectator.theme, msg, f.area());
+            return;
+        }
+        Snapshot::Mismatch(w, h) => {
+            let msg = format!(
+                "Waiting for game… (found a {}x{} board, restart to follow it)",
+                w, h
+            );
+            draw_waiting(f, &spectator.theme, &msg, f.area());
+            return;
+        }
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
+        .margin(1)
+        .split(f.area());
+
+    draw_game_board(
+        f,
+        game,
+        &spectator.viewport,
+        &spectator.theme,
+        Overlay {
+            blink: true,
+            labels: false,
+            effects: &[],
+        },
+        chunks[0],
+    );
+    draw_event_log(f, game, &spectator.theme, chunks[1]);
+}
+
+fn draw_waiting(f: &mut Frame, theme: &Theme, msg: &str, area: Rect) {
+    let block = theme.panel(format!(" {} ", tr("spectate.title")), false);
+    let paragraph = Paragraph::new(msg)
+        .block(block)
+        .alignment(Alignment::Center);
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{action::Action, engine::play, tests::game, theme::ThemeName};
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rogue_forest-spectate-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("live.json")
+    }
+
+    fn published(path: &Path) -> Game {
+        save::from_json(&fs::read_to_string(path).unwrap())
+            .unwrap()
+            .game
+    }
+
+    fn spectator(path: &Path) -> Spectator {
+        Spectator {
+            path: path.to_path_buf(),
+            snapshot: Snapshot::Waiting,
+            settings: None,
+            viewport: Viewport::whole(0, 0),
+            theme: Theme::new(ThemeName::Default),
+        }
+    }
+
+    #[test]
+    fn changes_go_out_at_most_every_interval() {
+        let path = scratch("throttle");
+        let mut publisher = Publisher::new(path.clone());
+        let mut game = game(&[]);
+        let start = Instant::now();
+        publisher.publish(&game, start).unwrap();
+        assert_eq!(published(&path).log.len(), game.log.len());
+
+        play(&mut game, Action::Place);
+        play(&mut game, Action::Place);
+        publisher
+            .publish(&game, start + PUBLISH_INTERVAL / 2)
+            .unwrap();
+        assert!(published(&path).log.len() < game.log.len());
+        publisher.flush(start + PUBLISH_INTERVAL).unwrap();
+        assert_eq!(published(&path).log.len(), game.log.len());
+        assert_eq!(publisher.last_write, Some(start + PUBLISH_INTERVAL));
+
+        // Unchanged, the state is only written again for a refresh
+        let later = start + PUBLISH_INTERVAL * 2;
+        publisher.publish(&game, later).unwrap();
+        assert_eq!(publisher.last_write, Some(start + PUBLISH_INTERVAL));
+        let due = start + PUBLISH_INTERVAL + REFRESH_INTERVAL;
+        publisher.flush(due).unwrap();
+        assert_eq!(publisher.last_write, Some(due));
+
+        publisher.finish().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn the_file_is_replaced_whole() {
+        let path = scratch("atomic");
+        fs::write(&path, "x".repeat(1 << 20)).unwrap();
+        let mut publisher = Publisher::new(path.clone());
+        publisher.publish(&game(&[]), Instant::now()).unwrap();
+
+        assert_eq!(published(&path).round, 0);
+        let siblings = fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(siblings, 1, "no temporary file is left behind");
+        publisher.finish().unwrap();
+    }
+
+    #[test]
+    fn a_file_left_behind_means_the_game_stopped() {
+        let path = scratch("stale");
+        let mut spectator = spectator(&path);
+        spectator.poll(SystemTime::now());
+        assert!(matches!(spectator.snapshot, Snapshot::Waiting));
+
+        let mut publisher = Publisher::new(path.clone());
+        publisher.publish(&game(&[]), Instant::now()).unwrap();
+        let written = fs::metadata(&path).unwrap().modified().unwrap();
+        spectator.poll(written + POLL_INTERVAL);
+        assert!(matches!(spectator.snapshot, Snapshot::Game(_)));
+        spectator.poll(written + STALE_AFTER + REFRESH_INTERVAL);
+        assert!(matches!(spectator.snapshot, Snapshot::Stopped));
+
+        publisher.finish().unwrap();
+        spectator.poll(SystemTime::now());
+        assert!(matches!(spectator.snapshot, Snapshot::Waiting));
+    }
+
+    #[test]
+    fn a_partial_or_corrupt_read_keeps_the_last_good_board() {
+        let path = scratch("corrupt");
+        let mut spectator = spectator(&path);
+        let mut game = game(&[]);
+        play(&mut game, Action::Place);
+        play(&mut game, Action::Place);
+        let mut publisher = Publisher::new(path.clone());
+        publisher.publish(&game, Instant::now()).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        spectator.poll(SystemTime::now());
+        let Snapshot::Game(seen) = &spectator.snapshot else {
+            panic!("the published game isn't shown");
+        };
+        let log = seen.log.clone();
+
+        for broken in [&contents.as_bytes()[..contents.len() / 2], b"\xff\xfe{"] {
+            fs::write(&path, broken).unwrap();
+            spectator.poll(SystemTime::now());
+            let Snapshot::Game(still) = &spectator.snapshot else {
+                panic!("a bad read replaced the board");
+            };
+            assert_eq!(still.log, log);
+        }
+        publisher.finish().unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::board::Board;
+
+/// Folder the board presets are read from, one `<name>.json` per preset.
+pub const PRESET_DIR: &str = "assets/presets";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Terrain {
+    #[default]
+    Plain,
+    /// Nothing can be planted on water.
+    Water,
+    /// Plants here grow one extra size per turn, unless a drought hits.
+    Fertile,
+    /// Nothing can be planted on rocks.
+    Rock,
+}
+
+impl Terrain {
+    pub fn can_plant(&self) -> bool {
+        matches!(self, Terrain::Plain | Terrain::Fertile)
+    }
+
+    pub fn glyph(&self) -> Option<char> {
+        match self {
+            Terrain::Plain => None,
+            Terrain::Water => Some('≈'),
+            Terrain::Fertile => Some('"'),
+            Terrain::Rock => Some('^'),
+        }
+    }
+}
+
+impl Display for Terrain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Terrain::Plain => f.write_str("Plain"),
+            Terrain::Water => f.write_str("Water"),
+            Terrain::Fertile => f.write_str("Fertile soil"),
+            Terrain::Rock => f.write_str("Rocks"),
+        }
+    }
+}
+
+/// A rectangle of terrain, given as fractions of the board so presets fit
+/// any board size. A tile belongs to the region when its center is inside.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    pub terrain: Terrain,
+    pub x: [f32; 2],
+    pub y: [f32; 2],
+}
+
+impl Region {
+    fn covers(&self, x: usize, y: usize, width: usize, height: usize) -> bool {
+        let cx = (x as f32 + 0.5) / width as f32;
+        let cy = (y as f32 + 0.5) / height as f32;
+        cx >= self.x[0] && cx < self.x[1] && cy >= self.y[0] && cy < self.y[1]
+    }
+}
+
+/// A starting terrain layout. Later regions are laid over earlier ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardPreset {
+    #[serde(skip)]
+    pub name: String,
+    pub regions: Vec<Region>,
+}
+
+impl BoardPreset {
+    /// The terrain of every tile, the last region covering it wins.
+    pub fn layout(&self, width: usize, height: usize) -> Board<Terrain> {
+        Board::from_fn(width, height, |x, y| {
+            self.regions
+                .iter()
+                .rev()
+                .find(|region| region.covers(x, y, width, height))
+                .map_or(Terrain::Plain, |region| region.terrain)
+        })
+    }
+}
+
+/// Names of the presets in `dir`, sorted.
+pub fn preset_names(dir: &Path) -> Vec<String> {
+    let mut names = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+        .collect::<Vec<String>>();
+    names.sort();
+    names
+}
+
+/// Reads the preset `name` from `dir`. Unknown names list what is there.
+pub fn load_preset(dir: &Path, name: &str) -> Result<BoardPreset, String> {
+    let path: PathBuf = dir.join(format!("{}.json", name));
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => {
+            return Err(format!(
+                "unknown preset `{}`, available: {}",
+                name,
+                preset_names(dir).join(", ")
+            ))
+        }
+    };
+    let mut preset: BoardPreset =
+        serde_json::from_str(&contents).map_err(|err| format!("{}: {}", path.display(), err))?;
+    preset.name = name.to_string();
+    Ok(preset)
+}
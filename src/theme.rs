@@ -0,0 +1,350 @@
+use clap::ValueEnum;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, BorderType, Borders},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::Rarity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum ThemeName {
+    Default,
+    HighContrast,
+    Colorblind,
+    /// Bought with profile seeds
+    Autumn,
+}
+
+impl ThemeName {
+    pub fn next(self) -> ThemeName {
+        match self {
+            ThemeName::Default => ThemeName::HighContrast,
+            ThemeName::HighContrast => ThemeName::Colorblind,
+            ThemeName::Colorblind => ThemeName::Autumn,
+            ThemeName::Autumn => ThemeName::Default,
+        }
+    }
+
+    /// Themes that are only for looks and have to be bought first. The
+    /// readability themes are always there.
+    pub fn cosmetic(self) -> bool {
+        matches!(self, ThemeName::Autumn)
+    }
+}
+
+/// Every color the UI uses. Active panels also get a heavier border so the
+/// focus never depends on color alone.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub name: ThemeName,
+    pub bg: Color,
+    pub active: Color,
+    pub inactive: Color,
+    /// Plants placed this round
+    pub new_plant: Color,
+    /// Plants past half their lifespan
+    pub aging: Color,
+    /// Plants close to their max age
+    pub maturing: Color,
+    /// Ripe plants waiting to be picked
+    pub overripe: Color,
+    pub infested: Color,
+    pub rain: Color,
+    pub drought: Color,
+    /// Stat names in the info panels
+    pub label: Color,
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+    pub highlight_symbol: &'static str,
+    pub active_border: BorderType,
+    /// Plant name colors from Common to Legendary
+    pub rarity: [Color; 4],
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Theme {
+        match name {
+            ThemeName::Default => Theme {
+                name,
+                bg: Color::Reset,
+                active: Color::Green,
+                inactive: Color::LightGreen,
+                new_plant: Color::Yellow,
+                aging: Color::LightYellow,
+                maturing: Color::Magenta,
+                overripe: Color::Rgb(255, 140, 0),
+                infested: Color::Red,
+                rain: Color::Blue,
+                drought: Color::LightRed,
+                label: Color::Cyan,
+                highlight_fg: Color::White,
+                highlight_bg: Color::LightGreen,
+                highlight_symbol: ">>  ",
+                active_border: BorderType::Double,
+                rarity: [
+                    Color::Gray,
+                    Color::Green,
+                    Color::Blue,
+                    Color::Rgb(255, 165, 0),
+                ],
+            },
+            ThemeName::HighContrast => Theme {
+                name,
+                bg: Color::Black,
+                active: Color::White,
+                inactive: Color::Gray,
+                new_plant: Color::LightYellow,
+                aging: Color::Yellow,
+                maturing: Color::LightMagenta,
+                overripe: Color::Rgb(255, 165, 0),
+                infested: Color::LightRed,
+                rain: Color::LightBlue,
+                drought: Color::LightYellow,
+                label: Color::LightCyan,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::White,
+                highlight_symbol: ">>  ",
+                active_border: BorderType::Thick,
+                rarity: [
+                    Color::White,
+                    Color::LightGreen,
+                    Color::LightBlue,
+                    Color::LightYellow,
+                ],
+            },
+            // Okabe-Ito colors, which stay apart for red-green color blindness
+            ThemeName::Colorblind => Theme {
+                name,
+                bg: Color::Reset,
+                active: Color::Rgb(86, 180, 233),
+                inactive: Color::Gray,
+                new_plant: Color::Rgb(240, 228, 66),
+                aging: Color::Rgb(0, 158, 115),
+                maturing: Color::Rgb(204, 121, 167),
+                overripe: Color::Rgb(213, 94, 0),
+                infested: Color::Rgb(230, 159, 0),
+                rain: Color::Rgb(0, 114, 178),
+                drought: Color::Rgb(213, 94, 0),
+                label: Color::White,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::Rgb(86, 180, 233),
+                highlight_symbol: ">>  ",
+                active_border: BorderType::Double,
+                rarity: [
+                    Color::Gray,
+                    Color::Rgb(0, 158, 115),
+                    Color::Rgb(0, 114, 178),
+                    Color::Rgb(230, 159, 0),
+                ],
+            },
+            ThemeName::Autumn => Theme {
+                name,
+                bg: Color::Reset,
+                active: Color::Rgb(214, 120, 40),
+                inactive: Color::Rgb(170, 130, 80),
+                new_plant: Color::Rgb(240, 200, 80),
+                aging: Color::Rgb(210, 170, 60),
+                maturing: Color::Rgb(190, 60, 40),
+                overripe: Color::Rgb(255, 150, 30),
+                infested: Color::Rgb(150, 40, 90),
+                rain: Color::Rgb(90, 130, 170),
+                drought: Color::Rgb(230, 170, 110),
+                label: Color::Rgb(220, 180, 120),
+                highlight_fg: Color::Black,
+                highlight_bg: Color::Rgb(214, 120, 40),
+                highlight_symbol: ">>  ",
+                active_border: BorderType::Double,
+                rarity: [
+                    Color::Gray,
+                    Color::Rgb(180, 160, 60),
+                    Color::Rgb(200, 100, 50),
+                    Color::Rgb(220, 50, 50),
+                ],
+            },
+        }
+    }
+
+    /// The color a plant of `age` counts down its lifespan in: healthy
+    /// while young, `aging` past half its lifespan and `maturing` in the
+    /// last `maturing_at` rounds before the harvest. A plant's `own` color
+    /// goes over healthy and aging, maturing goes over everything.
+    pub fn lifespan_color(
+        &self,
+        age: u32,
+        max_age: u32,
+        maturing_at: u32,
+        own: Option<Color>,
+    ) -> Color {
+        if max_age.saturating_sub(age) <= maturing_at {
+            self.maturing
+        } else if let Some(own) = own {
+            own
+        } else if age * 2 >= max_age {
+            self.aging
+        } else {
+            self.inactive
+        }
+    }
+
+    /// The next theme that doesn't have to be bought.
+    pub fn next(&self) -> Theme {
+        let mut name = self.name.next();
+        while name.cosmetic() {
+            name = name.next();
+        }
+        Theme::new(name)
+    }
+
+    pub fn title<'a>(&self, title: impl Into<std::borrow::Cow<'a, str>>) -> Span<'a> {
+        Span::styled(
+            title,
+            Style::default()
+                .fg(self.active)
+                .add_modifier(Modifier::BOLD),
+        )
+    }
+
+    /// A panel title in the color of a plant's rarity.
+    pub fn rarity_title<'a>(
+        &self,
+        title: impl Into<std::borrow::Cow<'a, str>>,
+        rarity: Rarity,
+    ) -> Span<'a> {
+        Span::styled(title, self.rarity(rarity).add_modifier(Modifier::BOLD))
+    }
+
+    pub fn rarity(&self, rarity: Rarity) -> Style {
+        Style::default().fg(self.rarity[rarity as usize])
+    }
+
+    /// A card name in lists: the plant's own color, or its rarity's.
+    pub fn card(&self, rarity: Rarity, own: Option<Color>) -> Style {
+        match own {
+            Some(own) => Style::default().fg(own),
+            None => self.rarity(rarity),
+        }
+    }
+
+    /// A bordered panel, highlighted when it has the focus.
+    pub fn panel<'a>(
+        &self,
+        title: impl Into<std::borrow::Cow<'a, str>>,
+        active: bool,
+    ) -> Block<'a> {
+        self.titled_panel(self.title(title), active)
+    }
+
+    /// Like `panel`, with an already styled title.
+    pub fn titled_panel<'a>(&self, title: Span<'a>, active: bool) -> Block<'a> {
+        let (color, border) = if active {
+            (self.active, self.active_border)
+        } else {
+            (self.inactive, BorderType::Plain)
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(border)
+            .border_style(Style::default().fg(color))
+            .title(title)
+    }
+
+    pub fn highlight(&self) -> Style {
+        Style::default()
+            .bg(self.highlight_bg)
+            .fg(self.highlight_fg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// The chosen plant on the tile it would go on, before it is placed.
+    pub fn ghost(&self) -> Style {
+        Style::default()
+            .fg(self.inactive)
+            .add_modifier(Modifier::DIM)
+    }
+}
+
+/// A plant's color as written in its file: `#RRGGBB`, or one of a few
+/// names. `None` if it is neither.
+pub fn parse_color(text: &str) -> Option<Color> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |at: usize| u8::from_str_radix(&hex[at..at + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    let (r, g, b) = match text.to_ascii_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "red" => (220, 50, 50),
+        "orange" => (230, 140, 30),
+        "yellow" => (230, 210, 50),
+        "lime" => (150, 220, 50),
+        "green" => (40, 160, 60),
+        "teal" => (0, 140, 140),
+        "cyan" => (60, 200, 220),
+        "blue" => (50, 100, 220),
+        "purple" => (140, 70, 200),
+        "magenta" => (210, 60, 180),
+        "pink" => (240, 140, 180),
+        "brown" => (140, 90, 40),
+        _ => return None,
+    };
+    Some(Color::Rgb(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_lifespan_ramps_from_healthy_to_maturing() {
+        let theme = Theme::new(ThemeName::Default);
+        let ramp = (0..=8)
+            .map(|age| theme.lifespan_color(age, 8, 2, None))
+            .collect::<Vec<Color>>();
+        let (healthy, aging, maturing) = (theme.inactive, theme.aging, theme.maturing);
+        assert_eq!(
+            ramp,
+            [healthy, healthy, healthy, healthy, aging, aging, maturing, maturing, maturing]
+        );
+    }
+
+    #[test]
+    fn the_maturing_threshold_is_configurable() {
+        let theme = Theme::new(ThemeName::Default);
+        assert_eq!(theme.lifespan_color(2, 8, 6, None), theme.maturing);
+        assert_eq!(theme.lifespan_color(7, 8, 0, None), theme.aging);
+        assert_eq!(theme.lifespan_color(8, 8, 0, None), theme.maturing);
+    }
+
+    #[test]
+    fn own_colors_give_way_only_to_maturing() {
+        let theme = Theme::new(ThemeName::Default);
+        let own = Some(Color::Cyan);
+        assert_eq!(theme.lifespan_color(0, 8, 2, own), Color::Cyan);
+        assert_eq!(theme.lifespan_color(5, 8, 2, own), Color::Cyan);
+        assert_eq!(theme.lifespan_color(6, 8, 2, own), theme.maturing);
+    }
+
+    #[test]
+    fn plant_colors_are_hex_or_a_name() {
+        assert_eq!(parse_color("#1a2B3c"), Some(Color::Rgb(0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse_color("Brown"), Some(Color::Rgb(140, 90, 40)));
+        assert_eq!(parse_color("grey"), parse_color("gray"));
+        for invalid in ["", "#12345", "#1234567", "#12345g", "chartreuse", "1a2b3c"] {
+            assert_eq!(parse_color(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn own_colors_go_over_the_rarity_in_the_hand() {
+        let theme = Theme::new(ThemeName::Default);
+        let own = Color::Rgb(1, 2, 3);
+        assert_eq!(theme.card(Rarity::Rare, Some(own)).fg, Some(own));
+        assert_eq!(theme.card(Rarity::Rare, None), theme.rarity(Rarity::Rare));
+    }
+}
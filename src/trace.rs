@@ -0,0 +1,187 @@
+//! `--log-file <path>`: what a run did, for debugging a TUI that can't
+//! print. Lines look like `[   12.345s]  INFO Grass at B2 matured for 3
+//! points`, filtered by `--log-level`. The game events are the ones the
+//! event log panel shows, so the two never disagree.
+//!
+//! The game traces through `tracing` and this module only sets up where it
+//! goes. Dependencies that log through the `log` crate, like crossterm's
+//! polling, aren't bridged over and never reach the file.
+//!
+//! The panic hook is installed for every run, with or without a log file.
+//! It hands the terminal back before anything is printed.
+
+use clap::ValueEnum;
+use crossterm::{
+    cursor::Show,
+    event::DisableMouseCapture,
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use once_cell::sync::OnceCell;
+use tracing::{debug, error, info, level_filters::LevelFilter, Subscriber};
+use tracing_subscriber::fmt::{format::Writer, time::FormatTime, MakeWriter};
+
+use std::{
+    backtrace::Backtrace,
+    fs::File,
+    io, panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Instant,
+};
+
+use crate::{engine::GameEvent, format_points};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    /// Game events and errors
+    #[default]
+    Info,
+    /// Also state changes and notices
+    Debug,
+    /// Also every key
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> LevelFilter {
+        match level {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Seconds since the log was opened, `[   12.345s]`.
+struct SinceStart(Instant);
+
+impl FormatTime for SinceStart {
+    fn format_time(&self, w: &mut Writer<'_>) -> std::fmt::Result {
+        write!(w, "[{:>9.3}s]", self.0.elapsed().as_secs_f32())
+    }
+}
+
+/// Lines of `level` and above, without colors, written to `out`.
+fn subscriber<W>(out: W, level: LogLevel) -> impl Subscriber + Send + Sync
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .with_writer(out)
+        .with_max_level(LevelFilter::from(level))
+        .with_timer(SinceStart(Instant::now()))
+        .with_target(false)
+        .with_ansi(false)
+        .finish()
+}
+
+static LOG_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+/// Whether the terminal is raw and on the alternate screen.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Starts writing to `path` at `level`, replacing what it held.
+pub fn init(path: &Path, level: LogLevel) -> Result<(), String> {
+    let file = File::create(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    tracing::subscriber::set_global_default(subscriber(Mutex::new(file), level))
+        .map_err(|err| err.to_string())?;
+    let _ = LOG_PATH.set(path.to_path_buf());
+    Ok(())
+}
+
+/// On a panic, restores the terminal, writes the panic and its backtrace
+/// to the log and says where the log is, then panics as usual.
+pub fn install_panic_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        report_panic(info);
+        default(info);
+        if let Some(path) = LOG_PATH.get() {
+            eprintln!("The backtrace is in the log at {}", path.display());
+        }
+    }));
+}
+
+/// Hands the terminal back and logs the panic with where it came from.
+/// Every line is written out as it is traced, nothing is left to flush.
+fn report_panic(info: &panic::PanicHookInfo) {
+    let _ = restore_terminal();
+    error!("{}\n{}", info, Backtrace::force_capture());
+}
+
+/// Raw mode on the alternate screen, for a TUI.
+pub fn take_terminal() -> io::Result<()> {
+    enable_raw_mode()?;
+    TAKEN.store(true, Ordering::SeqCst);
+    execute!(io::stdout(), EnterAlternateScreen)
+}
+
+/// Leaves raw mode and the alternate screen and shows the cursor again.
+/// Does nothing if the terminal wasn't taken, or was handed back already.
+pub fn restore_terminal() -> io::Result<()> {
+    if !TAKEN.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )
+}
+
+/// Logs what came of an action.
+pub fn events(events: &[GameEvent]) {
+    for event in events {
+        match event {
+            GameEvent::Logged(line) => info!("{}", line),
+            GameEvent::Notice(notice) => debug!("Notice: {}", notice),
+            GameEvent::Harvested { x, y, points } => {
+                debug!("Harvested ({}, {}) for {}", x, y, format_points(*points))
+            }
+            GameEvent::RoundEnded { round, score, .. } => {
+                info!("Round {} ended at {} points", round, format_points(*score))
+            }
+            GameEvent::GameOver { score } => info!("Game over at {} points", format_points(*score)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_panic_hands_the_terminal_back_and_logs_its_backtrace() {
+        let path =
+            std::env::temp_dir().join(format!("rogue_forest-panic-{}.log", std::process::id()));
+        let file = File::create(&path).unwrap();
+        TAKEN.store(true, Ordering::SeqCst);
+
+        install_panic_hook();
+        let result = tracing::subscriber::with_default(
+            subscriber(Mutex::new(file), LogLevel::Error),
+            || panic::catch_unwind(|| panic!("The forest burned down")),
+        );
+        drop(panic::take_hook());
+
+        assert!(result.is_err());
+        assert!(!TAKEN.load(Ordering::SeqCst));
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert!(log.contains("ERROR"), "{log}");
+        assert!(log.contains("The forest burned down"), "{log}");
+        assert!(log.contains("a_panic_hands_the_terminal_back"), "{log}");
+        // Handing it back again is harmless
+        assert!(restore_terminal().is_ok());
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{action::Action, engine::GameEvent, Game, Plant, PlantSet, State, Tile};
+
+/// Width and height of the tutorial board.
+pub const DIM: usize = 4;
+
+/// The tutorial deals and rolls the same on every run.
+pub const SEED: u64 = 7;
+
+/// Two plants and no items, so the tutorial works without the assets and
+/// nothing in the log distracts from the hints. A Sprout is
+/// harvested the round after the one it was planted in.
+const PLANTS: &str = r#"[
+    {
+        "max_age": 1,
+        "age": 0,
+        "size_per_turn": 2,
+        "size": 0,
+        "points_per_size": 1.0,
+        "class": "s",
+        "name": "Sprout",
+        "short_display": "v",
+        "description": "Ready the round after it is planted. Drops another Sprout.",
+        "drops": [{ "chance": 1.0, "plants": ["Sprout"] }]
+    },
+    {
+        "max_age": 3,
+        "age": 0,
+        "size_per_turn": 1,
+        "size": 0,
+        "points_per_size": 2.0,
+        "class": "S",
+        "name": "Bush",
+        "short_display": "B",
+        "description": "Slower, but each size is worth twice as much.",
+        "drops": [{ "chance": 1.0, "plants": ["Sprout", "Bush"] }]
+    }
+]"#;
+
+const HAND: [&str; 2] = ["Sprout", "Bush"];
+
+/// One hint of the tutorial and what moves it on.
+pub struct Step {
+    pub hint: &'static str,
+    /// Whether `action`, with what it led to, completes the step
+    done_by: fn(Action, &[GameEvent], &Game) -> bool,
+}
+
+/// More steps only need an entry here.
+pub const STEPS: [Step; 5] = [
+    Step {
+        hint: "Use ↑/↓ to select a plant in the Plants list, then press Space to pick it up",
+        done_by: picked_up,
+    },
+    Step {
+        hint: "Move the cursor with the arrow keys and press Space to plant it on a free tile",
+        done_by: planted,
+    },
+    Step {
+        hint: "Changed your mind? Pick up the next card, move onto your plant and press q \
+               to take it back into the hand",
+        done_by: took_back,
+    },
+    Step {
+        hint: "Plant a card again, then press Tab until Next Round is highlighted and \
+               press Space to end the round",
+        done_by: round_ended,
+    },
+    Step {
+        hint: "Plants are harvested for points once they reach their max age. Keep planting \
+               and ending rounds until one is",
+        done_by: harvested,
+    },
+];
+
+fn picked_up(action: Action, _: &[GameEvent], game: &Game) -> bool {
+    action == Action::Place && game.state == State::Placing
+}
+
+fn planted(_: Action, _: &[GameEvent], game: &Game) -> bool {
+    game.tile.iter().any(|tile| matches!(tile, Tile::New(_)))
+}
+
+fn took_back(action: Action, _: &[GameEvent], game: &Game) -> bool {
+    action == Action::Delete && !planted(action, &[], game)
+}
+
+fn round_ended(_: Action, events: &[GameEvent], _: &Game) -> bool {
+    events
+        .iter()
+        .any(|event| matches!(event, GameEvent::RoundEnded { .. }))
+}
+
+fn harvested(_: Action, events: &[GameEvent], _: &Game) -> bool {
+    events
+        .iter()
+        .any(|event| matches!(event, GameEvent::Harvested { .. }))
+}
+
+/// The plants of the tutorial run.
+pub fn plant_set() -> PlantSet {
+    let plants = serde_json::from_str::<Vec<Plant>>(PLANTS)
+        .unwrap_or_else(|err| panic!("Invalid tutorial plants: {}", err));
+    let hand = HAND.map(String::from);
+    PlantSet::new(plants, Vec::new(), Vec::new(), &hand).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// How far the player got.
+#[derive(Debug, Default)]
+pub struct Tutorial {
+    step: usize,
+}
+
+impl Tutorial {
+    /// The step shown now, `None` once all are done.
+    pub fn current(&self) -> Option<&'static Step> {
+        STEPS.get(self.step)
+    }
+
+    /// 1-based, for "Step 2 of 5".
+    pub fn number(&self) -> usize {
+        self.step + 1
+    }
+
+    /// Moves on if the action finished the current step. True once the
+    /// last one is done.
+    pub fn advance(&mut self, action: Action, events: &[GameEvent], game: &Game) -> bool {
+        if let Some(step) = self.current() {
+            if (step.done_by)(action, events, game) {
+                self.step += 1;
+            }
+        }
+        self.current().is_none()
+    }
+}
@@ -0,0 +1,288 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::Path,
+};
+
+use crate::{
+    curve::GrowthCurve, format_points, items::Item, theme::parse_color, HybridRule, Plant,
+};
+
+/// What is wrong with a plant set. Errors keep it from loading, warnings
+/// point at things that load fine but can't work as intended.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Checks a plant set and its hybrids by name, before anything is
+/// resolved, so every problem is reported at once. `hand` are the starting
+/// cards.
+pub fn check(plants: &[Plant], hybrids: &[HybridRule], items: &[Item], hand: &[String]) -> Report {
+    let mut report = Report::default();
+    let mut names = HashSet::new();
+    for plant in plants {
+        if !names.insert(plant.name.as_ref()) {
+            report
+                .errors
+                .push(format!("Plant <{}> is defined twice", plant.name));
+        }
+    }
+    for item in items {
+        if names.contains(item.name.as_str()) {
+            report
+                .errors
+                .push(format!("Item <{}> has the name of a plant", item.name));
+        }
+    }
+    let known = |name: &str| names.contains(name) || items.iter().any(|item| item.name == name);
+
+    for plant in plants {
+        for name in plant.drops.iter().flat_map(|d| d.plants.iter()) {
+            if !known(name) {
+                report.errors.push(format!(
+                    "Plant <{}> drops unknown Plant or item <{}>",
+                    plant.name, name
+                ));
+            }
+        }
+        for name in plant
+            .drops
+            .iter()
+            .filter_map(|d| d.requires_adjacent.as_ref())
+        {
+            if !names.contains(name.as_str()) {
+                report.errors.push(format!(
+                    "Plant <{}> has a drop that needs unknown neighbor Plant <{}>",
+                    plant.name, name
+                ));
+            }
+        }
+        if let Some(color) = plant.color.as_deref().filter(|c| parse_color(c).is_none()) {
+            report.errors.push(format!(
+                "Plant <{}> has an invalid color `{}`, use a name like `green` or `#RRGGBB`",
+                plant.name, color
+            ));
+        }
+        if plant.max_age == 0 {
+            report.errors.push(format!(
+                "Plant <{}> has a max_age of 0 and would never be on the board",
+                plant.name
+            ));
+        }
+        if plant.size_per_turn == 0 || plant.points_per_size <= 0.0 {
+            report.warnings.push(format!(
+                "Plant <{}> never scores, it needs size_per_turn and points_per_size above 0",
+                plant.name
+            ));
+        }
+        if let GrowthCurve::Burst { rounds } = plant.growth_curve {
+            if rounds == 0 || u32::from(rounds) > plant.max_age {
+                report.warnings.push(format!(
+                    "Plant <{}> bursts over {} rounds, it is cut to between 1 and its max_age of {}",
+                    plant.name, rounds, plant.max_age
+                ));
+            }
+        }
+        if plant.drops.iter().map(|d| d.chance.max(0.0)).sum::<f32>() <= 0.0 {
+            report.warnings.push(format!(
+                "Plant <{}> never drops anything, its chances add up to 0",
+                plant.name
+            ));
+        }
+        let texts = [
+            ("description", &plant.description),
+            ("flavor", &plant.flavor),
+        ];
+        for (field, _) in texts
+            .iter()
+            .filter(|(_, text)| text.as_ref().is_some_and(|t| t.trim().is_empty()))
+        {
+            report.warnings.push(format!(
+                "Plant <{}> has a blank {}, leave it out instead",
+                plant.name, field
+            ));
+        }
+        if plant.drops.iter().any(|d| d.plants.is_empty()) {
+            report.warnings.push(format!(
+                "Plant <{}> has a drop without any plants",
+                plant.name
+            ));
+        }
+    }
+    // Without a family a plant never counts towards the collection bonus
+    if plants.iter().any(|p| p.family.is_some()) {
+        for plant in plants.iter().filter(|p| p.family.is_none()) {
+            report.warnings.push(format!(
+                "Plant <{}> has no family, unlike other plants of the set",
+                plant.name
+            ));
+        }
+    }
+    for hybrid in hybrids {
+        let [a, b] = &hybrid.parents;
+        for name in [a, b, &hybrid.child] {
+            if !names.contains(name.as_str()) {
+                report.errors.push(format!(
+                    "Hybrid of <{}> and <{}> names unknown Plant <{}>",
+                    a, b, name
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&hybrid.chance) {
+            report.errors.push(format!(
+                "Hybrid of <{}> and <{}> has a chance of {}, it needs to be between 0 and 1",
+                a, b, hybrid.chance
+            ));
+        }
+    }
+    for name in hand.iter().filter(|name| !known(name)) {
+        report
+            .errors
+            .push(format!("Starting card <{}> is not a Plant or item", name));
+    }
+
+    // Locked plants still need a source once their threshold is passed
+    let open = reachable(
+        plants,
+        hybrids,
+        plants.iter().filter(|p| p.unlock_at_points <= 0.0),
+    );
+    for plant in plants.iter().filter(|p| !open.contains(p.name.as_ref())) {
+        report.errors.push(format!(
+            "Plant <{}> unlocks at {} points but only drops from plants locked behind it",
+            plant.name,
+            format_points(plant.unlock_at_points)
+        ));
+    }
+    let dealt = plants
+        .iter()
+        .filter(|p| hand.iter().any(|name| *name == p.name));
+    let in_play = reachable(plants, hybrids, dealt);
+    for plant in plants.iter().filter(|p| !in_play.contains(p.name.as_ref())) {
+        report.warnings.push(format!(
+            "Plant <{}> can never come into play from the starting hand",
+            plant.name
+        ));
+    }
+    for item in items.iter().filter(|item| !in_play.contains(&item.name)) {
+        report.warnings.push(format!(
+            "Item <{}> can never come into play from the starting hand",
+            item.name
+        ));
+    }
+    report
+}
+
+/// Names of every card the `start` plants can lead to through drops that
+/// can happen and through hybrids of plants that both come up.
+fn reachable<'a>(
+    plants: &'a [Plant],
+    hybrids: &[HybridRule],
+    start: impl Iterator<Item = &'a Plant>,
+) -> BTreeSet<String> {
+    let mut found = start
+        .map(|p| p.name.to_string())
+        .collect::<BTreeSet<String>>();
+    loop {
+        let dropped = plants
+            .iter()
+            .filter(|p| found.contains(p.name.as_ref()))
+            .flat_map(|p| p.drops.iter().filter(|d| d.chance > 0.0))
+            .flat_map(|d| d.plants.iter().cloned());
+        let bred = hybrids
+            .iter()
+            .filter(|h| h.chance > 0.0 && h.parents.iter().all(|p| found.contains(p)))
+            .map(|h| h.child.clone());
+        let new = dropped
+            .chain(bred)
+            .filter(|name| !found.contains(name))
+            .collect::<Vec<String>>();
+        if new.is_empty() {
+            return found;
+        }
+        found.extend(new);
+    }
+}
+
+/// Prints the report for `validate`.
+pub fn print_report(path: &Path, report: &Report) {
+    for error in &report.errors {
+        println!("error: {}", error);
+    }
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+    println!(
+        "{}: {}, {}",
+        path.display(),
+        count(report.errors.len(), "error"),
+        count(report.warnings.len(), "warning")
+    );
+}
+
+fn count(n: usize, what: &str) -> String {
+    match n {
+        1 => format!("1 {}", what),
+        _ => format!("{} {}s", n, what),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{builtin_hybrids, builtin_plants};
+
+    fn rule(parents: [&str; 2], child: &str, chance: f32) -> HybridRule {
+        HybridRule {
+            parents: parents.map(String::from),
+            child: child.into(),
+            chance,
+        }
+    }
+
+    fn hybrid_errors(report: &Report) -> Vec<&str> {
+        report
+            .errors
+            .iter()
+            .map(String::as_str)
+            .filter(|error| error.starts_with("Hybrid"))
+            .collect()
+    }
+
+    #[test]
+    fn hybrids_may_only_name_plants_of_the_set() {
+        let hybrids = [
+            rule(["Grass", "Oak"], "Fern", 0.3),
+            rule(["Grass", "Shrub"], "Fertilizer", 0.3),
+        ];
+        let report = check(&builtin_plants(), &hybrids, &crate::items::all(), &[]);
+        assert_eq!(
+            hybrid_errors(&report),
+            [
+                "Hybrid of <Grass> and <Oak> names unknown Plant <Oak>",
+                "Hybrid of <Grass> and <Shrub> names unknown Plant <Fertilizer>",
+            ]
+        );
+    }
+
+    #[test]
+    fn hybrid_chances_stay_between_0_and_1() {
+        for (chance, refused) in [(-0.1, true), (0.0, false), (1.0, false), (1.5, true)] {
+            let hybrids = [rule(["Grass", "Shrub"], "Fern", chance)];
+            let report = check(&builtin_plants(), &hybrids, &[], &[]);
+            assert_eq!(!hybrid_errors(&report).is_empty(), refused, "{}", chance);
+        }
+    }
+
+    #[test]
+    fn a_hybrid_brings_its_child_into_play() {
+        let plants = builtin_plants();
+        let hand = ["Grass".to_string(), "Shrub".to_string()];
+        let fern = "Plant <Fern> can never come into play from the starting hand";
+        let without = check(&plants, &[], &[], &hand);
+        assert!(without.warnings.iter().any(|w| w == fern));
+        let with = check(&plants, &builtin_hybrids(), &[], &hand);
+        assert!(!with.warnings.iter().any(|w| w == fern));
+    }
+}
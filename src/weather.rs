@@ -0,0 +1,256 @@
+use once_cell::sync::OnceCell;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Relative chances of the round weather, see `assets/weather.json`.
+const TABLE: &str = include_str!("../assets/weather.json");
+
+/// Side length of the square footprint a cloud covers.
+pub const CLOUD_SIZE: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CloudKind {
+    /// Tiles below grow one extra size and are safe from droughts.
+    Rain,
+    /// Tiles below don't grow.
+    Drought,
+}
+
+impl Display for CloudKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CloudKind::Rain => f.write_str("Rain cloud"),
+            CloudKind::Drought => f.write_str("Drought"),
+        }
+    }
+}
+
+/// The board edge a cloud entered from. Clouds drift towards the opposite edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Edge {
+    West,
+    East,
+}
+
+impl Display for Edge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edge::West => f.write_str("west"),
+            Edge::East => f.write_str("east"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Cloud {
+    pub kind: CloudKind,
+    pub entry: Edge,
+    /// Leftmost column of the footprint, may be off the board while leaving.
+    pub x: i64,
+    /// Bottom row of the footprint.
+    pub y: usize,
+}
+
+impl Cloud {
+    /// Rolls a new cloud on a random west or east edge position of a board
+    /// `width` by `height` tiles.
+    pub fn spawn<R: Rng>(rng: &mut R, width: usize, height: usize) -> Cloud {
+        let kind = if rng.gen::<bool>() {
+            CloudKind::Rain
+        } else {
+            CloudKind::Drought
+        };
+        let entry = if rng.gen::<bool>() {
+            Edge::West
+        } else {
+            Edge::East
+        };
+        let x = match entry {
+            Edge::West => 0,
+            Edge::East => width as i64 - CLOUD_SIZE as i64,
+        };
+        let y = rng.gen_range(0, height.saturating_sub(CLOUD_SIZE) + 1);
+        Cloud { kind, entry, x, y }
+    }
+
+    /// The cloud one round from now.
+    pub fn advanced(&self) -> Cloud {
+        let dx = match self.entry {
+            Edge::West => 1,
+            Edge::East => -1,
+        };
+        Cloud {
+            x: self.x + dx,
+            ..*self
+        }
+    }
+
+    pub fn covers(&self, x: usize, y: usize) -> bool {
+        let x = x as i64;
+        x >= self.x && x < self.x + CLOUD_SIZE as i64 && y >= self.y && y < self.y + CLOUD_SIZE
+    }
+
+    /// Whether any part of the footprint is still over a board `width`
+    /// tiles across.
+    pub fn on_board(&self, width: usize) -> bool {
+        self.x + (CLOUD_SIZE as i64) > 0 && self.x < width as i64
+    }
+
+    pub fn glyph(&self) -> char {
+        match self.kind {
+            CloudKind::Rain => '~',
+            CloudKind::Drought => '*',
+        }
+    }
+}
+
+/// The weather effect on a single tile, rain wins over drought.
+pub fn weather_at(clouds: &[Cloud], x: usize, y: usize) -> Option<CloudKind> {
+    let mut weather = None;
+    for cloud in clouds.iter().filter(|c| c.covers(x, y)) {
+        match cloud.kind {
+            CloudKind::Rain => return Some(CloudKind::Rain),
+            CloudKind::Drought => weather = Some(CloudKind::Drought),
+        }
+    }
+    weather
+}
+
+/// The weather of a whole round, on top of any clouds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Sunny,
+    /// Every plant grows one extra size.
+    Rain,
+    /// No plant grows, they still age.
+    Drought,
+    /// Uproots one plant placed this round.
+    Storm,
+}
+
+impl Display for Weather {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Weather::Sunny => f.pad("Sunny"),
+            Weather::Rain => f.pad("Rain"),
+            Weather::Drought => f.pad("Drought"),
+            Weather::Storm => f.pad("Storm"),
+        }
+    }
+}
+
+pub fn table() -> &'static BTreeMap<Weather, f32> {
+    static PARSED: OnceCell<BTreeMap<Weather, f32>> = OnceCell::new();
+    PARSED.get_or_init(|| serde_json::from_str(TABLE).expect("assets/weather.json is invalid"))
+}
+
+/// Rolls the weather of a round, weighted by the table. Sunny if the
+/// weights don't add up to anything.
+pub fn roll<R: Rng>(rng: &mut R) -> Weather {
+    let table = table();
+    let total = table.values().filter(|&&w| w > 0.0).sum::<f32>();
+    if total <= 0.0 {
+        return Weather::Sunny;
+    }
+    let mut rnd = rng.gen::<f32>() * total;
+    let mut rolled = Weather::Sunny;
+    for (&weather, &weight) in table.iter().filter(|(_, &w)| w > 0.0) {
+        rolled = weather;
+        if rnd < weight {
+            break;
+        }
+        rnd -= weight;
+    }
+    rolled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[test]
+    fn the_same_seed_rolls_the_same_clouds() {
+        let spawn = |seed| {
+            let mut rng = XorShiftRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| Cloud::spawn(&mut rng, 6, 6))
+                .collect::<Vec<Cloud>>()
+        };
+        assert_eq!(spawn(3), spawn(3));
+        for cloud in spawn(3) {
+            let x = match cloud.entry {
+                Edge::West => 0,
+                Edge::East => 4,
+            };
+            assert_eq!(cloud.x, x);
+            assert!(cloud.y <= 4);
+        }
+    }
+
+    #[test]
+    fn clouds_drift_one_column_a_round_to_the_far_edge() {
+        let mut cloud = Cloud {
+            kind: CloudKind::Rain,
+            entry: Edge::East,
+            x: 4,
+            y: 2,
+        };
+        let mut path = Vec::new();
+        while cloud.on_board(6) {
+            path.push(cloud.x);
+            cloud = cloud.advanced();
+            assert_eq!(cloud.y, 2);
+        }
+        assert_eq!(path, [4, 3, 2, 1, 0, -1]);
+        // Half out of the board it only covers its inner column
+        let leaving = Cloud { x: -1, ..cloud };
+        assert!(leaving.covers(0, 2) && leaving.covers(0, 3));
+        assert!(!leaving.covers(1, 2));
+
+        let west = Cloud {
+            entry: Edge::West,
+            x: 5,
+            ..cloud
+        };
+        assert!(west.on_board(6));
+        assert!(!west.advanced().on_board(6));
+    }
+
+    #[test]
+    fn a_cloud_covers_two_by_two_tiles() {
+        let cloud = Cloud {
+            kind: CloudKind::Drought,
+            entry: Edge::West,
+            x: 1,
+            y: 3,
+        };
+        let covered = (0..6)
+            .flat_map(|x| (0..6).map(move |y| (x, y)))
+            .filter(|&(x, y)| cloud.covers(x, y))
+            .collect::<Vec<(usize, usize)>>();
+        assert_eq!(covered, [(1, 3), (1, 4), (2, 3), (2, 4)]);
+    }
+
+    #[test]
+    fn rain_wins_over_a_drought_on_the_same_tile() {
+        let drought = Cloud {
+            kind: CloudKind::Drought,
+            entry: Edge::West,
+            x: 0,
+            y: 0,
+        };
+        let rain = Cloud {
+            kind: CloudKind::Rain,
+            x: 1,
+            ..drought
+        };
+        let clouds = [drought, rain];
+        assert_eq!(weather_at(&clouds, 0, 0), Some(CloudKind::Drought));
+        assert_eq!(weather_at(&clouds, 1, 1), Some(CloudKind::Rain));
+        assert_eq!(weather_at(&clouds, 3, 0), None);
+    }
+}
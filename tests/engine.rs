@@ -0,0 +1,96 @@
+use rogue_forest::{
+    action::Action,
+    engine::{self, Engine, GameEvent},
+    State,
+};
+
+fn engine(flags: &[&str], seed: u64) -> Engine {
+    let config = engine::config_from_flags(flags).unwrap();
+    let plants = engine::builtin_plants(&config).unwrap();
+    Engine::new(config, plants, seed)
+}
+
+/// Plants the first card of the hand, then ends rounds until the run is
+/// over, and returns everything that happened.
+fn play_out(engine: &mut Engine) -> Vec<GameEvent> {
+    let mut events = engine.apply(Action::Place);
+    events.extend(engine.apply(Action::Place));
+    for _ in 0..100 {
+        if engine.view().state() == State::GameOver {
+            break;
+        }
+        events.extend(engine.apply(Action::NextRound));
+    }
+    events
+}
+
+#[test]
+fn a_full_game_reports_every_round_and_the_end() {
+    let mut engine = engine(&["--rounds", "5"], 7);
+    let events = play_out(&mut engine);
+
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, GameEvent::Notice(notice) if notice.starts_with("Placed"))));
+    let rounds = events
+        .iter()
+        .filter_map(|event| match event {
+            GameEvent::RoundEnded { round, .. } => Some(*round),
+            _ => None,
+        })
+        .collect::<Vec<u32>>();
+    assert_eq!(rounds, vec![0, 1, 2, 3, 4]);
+    let over = events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::GameOver { .. }))
+        .count();
+    assert_eq!(over, 1);
+    match events.last() {
+        Some(GameEvent::GameOver { score }) => assert_eq!(*score, engine.view().total_points()),
+        last => panic!("the run ended with {:?}", last),
+    }
+    assert_eq!(engine.view().state(), State::GameOver);
+    assert_eq!(engine.view().rounds_left(), Some(0));
+}
+
+#[test]
+fn actions_after_the_end_change_nothing() {
+    let mut engine = engine(&["--rounds", "2"], 1);
+    play_out(&mut engine);
+    assert!(engine.apply(Action::NextRound).is_empty());
+    assert_eq!(engine.view().round(), 2);
+}
+
+#[test]
+fn the_same_seed_plays_the_same_game() {
+    let mut first = engine(&["--rounds", "6"], 42);
+    let mut second = engine(&["--rounds", "6"], 42);
+    assert_eq!(play_out(&mut first), play_out(&mut second));
+}
+
+#[test]
+fn engines_side_by_side_keep_their_own_settings() {
+    let mut small = engine(&["--dim", "3", "--rounds", "2"], 5);
+    let mut large = engine(&["--dim", "9", "--rounds", "4"], 5);
+    assert_eq!((small.view().width(), small.view().height()), (3, 3));
+    assert_eq!((large.view().width(), large.view().height()), (9, 9));
+    play_out(&mut small);
+    large.apply(Action::NextRound);
+    assert_eq!(small.view().state(), State::GameOver);
+    assert_eq!(large.view().state(), State::Choosing);
+    assert_eq!(large.view().rounds_left(), Some(3));
+}
+
+#[test]
+fn events_and_actions_round_trip_through_json() {
+    let mut engine = engine(&["--rounds", "3"], 9);
+    for event in play_out(&mut engine) {
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<GameEvent>(&json).unwrap(), event);
+    }
+    let json = serde_json::to_string(&Action::MoveTo(2, 1)).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Action>(&json).unwrap(),
+        Action::MoveTo(2, 1)
+    );
+}
@@ -0,0 +1,118 @@
+use serde_json::Value;
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+/// A bot's end of `serve --stdio`, with config and saves kept out of the
+/// way of whoever runs the tests.
+struct Bot {
+    home: PathBuf,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Bot {
+    fn spawn(name: &str, flags: &[&str]) -> Bot {
+        let home = env::temp_dir().join(format!(
+            "rogue_forest-serve-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rogue_forest"))
+            .args(flags)
+            .args(["serve", "--stdio"])
+            .env("HOME", &home)
+            .env("XDG_CONFIG_HOME", home.join("config"))
+            .env("XDG_DATA_HOME", home.join("data"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Bot {
+            home,
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    fn read(&mut self) -> Value {
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    fn send(&mut self, line: &str) -> Value {
+        writeln!(self.stdin, "{}", line).unwrap();
+        self.read()
+    }
+
+    fn quit(mut self) {
+        let last = self.send(r#"{"action":"quit"}"#);
+        assert!(last["error"].is_null());
+        assert!(self.child.wait().unwrap().success());
+        let _ = fs::remove_dir_all(&self.home);
+    }
+}
+
+fn error_code(response: &Value) -> Option<&str> {
+    response["error"]["code"].as_str()
+}
+
+#[test]
+fn a_bot_plays_over_the_pipe() {
+    let mut bot = Bot::spawn("play", &["--seed", "1"]);
+    let start = bot.read();
+    assert_eq!(start["version"], 1);
+    assert_eq!(start["state"]["board"], Value::Array(Vec::new()));
+    let hand = start["state"]["hand"].as_array().unwrap().len();
+
+    let placed = bot.send(r#"{"action":"place","x":0,"y":0,"hand_index":0}"#);
+    assert_eq!(error_code(&placed), None);
+    assert_eq!(placed["state"]["board"][0]["x"], 0);
+    assert_eq!(placed["state"]["board"][0]["y"], 0);
+    assert_eq!(placed["state"]["hand"].as_array().unwrap().len(), hand - 1);
+
+    let again = bot.send(r#"{"action":"place","x":0,"y":0,"hand_index":0}"#);
+    assert_eq!(error_code(&again), Some("illegal"));
+    assert_eq!(again["error"]["message"], "Tile occupied");
+    let off = bot.send(r#"{"action":"place","x":9,"y":0,"hand_index":0}"#);
+    assert_eq!(error_code(&off), Some("out_of_range"));
+    assert_eq!(error_code(&bot.send("no json")), Some("parse"));
+
+    let next = bot.send(r#"{"action":"next_round"}"#);
+    assert_eq!(error_code(&next), None);
+    assert_eq!(next["state"]["round"], 1);
+    assert!(next["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|event| event.get("RoundEnded").is_some()));
+    bot.quit();
+}
+
+#[test]
+fn tending_a_spent_turn_leaves_the_round_running() {
+    let mut bot = Bot::spawn("spent", &["--seed", "1", "--difficulty", "easy"]);
+    bot.read();
+    for x in 0..2 {
+        let line = format!(r#"{{"action":"place","x":{},"y":0,"hand_index":0}}"#, x);
+        assert_eq!(error_code(&bot.send(&line)), None);
+    }
+
+    let water = bot.send(r#"{"action":"water","x":0,"y":0}"#);
+    assert_eq!(error_code(&water), Some("illegal"));
+    assert_eq!(water["state"]["round"], 0);
+    assert_eq!(water["state"]["water"], 3);
+    let place = bot.send(r#"{"action":"place","x":2,"y":0,"hand_index":0}"#);
+    assert_eq!(error_code(&place), Some("illegal"));
+    assert_eq!(place["state"]["round"], 0);
+    bot.quit();
+}